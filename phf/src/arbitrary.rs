@@ -0,0 +1,64 @@
+//! Sampling static tables from [`arbitrary`] input, for fuzz
+//! targets.
+//!
+//! ```
+//! use arbitrary::Unstructured;
+//! use phf::phf_map;
+//!
+//! static STATUS: phf::Map<&'static str, u16> = phf_map! {
+//!     "ok" => 200,
+//!     "not found" => 404,
+//! };
+//!
+//! let mut u = Unstructured::new(&[7, 1, 2, 3, 4, 5, 6, 7, 8]);
+//! let (name, _) = phf::arbitrary::entry(&STATUS, &mut u)?;
+//! assert!(STATUS.contains_key(name));
+//! let _almost_a_name: String = phf::arbitrary::near_miss_key(&STATUS, &mut u)?;
+//! # Ok::<(), arbitrary::Error>(())
+//! ```
+//!
+//! All functions return [`Error::EmptyChoose`](::arbitrary::Error::EmptyChoose)
+//! for empty tables.
+//!
+//! Requires the `arbitrary` feature.
+use ::arbitrary::{Result, Unstructured};
+
+use crate::near_miss::NearMiss;
+use crate::{Map, Set};
+
+/// Returns one of the keys of `map`.
+pub fn key<'m, K, V>(map: &'m Map<K, V>, u: &mut Unstructured<'_>) -> Result<&'m K> {
+    entry(map, u).map(|e| e.0)
+}
+
+/// Returns one of the values of `map`.
+pub fn value<'m, K, V>(map: &'m Map<K, V>, u: &mut Unstructured<'_>) -> Result<&'m V> {
+    entry(map, u).map(|e| e.1)
+}
+
+/// Returns one of the entries of `map`.
+pub fn entry<'m, K, V>(map: &'m Map<K, V>, u: &mut Unstructured<'_>) -> Result<(&'m K, &'m V)> {
+    u.choose(map.entries).map(|(k, v)| (k, v))
+}
+
+/// Returns one of the elements of `set`.
+pub fn element<'s, T>(set: &'s Set<T>, u: &mut Unstructured<'_>) -> Result<&'s T> {
+    key(&set.map, u)
+}
+
+/// Returns a [near miss](NearMiss) of one of the keys of `map`.
+pub fn near_miss_key<K, V>(map: &Map<K, V>, u: &mut Unstructured<'_>) -> Result<K::Owned>
+where
+    K: NearMiss,
+{
+    let key = key(map, u)?;
+    Ok(key.near_miss(u.arbitrary()?))
+}
+
+/// Returns a [near miss](NearMiss) of one of the elements of `set`.
+pub fn near_miss_element<T>(set: &Set<T>, u: &mut Unstructured<'_>) -> Result<T::Owned>
+where
+    T: NearMiss,
+{
+    near_miss_key(&set.map, u)
+}
@@ -0,0 +1,568 @@
+//! Maps built at runtime into fixed-size buffers, without allocating.
+//!
+//! `phf_generator` needs `std` to build a table, which rules it out for
+//! firmware that only learns its keys at runtime. [`build_in_place`] runs the
+//! same CHD construction over buffers the caller provides, and [`ArrayMap`]
+//! wraps it for tables whose size is known at compile time.
+//!
+//! These maps always use the CHD layout, whether or not the `ptrhash` feature
+//! is enabled.
+//!
+//! ```
+//! use phf::array_map::{self, Scratch};
+//!
+//! let mut entries = [("red", 0xff0000), ("green", 0x00ff00), ("blue", 0x0000ff)];
+//! let mut disps = [(0, 0); array_map::disps_len(3)];
+//! let mut scratch = [Scratch::EMPTY; 3];
+//!
+//! let map = array_map::build_in_place(&mut entries, &mut disps, &mut scratch).unwrap();
+//! assert_eq!(map.get("green"), Some(&0x00ff00));
+//! assert_eq!(map.get("black"), None);
+//! ```
+use core::fmt;
+use phf_shared::{HashKey, PhfEq, PhfHash};
+
+#[cfg(not(feature = "ptrhash"))]
+use crate::Map;
+use crate::map::{Entries, Keys, Values};
+
+// Matches the bucket size `phf_generator` uses.
+const LAMBDA: usize = 3;
+
+// The number of hash keys tried before giving up. `phf_generator` tries until
+// it succeeds, but a table that fails this many is almost certainly broken.
+const ATTEMPTS: usize = 64;
+
+const FIXED_SEED: u64 = 1234567890;
+const EMPTY_SLOT: u32 = u32::MAX;
+
+/// Returns the number of displacements [`build_in_place`] needs for `len`
+/// entries.
+pub const fn disps_len(len: usize) -> usize {
+    len.div_ceil(LAMBDA)
+}
+
+/// Working memory for [`build_in_place`], one per entry.
+///
+/// Its contents are only meaningful while a map is being built, so one
+/// buffer can be reused for any number of builds.
+#[derive(Debug, Clone, Copy)]
+pub struct Scratch {
+    // The hashes of the entry at this index.
+    g: u32,
+    f1: u32,
+    f2: u32,
+    // The entries grouped by bucket.
+    bucket_key: u32,
+    // The range of `bucket_key` holding the bucket at this index.
+    bucket_start: u32,
+    bucket_len: u32,
+    // The entry placed in the slot at this index.
+    slot: u32,
+    // The last placement attempt to claim the slot at this index.
+    generation: u32,
+}
+
+impl Scratch {
+    /// Unused scratch space, for initializing buffers.
+    pub const EMPTY: Self = Self {
+        g: 0,
+        f1: 0,
+        f2: 0,
+        bucket_key: 0,
+        bucket_start: 0,
+        bucket_len: 0,
+        slot: EMPTY_SLOT,
+        generation: 0,
+    };
+}
+
+impl Default for Scratch {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+/// The error returned when a map can't be built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// Two entries have equal keys.
+    DuplicateKey,
+    /// The displacement or scratch buffer is shorter than [`disps_len`] or
+    /// the number of entries.
+    BufferTooSmall,
+    /// There are more entries than a map can index.
+    TooManyEntries,
+    /// No hash key placed every entry in its own slot.
+    NoHashFound,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BuildError::DuplicateKey => "two entries have equal keys",
+            BuildError::BufferTooSmall => "a buffer is too small for the entries",
+            BuildError::TooManyEntries => "there are too many entries to index",
+            BuildError::NoHashFound => "no perfect hash function was found for the keys",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BuildError {}
+
+/// Builds a map over `entries`, storing the hash parameters in `disps`.
+///
+/// `disps` must hold at least [`disps_len`]`(entries.len())` elements and
+/// `scratch` at least `entries.len()`. On success `entries` is reordered into
+/// the slots the returned map looks them up in; on failure it is left
+/// untouched.
+pub fn build_in_place<'a, K, V>(
+    entries: &'a mut [(K, V)],
+    disps: &'a mut [(u32, u32)],
+    scratch: &mut [Scratch],
+) -> Result<SliceMap<'a, K, V>, BuildError>
+where
+    K: PhfHash + Eq,
+{
+    let len = entries.len();
+    let buckets_len = disps_len(len);
+    if len >= EMPTY_SLOT as usize {
+        return Err(BuildError::TooManyEntries);
+    }
+    if disps.len() < buckets_len || scratch.len() < len {
+        return Err(BuildError::BufferTooSmall);
+    }
+    let disps = &mut disps[..buckets_len];
+    let scratch = &mut scratch[..len];
+
+    let mut seed = FIXED_SEED;
+    for _ in 0..ATTEMPTS {
+        let key = split_mix(&mut seed);
+        for (s, (k, _)) in scratch.iter_mut().zip(&*entries) {
+            let hashes = phf_shared::hash(k, &key);
+            *s = Scratch {
+                g: hashes.g,
+                f1: hashes.f1,
+                f2: hashes.f2,
+                ..Scratch::EMPTY
+            };
+        }
+
+        if try_generate_hash(entries, disps, scratch)? {
+            permute(entries, scratch);
+            return Ok(SliceMap {
+                key,
+                disps,
+                entries,
+            });
+        }
+    }
+    Err(BuildError::NoHashFound)
+}
+
+// Advances a SplitMix64 generator, which is enough to vary the hash keys tried.
+fn split_mix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+// Places every entry with the hashes in `scratch`, like `phf_generator` does,
+// and returns whether it succeeded.
+fn try_generate_hash<K: Eq, V>(
+    entries: &[(K, V)],
+    disps: &mut [(u32, u32)],
+    scratch: &mut [Scratch],
+) -> Result<bool, BuildError> {
+    let table_len = scratch.len() as u32;
+    let buckets_len = disps.len() as u32;
+    if table_len == 0 {
+        return Ok(true);
+    }
+
+    // Group the entries by bucket in `bucket_key`.
+    for i in 0..scratch.len() {
+        let bucket = (scratch[i].g % buckets_len) as usize;
+        scratch[bucket].bucket_len += 1;
+    }
+    let mut start = 0;
+    let mut max_bucket_len = 0;
+    for s in scratch[..buckets_len as usize].iter_mut() {
+        s.bucket_start = start;
+        start += s.bucket_len;
+        max_bucket_len = max_bucket_len.max(s.bucket_len);
+        s.bucket_len = 0;
+    }
+    for i in 0..scratch.len() {
+        let bucket = (scratch[i].g % buckets_len) as usize;
+        let bucket = &mut scratch[bucket];
+        let cursor = (bucket.bucket_start + bucket.bucket_len) as usize;
+        bucket.bucket_len += 1;
+        scratch[cursor].bucket_key = i as u32;
+    }
+
+    // Entries with the same hashes always collide, so there's no point
+    // searching for displacements. Equal keys can't be placed with any hash
+    // key, so they're reported instead.
+    for bucket in 0..buckets_len as usize {
+        let start = scratch[bucket].bucket_start as usize;
+        let end = start + scratch[bucket].bucket_len as usize;
+        for a in start..end {
+            for b in a + 1..end {
+                let (a, b) = (
+                    scratch[a].bucket_key as usize,
+                    scratch[b].bucket_key as usize,
+                );
+                if (scratch[a].f1, scratch[a].f2) == (scratch[b].f1, scratch[b].f2) {
+                    if entries[a].0 == entries[b].0 {
+                        return Err(BuildError::DuplicateKey);
+                    }
+                    return Ok(false);
+                }
+            }
+        }
+    }
+
+    let mut generation = 0u32;
+
+    // Place the largest buckets first, in the order `phf_generator` does.
+    for bucket_len in (1..=max_bucket_len).rev() {
+        'buckets: for bucket in 0..buckets_len as usize {
+            if scratch[bucket].bucket_len != bucket_len {
+                continue;
+            }
+            let start = scratch[bucket].bucket_start as usize;
+            let keys = start..start + bucket_len as usize;
+
+            for d1 in 0..table_len {
+                'disps: for d2 in 0..table_len {
+                    if generation == u32::MAX {
+                        scratch.iter_mut().for_each(|s| s.generation = 0);
+                        generation = 0;
+                    }
+                    generation += 1;
+
+                    for key in keys.clone() {
+                        let idx = slot(
+                            &scratch[scratch[key].bucket_key as usize],
+                            d1,
+                            d2,
+                            table_len,
+                        );
+                        if scratch[idx].slot != EMPTY_SLOT || scratch[idx].generation == generation
+                        {
+                            continue 'disps;
+                        }
+                        scratch[idx].generation = generation;
+                    }
+
+                    disps[bucket] = (d1, d2);
+                    for key in keys.clone() {
+                        let entry = scratch[key].bucket_key;
+                        let idx = slot(&scratch[entry as usize], d1, d2, table_len);
+                        scratch[idx].slot = entry;
+                    }
+                    continue 'buckets;
+                }
+            }
+
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn slot(hashes: &Scratch, d1: u32, d2: u32, table_len: u32) -> usize {
+    (phf_shared::displace(hashes.f1, hashes.f2, d1, d2) % table_len) as usize
+}
+
+// Moves each entry to the slot it was placed in, following the cycles of the
+// permutation so that no extra buffer is needed.
+fn permute<K, V>(entries: &mut [(K, V)], scratch: &mut [Scratch]) {
+    for start in 0..entries.len() {
+        let mut cur = start;
+        while scratch[cur].slot != EMPTY_SLOT {
+            let src = scratch[cur].slot as usize;
+            scratch[cur].slot = EMPTY_SLOT;
+            if src == start {
+                break;
+            }
+            entries.swap(cur, src);
+            cur = src;
+        }
+    }
+}
+
+/// A map over buffers filled in by [`build_in_place`].
+pub struct SliceMap<'a, K, V> {
+    key: HashKey,
+    disps: &'a [(u32, u32)],
+    entries: &'a [(K, V)],
+}
+
+impl<'a, K, V> Clone for SliceMap<'a, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K, V> Copy for SliceMap<'a, K, V> {}
+
+impl<'a, K, V> fmt::Debug for SliceMap<'a, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_map().entries(self.entries()).finish()
+    }
+}
+
+impl<'a, K, V> SliceMap<'a, K, V> {
+    /// Returns the number of entries in the map.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the map is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Determines if `key` is in the map.
+    pub fn contains_key<T>(&self, key: &T) -> bool
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns a reference to the value that `key` maps to.
+    pub fn get<T>(&self, key: &T) -> Option<&'a V>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.get_entry(key).map(|e| e.1)
+    }
+
+    /// Returns a reference to the map's own instance of the given key.
+    pub fn get_key<T>(&self, key: &T) -> Option<&'a K>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.get_entry(key).map(|e| e.0)
+    }
+
+    /// Like `get`, but returns both the key and the value.
+    pub fn get_entry<T>(&self, key: &T) -> Option<(&'a K, &'a V)>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        if self.is_empty() {
+            return None;
+        }
+        let hashes = phf_shared::hash(key, &self.key);
+        let index = phf_shared::get_index(&hashes, self.disps, self.len());
+        let (k, v) = &self.entries[index as usize];
+        if k.phf_eq(key) { Some((k, v)) } else { None }
+    }
+
+    /// Returns an iterator over the key/value pairs in the map.
+    ///
+    /// Entries are returned in the order `build_in_place` left them in.
+    pub fn entries(&self) -> Entries<'a, K, V> {
+        Entries {
+            iter: self.entries.iter(),
+        }
+    }
+
+    /// Returns an iterator over the keys in the map.
+    pub fn keys(&self) -> Keys<'a, K, V> {
+        Keys {
+            iter: self.entries(),
+        }
+    }
+
+    /// Returns an iterator over the values in the map.
+    pub fn values(&self) -> Values<'a, K, V> {
+        Values {
+            iter: self.entries(),
+        }
+    }
+}
+
+impl<K, V> SliceMap<'static, K, V> {
+    /// Converts a map built into `'static` buffers into a [`Map`].
+    ///
+    /// Not available with the `ptrhash` feature, since `Map` then uses a
+    /// different layout.
+    #[cfg(not(feature = "ptrhash"))]
+    pub const fn into_map(self) -> Map<K, V> {
+        Map::from_raw_parts(self.key, self.disps, self.entries)
+    }
+}
+
+/// A map of exactly `N` entries, built at runtime without allocating.
+///
+/// The map stores `D` displacements, of which it uses [`disps_len`]`(N)`.
+/// [`ArrayMap::new`] reserves `N`, about three times as many as needed, so
+/// that the type can be written without computing `D`; maps where the space
+/// matters can be built with [`ArrayMap::with_disps`] instead.
+///
+/// # Example
+///
+/// ```
+/// use phf::ArrayMap;
+///
+/// let map = ArrayMap::new([(0x10u8, "reset"), (0x20, "status"), (0x30, "data")]).unwrap();
+/// assert_eq!(map.get(&0x20), Some(&"status"));
+/// assert_eq!(map.get(&0x40), None);
+/// ```
+pub struct ArrayMap<K, V, const N: usize, const D: usize = N> {
+    key: HashKey,
+    // Only the first `disps_len(N)` are used.
+    disps: [(u32, u32); D],
+    entries: [(K, V); N],
+}
+
+impl<K, V, const N: usize, const D: usize> fmt::Debug for ArrayMap<K, V, N, D>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice_map().fmt(fmt)
+    }
+}
+
+impl<K, V, const N: usize> ArrayMap<K, V, N> {
+    /// Builds a map over `entries`, with room for `N` displacements.
+    ///
+    /// The scratch space is taken from the stack.
+    pub fn new(entries: [(K, V); N]) -> Result<Self, BuildError>
+    where
+        K: PhfHash + Eq,
+    {
+        Self::with_disps(entries)
+    }
+}
+
+impl<K, V, const N: usize, const D: usize> ArrayMap<K, V, N, D> {
+    /// Builds a map over `entries`, with room for `D` displacements.
+    ///
+    /// `D` must be at least [`disps_len`]`(N)`, which is checked at compile
+    /// time. The scratch space is taken from the stack.
+    ///
+    /// ```
+    /// use phf::ArrayMap;
+    /// use phf::array_map::disps_len;
+    ///
+    /// type Registers = ArrayMap<u8, &'static str, 3, { disps_len(3) }>;
+    ///
+    /// let map = Registers::with_disps([(0x10, "reset"), (0x20, "status"), (0x30, "data")]);
+    /// assert_eq!(map.unwrap().get(&0x30), Some(&"data"));
+    /// ```
+    pub fn with_disps(entries: [(K, V); N]) -> Result<Self, BuildError>
+    where
+        K: PhfHash + Eq,
+    {
+        const {
+            assert!(
+                D >= disps_len(N),
+                "`ArrayMap` needs at least `disps_len(N)` displacements"
+            )
+        };
+        let mut map = ArrayMap {
+            key: 0,
+            disps: [(0, 0); D],
+            entries,
+        };
+        let mut scratch = [Scratch::EMPTY; N];
+        map.key = build_in_place(&mut map.entries, &mut map.disps, &mut scratch)?.key;
+        Ok(map)
+    }
+
+    /// Returns a view of the map that borrows its buffers.
+    pub fn as_slice_map(&self) -> SliceMap<'_, K, V> {
+        SliceMap {
+            key: self.key,
+            disps: &self.disps[..disps_len(N)],
+            entries: &self.entries,
+        }
+    }
+
+    /// Returns the number of entries in the map.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns true if the map is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Determines if `key` is in the map.
+    pub fn contains_key<T>(&self, key: &T) -> bool
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.as_slice_map().contains_key(key)
+    }
+
+    /// Returns a reference to the value that `key` maps to.
+    pub fn get<T>(&self, key: &T) -> Option<&V>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.as_slice_map().get(key)
+    }
+
+    /// Returns a reference to the map's own instance of the given key.
+    pub fn get_key<T>(&self, key: &T) -> Option<&K>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.as_slice_map().get_key(key)
+    }
+
+    /// Like `get`, but returns both the key and the value.
+    pub fn get_entry<T>(&self, key: &T) -> Option<(&K, &V)>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.as_slice_map().get_entry(key)
+    }
+
+    /// Returns an iterator over the key/value pairs in the map.
+    ///
+    /// Entries are returned in an arbitrary but fixed order.
+    pub fn entries(&self) -> Entries<'_, K, V> {
+        self.as_slice_map().entries()
+    }
+
+    /// Returns an iterator over the keys in the map.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        self.as_slice_map().keys()
+    }
+
+    /// Returns an iterator over the values in the map.
+    pub fn values(&self) -> Values<'_, K, V> {
+        self.as_slice_map().values()
+    }
+}
@@ -0,0 +1,561 @@
+//! A map that keeps the perfect hash lookup of [`Map`](crate::Map) but can be
+//! modified.
+//!
+//! [`Map`] stores its entries in a perfectly hashed table built by
+//! `phf_generator`. Inserted keys that aren't in the table go to a small
+//! overflow area and removed keys leave tombstones, and once either grows
+//! past a fraction of the table the table is rebuilt from every live entry.
+//! Keys that are in the table are found with the same hash and comparison as
+//! [`phf::Map::get`](crate::Map::get), so a mostly-static map with a trickle
+//! of updates is nearly as fast to read as a static one.
+//!
+//! Requires the `dynamic` feature.
+//!
+//! ```
+//! use phf::dynamic::Map;
+//!
+//! let mut map = [("a", 1), ("b", 2)].into_iter().collect::<Map<_, _>>();
+//! map.insert("c", 3);
+//! map.remove("a");
+//!
+//! assert_eq!(map.get("a"), None);
+//! assert_eq!(map.get("c"), Some(&3));
+//! assert_eq!(map.len(), 2);
+//! ```
+use core::fmt;
+use core::iter::FusedIterator;
+use core::mem;
+use core::slice;
+use phf_shared::{HashKey, Hashes, PhfEq, PhfHash};
+use std::collections::hash_map::{self, HashMap};
+use std::vec::Vec;
+
+// The overflow area and tombstones may each grow to this fraction of the
+// table, or to `MIN_REBUILD` entries for small tables, before it's rebuilt.
+const OVERFLOW_DIVISOR: usize = 8;
+const TOMBSTONE_DIVISOR: usize = 4;
+const MIN_REBUILD: usize = 16;
+
+/// A perfectly hashed map that supports insertion and removal.
+///
+/// See the [module documentation](self) for how it works.
+pub struct Map<K, V> {
+    key: HashKey,
+    disps: Vec<(u32, u32)>,
+    // The perfectly hashed table. A removed entry keeps its key, so inserting
+    // it again reuses its slot.
+    slots: Vec<(K, Option<V>)>,
+    tombstones: usize,
+    // Entries that aren't in the table, by `phf_shared::fingerprint`.
+    overflow: HashMap<u64, Vec<(K, V)>>,
+    overflow_len: usize,
+}
+
+impl<K, V> Default for Map<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> fmt::Debug for Map<K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_map().entries(self.entries()).finish()
+    }
+}
+
+impl<K, V> Clone for Map<K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key,
+            disps: self.disps.clone(),
+            slots: self.slots.clone(),
+            tombstones: self.tombstones,
+            overflow: self.overflow.clone(),
+            overflow_len: self.overflow_len,
+        }
+    }
+}
+
+impl<K, V> Map<K, V> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self {
+            key: 0,
+            disps: Vec::new(),
+            slots: Vec::new(),
+            tombstones: 0,
+            overflow: HashMap::new(),
+            overflow_len: 0,
+        }
+    }
+
+    /// Returns the number of entries in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.tombstones + self.overflow_len
+    }
+
+    /// Returns true if the map is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of entries that are waiting in the overflow area
+    /// for the next rebuild.
+    pub fn overflow_len(&self) -> usize {
+        self.overflow_len
+    }
+
+    /// Determines if `key` is in the map.
+    pub fn contains_key<T>(&self, key: &T) -> bool
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns a reference to the value that `key` maps to.
+    pub fn get<T>(&self, key: &T) -> Option<&V>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.get_entry(key).map(|e| e.1)
+    }
+
+    /// Returns a mutable reference to the value that `key` maps to.
+    pub fn get_mut<T>(&mut self, key: &T) -> Option<&mut V>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        let hashes = phf_shared::hash(key, &self.key);
+        match self.find(&hashes, |k| k.phf_eq(key)) {
+            Found::Slot(index) => self.slots[index].1.as_mut(),
+            Found::Overflow(index) => self
+                .overflow
+                .get_mut(&phf_shared::fingerprint(&hashes))
+                .map(|bucket| &mut bucket[index].1),
+            Found::Tombstone(_) | Found::None => None,
+        }
+    }
+
+    /// Returns a reference to the map's own instance of the given key.
+    pub fn get_key<T>(&self, key: &T) -> Option<&K>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.get_entry(key).map(|e| e.0)
+    }
+
+    /// Like `get`, but returns both the key and the value.
+    pub fn get_entry<T>(&self, key: &T) -> Option<(&K, &V)>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        let hashes = phf_shared::hash(key, &self.key);
+        match self.find(&hashes, |k| k.phf_eq(key)) {
+            Found::Slot(index) => {
+                let (k, v) = &self.slots[index];
+                v.as_ref().map(|v| (k, v))
+            }
+            Found::Overflow(index) => {
+                let (k, v) = &self.overflow[&phf_shared::fingerprint(&hashes)][index];
+                Some((k, v))
+            }
+            Found::Tombstone(_) | Found::None => None,
+        }
+    }
+
+    /// Removes `key` from the map, returning its value if it was present.
+    ///
+    /// # Rebuilds
+    ///
+    /// The removed entry leaves a tombstone in the table. When there are too
+    /// many, the table is rebuilt before returning, which takes time linear in
+    /// the length of the map rather than the usual constant time. Latency
+    /// sensitive callers can [`rebuild`](Self::rebuild) at a convenient time
+    /// instead, which resets the count.
+    pub fn remove<T>(&mut self, key: &T) -> Option<V>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T> + PhfHash,
+    {
+        let hashes = phf_shared::hash(key, &self.key);
+        let value = match self.find(&hashes, |k| k.phf_eq(key)) {
+            Found::Slot(index) => {
+                self.tombstones += 1;
+                self.slots[index].1.take()
+            }
+            Found::Overflow(index) => {
+                let fingerprint = phf_shared::fingerprint(&hashes);
+                let bucket = self.overflow.get_mut(&fingerprint)?;
+                let (_, value) = bucket.swap_remove(index);
+                if bucket.is_empty() {
+                    self.overflow.remove(&fingerprint);
+                }
+                self.overflow_len -= 1;
+                Some(value)
+            }
+            Found::Tombstone(_) | Found::None => return None,
+        };
+        if self.tombstones > rebuild_threshold(self.slots.len(), TOMBSTONE_DIVISOR) {
+            self.rebuild();
+        }
+        value
+    }
+
+    /// Inserts `key` with `value`, returning the previous value of `key` if it
+    /// was present.
+    ///
+    /// # Rebuilds
+    ///
+    /// A key that isn't in the table goes to the overflow area. When that
+    /// grows too large, the table is rebuilt before returning, which takes
+    /// time linear in the length of the map rather than the usual constant
+    /// time. Latency sensitive callers can [`rebuild`](Self::rebuild) at a
+    /// convenient time instead, which empties the overflow area.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V>
+    where
+        K: PhfHash + Eq,
+    {
+        let previous = self.insert_entry(key, value);
+        if self.overflow_len > rebuild_threshold(self.slots.len(), OVERFLOW_DIVISOR) {
+            self.rebuild();
+        }
+        previous
+    }
+
+    // Inserts an entry without rebuilding the table.
+    fn insert_entry(&mut self, key: K, value: V) -> Option<V>
+    where
+        K: PhfHash + Eq,
+    {
+        let hashes = phf_shared::hash(&key, &self.key);
+        match self.find(&hashes, |k| *k == key) {
+            Found::Slot(index) => return self.slots[index].1.replace(value),
+            Found::Tombstone(index) => {
+                self.tombstones -= 1;
+                self.slots[index].1 = Some(value);
+                return None;
+            }
+            Found::Overflow(index) => {
+                let bucket = self
+                    .overflow
+                    .get_mut(&phf_shared::fingerprint(&hashes))
+                    .unwrap();
+                return Some(mem::replace(&mut bucket[index].1, value));
+            }
+            Found::None => {}
+        }
+
+        self.overflow
+            .entry(phf_shared::fingerprint(&hashes))
+            .or_default()
+            .push((key, value));
+        self.overflow_len += 1;
+        None
+    }
+
+    /// Rebuilds the perfectly hashed table from every entry, emptying the
+    /// overflow area and dropping tombstones.
+    ///
+    /// Maps rebuild themselves as they're modified, but this can be called to
+    /// do it at a convenient time instead, such as after a batch of updates.
+    pub fn rebuild(&mut self)
+    where
+        K: PhfHash,
+    {
+        let mut entries = Vec::with_capacity(self.len());
+        entries.extend(
+            mem::take(&mut self.slots)
+                .into_iter()
+                .filter_map(|(k, v)| Some((k, v?))),
+        );
+        entries.extend(self.overflow.drain().flat_map(|(_, bucket)| bucket));
+        self.tombstones = 0;
+        self.overflow_len = 0;
+
+        if entries.is_empty() {
+            *self = Self::new();
+            return;
+        }
+
+        // Every key is distinct: overflow entries are only added for keys
+        // that aren't in the table or the overflow area.
+        let state = phf_generator::generate_hash_with_hash_fn(&entries, |(k, _), key| {
+            phf_shared::hash(k, key)
+        });
+        let mut entries = entries.into_iter().map(Some).collect::<Vec<_>>();
+        self.key = state.key;
+        self.disps = state.disps;
+        self.slots = state
+            .map
+            .iter()
+            .map(|&i| {
+                let (k, v) = entries[i].take().unwrap();
+                (k, Some(v))
+            })
+            .collect();
+    }
+
+    /// Returns an iterator over the key/value pairs in the map.
+    ///
+    /// Entries are returned in an arbitrary order, which changes when the map
+    /// is modified.
+    pub fn entries(&self) -> Entries<'_, K, V> {
+        Entries {
+            slots: self.slots.iter(),
+            overflow: self.overflow.values(),
+            bucket: [].iter(),
+            len: self.len(),
+        }
+    }
+
+    /// Returns an iterator over the keys in the map.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys {
+            iter: self.entries(),
+        }
+    }
+
+    /// Returns an iterator over the values in the map.
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values {
+            iter: self.entries(),
+        }
+    }
+
+    fn find(&self, hashes: &Hashes, eq: impl Fn(&K) -> bool) -> Found {
+        if !self.slots.is_empty() {
+            let index = phf_shared::get_index(hashes, &self.disps, self.slots.len()) as usize;
+            let (k, v) = &self.slots[index];
+            if eq(k) {
+                return if v.is_some() {
+                    Found::Slot(index)
+                } else {
+                    Found::Tombstone(index)
+                };
+            }
+        }
+        if self.overflow_len == 0 {
+            return Found::None;
+        }
+        self.overflow
+            .get(&phf_shared::fingerprint(hashes))
+            .and_then(|bucket| bucket.iter().position(|(k, _)| eq(k)))
+            .map_or(Found::None, Found::Overflow)
+    }
+}
+
+enum Found {
+    Slot(usize),
+    Tombstone(usize),
+    Overflow(usize),
+    None,
+}
+
+fn rebuild_threshold(table_len: usize, divisor: usize) -> usize {
+    (table_len / divisor).max(MIN_REBUILD)
+}
+
+impl<K, V> FromIterator<(K, V)> for Map<K, V>
+where
+    K: PhfHash + Eq,
+{
+    /// Collects every entry, then builds the table once.
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (k, v) in iter {
+            map.insert_entry(k, v);
+        }
+        map.rebuild();
+        map
+    }
+}
+
+impl<K, V> Extend<(K, V)> for Map<K, V>
+where
+    K: PhfHash + Eq,
+{
+    /// Inserts every entry with [`insert`](Map::insert), rebuilding the table
+    /// whenever it would.
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a Map<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Entries<'a, K, V>;
+
+    fn into_iter(self) -> Entries<'a, K, V> {
+        self.entries()
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, K, V> ::arbitrary::Arbitrary<'a> for Map<K, V>
+where
+    K: ::arbitrary::Arbitrary<'a> + PhfHash + Eq,
+    V: ::arbitrary::Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Self> {
+        u.arbitrary_iter()?.collect()
+    }
+
+    fn arbitrary_take_rest(u: ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Self> {
+        u.arbitrary_take_rest_iter()?.collect()
+    }
+}
+
+/// An iterator over the key/value pairs in a dynamic `Map`.
+pub struct Entries<'a, K, V> {
+    slots: slice::Iter<'a, (K, Option<V>)>,
+    overflow: hash_map::Values<'a, u64, Vec<(K, V)>>,
+    bucket: slice::Iter<'a, (K, V)>,
+    len: usize,
+}
+
+impl<'a, K, V> Clone for Entries<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            slots: self.slots.clone(),
+            overflow: self.overflow.clone(),
+            bucket: self.bucket.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for Entries<'a, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Entries<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        let entry = self
+            .slots
+            .by_ref()
+            .find_map(|(k, v)| Some((k, v.as_ref()?)))
+            .or_else(|| {
+                loop {
+                    if let Some((k, v)) = self.bucket.next() {
+                        return Some((k, v));
+                    }
+                    self.bucket = self.overflow.next()?.iter();
+                }
+            })?;
+        self.len -= 1;
+        Some(entry)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Entries<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Entries<'a, K, V> {}
+
+/// An iterator over the keys in a dynamic `Map`.
+pub struct Keys<'a, K, V> {
+    iter: Entries<'a, K, V>,
+}
+
+impl<'a, K, V> Clone for Keys<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for Keys<'a, K, V>
+where
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.iter.next().map(|e| e.0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Keys<'a, K, V> {}
+
+/// An iterator over the values in a dynamic `Map`.
+pub struct Values<'a, K, V> {
+    iter: Entries<'a, K, V>,
+}
+
+impl<'a, K, V> Clone for Values<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for Values<'a, K, V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        self.iter.next().map(|e| e.1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Values<'a, K, V> {}
@@ -0,0 +1,227 @@
+//! An immutable approximate-membership filter constructed at compile time.
+use core::fmt;
+use phf_shared::{HashKey, PhfHash};
+
+use crate::lookup::{self, Params};
+
+/// An immutable approximate-membership filter constructed at compile time.
+///
+/// A `Filter` stores a small fingerprint of each key at the index the perfect
+/// hash function assigns it, and none of the keys themselves. Lookups of keys
+/// that were added always succeed; lookups of other keys wrongly succeed with
+/// a probability of about 2<sup>-bits</sup>, where `bits` is the width of the
+/// fingerprint type `F`.
+///
+/// Keys must be looked up with a type that hashes like the one the filter was
+/// built from, e.g. `str` for `&'static str` keys.
+///
+/// ## Note
+///
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`Filter::from_raw_parts`] instead.
+#[cfg(not(feature = "ptrhash"))]
+pub struct Filter<F: 'static = u16> {
+    #[doc(hidden)]
+    pub key: HashKey,
+    #[doc(hidden)]
+    pub disps: &'static [(u32, u32)],
+    #[doc(hidden)]
+    pub fingerprints: &'static [F],
+}
+
+/// An immutable approximate-membership filter constructed at compile time.
+///
+/// A `Filter` stores a small fingerprint of each key at the index the perfect
+/// hash function assigns it, and none of the keys themselves. Lookups of keys
+/// that were added always succeed; lookups of other keys wrongly succeed with
+/// a probability of about 2<sup>-bits</sup>, where `bits` is the width of the
+/// fingerprint type `F`.
+///
+/// Keys must be looked up with a type that hashes like the one the filter was
+/// built from, e.g. `str` for `&'static str` keys.
+///
+/// ## Note
+///
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`Filter::from_raw_parts`] instead.
+#[cfg(feature = "ptrhash")]
+pub struct Filter<F: 'static = u16> {
+    #[doc(hidden)]
+    pub key: HashKey,
+    #[doc(hidden)]
+    pub pilots: &'static [u8],
+    #[doc(hidden)]
+    pub remap: &'static [u32],
+    #[doc(hidden)]
+    pub fingerprints: &'static [F],
+}
+
+/// An unsigned integer type used as a [`Filter`] fingerprint.
+///
+/// Wider fingerprints lower the false-positive rate at the cost of space.
+pub trait Fingerprint: Copy + Eq + sealed::Sealed {
+    /// The width of the fingerprint in bits.
+    const BITS: u32;
+
+    /// Truncates fingerprint bits to this type.
+    fn from_bits(bits: u64) -> Self;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! fingerprint_impl {
+    ($($t:ty),*) => {
+        $(
+            impl sealed::Sealed for $t {}
+
+            impl Fingerprint for $t {
+                const BITS: u32 = <$t>::BITS;
+
+                #[inline]
+                fn from_bits(bits: u64) -> Self {
+                    bits as $t
+                }
+            }
+        )*
+    };
+}
+
+fingerprint_impl!(u8, u16, u32);
+
+impl<F> fmt::Debug for Filter<F> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Filter")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> Filter<F> {
+    /// Creates a filter from the hash parameters computed by `phf_generator`
+    /// and the fingerprints of the keys, in slot order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parts are inconsistent, which fails compilation when the
+    /// filter initializes a `static` or `const`.
+    #[cfg(not(feature = "ptrhash"))]
+    pub const fn from_raw_parts(
+        key: HashKey,
+        disps: &'static [(u32, u32)],
+        fingerprints: &'static [F],
+    ) -> Self {
+        lookup::check_params(disps, fingerprints.len());
+        Filter {
+            key,
+            disps,
+            fingerprints,
+        }
+    }
+
+    /// Creates a filter from the hash parameters computed by `phf_generator`
+    /// and the fingerprints of the keys, in slot order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parts are inconsistent, which fails compilation when the
+    /// filter initializes a `static` or `const`.
+    #[cfg(feature = "ptrhash")]
+    pub const fn from_raw_parts(
+        key: HashKey,
+        pilots: &'static [u8],
+        remap: &'static [u32],
+        fingerprints: &'static [F],
+    ) -> Self {
+        lookup::check_params(pilots, remap, fingerprints.len());
+        Filter {
+            key,
+            pilots,
+            remap,
+            fingerprints,
+        }
+    }
+
+    /// Returns the number of keys the `Filter` was built from.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.fingerprints.len()
+    }
+
+    /// Returns true if the `Filter` was built from no keys.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<F: Fingerprint> Filter<F> {
+    /// Returns the probability that `maybe_contains` returns true for a key
+    /// the filter was not built from.
+    pub fn false_positive_rate(&self) -> f64 {
+        if self.is_empty() {
+            0.0
+        } else {
+            1.0 / (1u64 << F::BITS) as f64
+        }
+    }
+
+    /// Returns true if `key` may have been one of the keys the filter was
+    /// built from, and false if it definitely was not.
+    ///
+    /// ```
+    /// use phf::{Filter, phf_filter};
+    ///
+    /// static DENY: Filter<u32> = phf_filter! {
+    ///     "evil.example",
+    ///     "spam.example",
+    /// };
+    ///
+    /// assert!(DENY.maybe_contains("evil.example"));
+    /// assert!(!DENY.maybe_contains("rust-lang.org"));
+    /// ```
+    pub fn maybe_contains<T>(&self, key: &T) -> bool
+    where
+        T: PhfHash + ?Sized,
+    {
+        self.probe(key)
+            .is_some_and(|(index, bits)| self.fingerprints[index] == F::from_bits(bits))
+    }
+
+    /// Returns the fingerprint index and bits for `key`.
+    fn probe<T>(&self, key: &T) -> Option<(usize, u64)>
+    where
+        T: PhfHash + ?Sized,
+    {
+        let hash = lookup::hash(key, &self.key);
+        let index = self.params().index(&hash)?;
+        #[cfg(not(feature = "ptrhash"))]
+        let bits = phf_shared::fingerprint(&hash);
+        #[cfg(feature = "ptrhash")]
+        let bits = phf_shared::ptrhash::fingerprint(hash);
+        Some((index, bits))
+    }
+
+    #[cfg(not(feature = "ptrhash"))]
+    fn params(&self) -> Params<'_> {
+        Params {
+            disps: self.disps,
+            len: self.fingerprints.len(),
+        }
+    }
+
+    #[cfg(feature = "ptrhash")]
+    fn params(&self) -> Params<'_> {
+        Params {
+            key: self.key,
+            pilots: self.pilots,
+            remap: self.remap,
+            len: self.fingerprints.len(),
+        }
+    }
+}
@@ -0,0 +1,543 @@
+//! Scanning text for occurrences of map keys.
+use core::fmt;
+use core::iter::FusedIterator;
+use core::mem;
+use core::ops::{Deref, Range};
+use phf_shared::PhfBorrow;
+
+use crate::layout::MemoryUsage;
+use crate::{Map, Set, map};
+
+/// How [`FindIter`] reports matches that start at or overlap the same
+/// position.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MatchKind {
+    /// Report non-overlapping matches, preferring the longest key at the
+    /// leftmost position.
+    #[default]
+    LeftmostLongest,
+    /// Report non-overlapping matches, preferring the shortest key at the
+    /// leftmost position.
+    LeftmostShortest,
+    /// Report every match, including ones that overlap. Matches are ordered
+    /// by start position, then by length.
+    Overlapping,
+}
+
+// Lengths below this are recorded in a bitset, longer ones in a sorted list.
+const SMALL_LENGTHS: usize = 256;
+
+/// The lengths of the keys of a [`FindMap`] or [`FindSet`], recorded when the
+/// table is generated so that a [`Finder`] only probes lengths some key has.
+///
+/// ## Note
+///
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`KeyLengths::from_raw_parts`] instead.
+#[derive(Debug)]
+pub struct KeyLengths {
+    /// Bit `len % 64` of word `len / 64` is set if a key is `len` bytes long.
+    #[doc(hidden)]
+    pub small: [u64; SMALL_LENGTHS / 64],
+    /// The lengths of at least `SMALL_LENGTHS` bytes, in ascending order.
+    #[doc(hidden)]
+    pub long: &'static [usize],
+    #[doc(hidden)]
+    pub min: usize,
+    #[doc(hidden)]
+    pub max: usize,
+}
+
+impl KeyLengths {
+    /// Creates the key lengths computed by `phf_generator::find::key_lengths`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parts are inconsistent, which fails compilation when the
+    /// lengths initialize a `static` or `const`.
+    pub const fn from_raw_parts(
+        small: [u64; SMALL_LENGTHS / 64],
+        long: &'static [usize],
+        min: usize,
+        max: usize,
+    ) -> Self {
+        assert!(min <= max, "`min` must not exceed `max`");
+        let mut i = 0;
+        while i < long.len() {
+            assert!(
+                long[i] >= SMALL_LENGTHS && long[i] <= max,
+                "`long` lengths must be in `SMALL_LENGTHS..=max`"
+            );
+            assert!(
+                i == 0 || long[i - 1] < long[i],
+                "`long` lengths must be in ascending order"
+            );
+            i += 1;
+        }
+        KeyLengths {
+            small,
+            long,
+            min,
+            max,
+        }
+    }
+
+    fn memory_usage(&self) -> usize {
+        mem::size_of::<KeyLengths>() + mem::size_of_val(self.long)
+    }
+}
+
+/// The set of key lengths a [`Finder`] probes.
+#[derive(Clone, Copy)]
+pub(crate) struct Lengths {
+    small: [u64; SMALL_LENGTHS / 64],
+    // `None` for lengths collected from the keys, in which case every length
+    // from `SMALL_LENGTHS` up to `max` is probed.
+    long: Option<&'static [usize]>,
+    min: usize,
+    max: usize,
+}
+
+impl Lengths {
+    fn recorded(lengths: &KeyLengths) -> Self {
+        Lengths {
+            small: lengths.small,
+            long: Some(lengths.long),
+            min: lengths.min,
+            max: lengths.max,
+        }
+    }
+
+    pub(crate) fn new(lens: impl Iterator<Item = usize>) -> Self {
+        let mut lengths = Lengths {
+            small: [0; SMALL_LENGTHS / 64],
+            long: None,
+            min: usize::MAX,
+            max: 0,
+        };
+        for len in lens.filter(|&len| len > 0) {
+            if len < SMALL_LENGTHS {
+                lengths.small[len / 64] |= 1 << (len % 64);
+            }
+            lengths.min = lengths.min.min(len);
+            lengths.max = lengths.max.max(len);
+        }
+        lengths
+    }
+
+    fn contains_small(&self, len: usize) -> bool {
+        self.small[len / 64] & (1 << (len % 64)) != 0
+    }
+
+    /// Returns the next key length in `from..=to`, searching upwards.
+    fn next_up(&self, from: usize, to: usize) -> Option<usize> {
+        let (from, to) = (from.max(self.min), to.min(self.max));
+        if let Some(len) = (from..=to.min(SMALL_LENGTHS - 1)).find(|&len| self.contains_small(len))
+        {
+            return Some(len);
+        }
+        let from = from.max(SMALL_LENGTHS);
+        match self.long {
+            Some(long) => long[long.partition_point(|&len| len < from)..]
+                .first()
+                .copied()
+                .filter(|&len| len <= to),
+            None => Some(from).filter(|&len| len <= to),
+        }
+    }
+
+    /// Returns the next key length in `from..=to`, searching downwards.
+    fn next_down(&self, from: usize, to: usize) -> Option<usize> {
+        let (from, to) = (from.max(self.min), to.min(self.max));
+        let long_from = from.max(SMALL_LENGTHS);
+        let long = match self.long {
+            Some(long) => long[..long.partition_point(|&len| len <= to)]
+                .last()
+                .copied()
+                .filter(|&len| len >= long_from),
+            None => Some(to).filter(|&len| len >= long_from),
+        };
+        long.or_else(|| {
+            (from..=to.min(SMALL_LENGTHS - 1))
+                .rev()
+                .find(|&len| self.contains_small(len))
+        })
+    }
+}
+
+/// A [`Map`] that also records the lengths of its keys, so that scanning text
+/// for them doesn't have to visit every key first.
+///
+/// `phf_map!` generates a `FindMap` for maps with string or byte string keys
+/// that start with `#![find]`, and `phf_codegen` does so for maps built with
+/// `record_key_lengths()`. It dereferences to the underlying [`Map`] for
+/// lookups and iteration.
+///
+/// ```
+/// use phf::phf_map;
+///
+/// static UNITS: phf::find::FindMap<&'static str, u32> = phf_map! {
+///     #![find]
+///     "k" => 1_000,
+///     "km" => 1_000,
+///     "m" => 1,
+/// };
+///
+/// let found = UNITS
+///     .find_iter("5km or 3m")
+///     .map(|m| (m.range(), *m.key()))
+///     .collect::<Vec<_>>();
+/// assert_eq!(found, [(1..3, "km"), (8..9, "m")]);
+/// assert_eq!(UNITS.get("m"), Some(&1));
+/// ```
+///
+/// ## Note
+///
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`FindMap::from_raw_parts`] instead.
+pub struct FindMap<K: 'static, V: 'static> {
+    #[doc(hidden)]
+    pub map: Map<K, V>,
+    #[doc(hidden)]
+    pub key_lengths: &'static KeyLengths,
+}
+
+impl<K, V> FindMap<K, V> {
+    /// Creates a map from the underlying map and the lengths of its keys.
+    pub const fn from_raw_parts(map: Map<K, V>, key_lengths: &'static KeyLengths) -> Self {
+        FindMap { map, key_lengths }
+    }
+
+    /// Returns a reusable [`Finder`] that scans text for the keys of this
+    /// map.
+    pub fn finder<B>(&self) -> Finder<'_, K, V>
+    where
+        K: PhfBorrow<B>,
+        B: AsRef<[u8]> + ?Sized + 'static,
+    {
+        Finder::new(
+            &self.map,
+            map::key_bytes::<K, B>,
+            Lengths::recorded(self.key_lengths),
+        )
+    }
+
+    /// Returns an iterator over the non-overlapping occurrences of keys in
+    /// `haystack`, preferring the longest key at the leftmost position.
+    ///
+    /// See [`Map::find_iter`].
+    pub fn find_iter<'h, B, H>(&self, haystack: &'h H) -> FindIter<'_, 'h, K, V>
+    where
+        K: PhfBorrow<B>,
+        B: AsRef<[u8]> + ?Sized + 'static,
+        H: AsRef<[u8]> + ?Sized,
+    {
+        self.finder().find_iter(haystack)
+    }
+
+    /// Returns the number of bytes of static data the map uses, including
+    /// its key lengths.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.map
+            .memory_usage()
+            .with_auxiliary(self.key_lengths.memory_usage())
+    }
+}
+
+impl<K, V> Deref for FindMap<K, V> {
+    type Target = Map<K, V>;
+
+    fn deref(&self) -> &Map<K, V> {
+        &self.map
+    }
+}
+
+impl<K, V> fmt::Debug for FindMap<K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.map.fmt(fmt)
+    }
+}
+
+/// A [`Set`] that also records the lengths of its values. See [`FindMap`].
+///
+/// ## Note
+///
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`FindSet::from_raw_parts`] instead.
+pub struct FindSet<T: 'static> {
+    #[doc(hidden)]
+    pub set: Set<T>,
+    #[doc(hidden)]
+    pub key_lengths: &'static KeyLengths,
+}
+
+impl<T> FindSet<T> {
+    /// Creates a set from the underlying set and the lengths of its values.
+    pub const fn from_raw_parts(set: Set<T>, key_lengths: &'static KeyLengths) -> Self {
+        FindSet { set, key_lengths }
+    }
+
+    /// Returns a reusable [`Finder`] that scans text for the values of this
+    /// set.
+    pub fn finder<B>(&self) -> Finder<'_, T, ()>
+    where
+        T: PhfBorrow<B>,
+        B: AsRef<[u8]> + ?Sized + 'static,
+    {
+        Finder::new(
+            &self.set.map,
+            map::key_bytes::<T, B>,
+            Lengths::recorded(self.key_lengths),
+        )
+    }
+
+    /// Returns an iterator over the non-overlapping occurrences of values in
+    /// `haystack`, preferring the longest value at the leftmost position.
+    ///
+    /// See [`Map::find_iter`].
+    pub fn find_iter<'h, B, H>(&self, haystack: &'h H) -> FindIter<'_, 'h, T, ()>
+    where
+        T: PhfBorrow<B>,
+        B: AsRef<[u8]> + ?Sized + 'static,
+        H: AsRef<[u8]> + ?Sized,
+    {
+        self.finder().find_iter(haystack)
+    }
+
+    /// Returns the number of bytes of static data the set uses, including
+    /// the lengths of its values.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.set
+            .memory_usage()
+            .with_auxiliary(self.key_lengths.memory_usage())
+    }
+}
+
+impl<T> Deref for FindSet<T> {
+    type Target = Set<T>;
+
+    fn deref(&self) -> &Set<T> {
+        &self.set
+    }
+}
+
+impl<T> fmt::Debug for FindSet<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.set.fmt(fmt)
+    }
+}
+
+/// A reusable scanner for the keys of a [`Map`] or [`Set`].
+///
+/// At each position of the haystack, a `Finder` probes the map for every key
+/// length it has. A [`FindMap`] or [`FindSet`] records those lengths when it's
+/// generated; for a plain [`Map`] or [`Set`], creating a `Finder` visits every
+/// key to collect them, so prefer reusing one over calling `find_iter`
+/// repeatedly on large maps. Empty keys are never reported.
+pub struct Finder<'m, K: 'static, V: 'static> {
+    map: &'m Map<K, V>,
+    key_bytes: fn(&K) -> &[u8],
+    lengths: Lengths,
+    kind: MatchKind,
+}
+
+impl<'m, K, V> Finder<'m, K, V> {
+    pub(crate) fn new(map: &'m Map<K, V>, key_bytes: fn(&K) -> &[u8], lengths: Lengths) -> Self {
+        Finder {
+            map,
+            key_bytes,
+            lengths,
+            kind: MatchKind::default(),
+        }
+    }
+
+    /// Sets how matches are reported. Defaults to
+    /// [`MatchKind::LeftmostLongest`].
+    pub fn match_kind(mut self, kind: MatchKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Returns an iterator over the occurrences of keys in `haystack`.
+    ///
+    /// `haystack` is compared bytewise, so a `str` key can only match at
+    /// character boundaries of a `str` haystack.
+    pub fn find_iter<'h, H>(&self, haystack: &'h H) -> FindIter<'m, 'h, K, V>
+    where
+        H: AsRef<[u8]> + ?Sized,
+    {
+        FindIter {
+            finder: self.clone(),
+            haystack: haystack.as_ref(),
+            pos: 0,
+            next_len: 0,
+        }
+    }
+
+    fn probe(&self, bytes: &[u8]) -> Option<(&'m K, &'m V)> {
+        let key_bytes = self.key_bytes;
+        self.map.probe_entry(bytes, |key| key_bytes(key) == bytes)
+    }
+}
+
+impl<'m, K, V> Clone for Finder<'m, K, V> {
+    fn clone(&self) -> Self {
+        Finder {
+            map: self.map,
+            key_bytes: self.key_bytes,
+            lengths: self.lengths,
+            kind: self.kind,
+        }
+    }
+}
+
+impl<'m, K, V> fmt::Debug for Finder<'m, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Finder")
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An occurrence of a key found by [`FindIter`].
+pub struct Match<'m, K, V> {
+    start: usize,
+    end: usize,
+    key: &'m K,
+    value: &'m V,
+}
+
+impl<'m, K, V> Match<'m, K, V> {
+    /// Returns the byte offset where the match starts.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the byte offset just past the end of the match.
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the byte range of the match in the haystack.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Returns the matched key as stored in the map.
+    pub fn key(&self) -> &'m K {
+        self.key
+    }
+
+    /// Returns the value associated with the matched key.
+    pub fn value(&self) -> &'m V {
+        self.value
+    }
+}
+
+impl<'m, K, V> Clone for Match<'m, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'m, K, V> Copy for Match<'m, K, V> {}
+
+impl<'m, K, V> fmt::Debug for Match<'m, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Match")
+            .field("range", &self.range())
+            .field("key", self.key)
+            .field("value", self.value)
+            .finish()
+    }
+}
+
+/// An iterator over the occurrences of keys in a haystack.
+pub struct FindIter<'m, 'h, K: 'static, V: 'static> {
+    finder: Finder<'m, K, V>,
+    haystack: &'h [u8],
+    pos: usize,
+    // Used by `MatchKind::Overlapping` to resume at the next key length.
+    next_len: usize,
+}
+
+impl<'m, 'h, K, V> Iterator for FindIter<'m, 'h, K, V> {
+    type Item = Match<'m, K, V>;
+
+    fn next(&mut self) -> Option<Match<'m, K, V>> {
+        let lengths = self.finder.lengths;
+        while self.pos < self.haystack.len() {
+            let rest = &self.haystack[self.pos..];
+            let mut len = match self.finder.kind {
+                MatchKind::LeftmostLongest => lengths.next_down(1, rest.len()),
+                MatchKind::LeftmostShortest => lengths.next_up(1, rest.len()),
+                MatchKind::Overlapping => lengths.next_up(self.next_len.max(1), rest.len()),
+            };
+
+            while let Some(l) = len {
+                if let Some((key, value)) = self.finder.probe(&rest[..l]) {
+                    let start = self.pos;
+                    if self.finder.kind == MatchKind::Overlapping {
+                        self.next_len = l + 1;
+                    } else {
+                        self.pos += l;
+                    }
+                    return Some(Match {
+                        start,
+                        end: start + l,
+                        key,
+                        value,
+                    });
+                }
+                len = match self.finder.kind {
+                    MatchKind::LeftmostLongest => lengths.next_down(1, l - 1),
+                    _ => lengths.next_up(l + 1, rest.len()),
+                };
+            }
+
+            self.pos += 1;
+            self.next_len = 0;
+        }
+        None
+    }
+}
+
+impl<'m, 'h, K, V> FusedIterator for FindIter<'m, 'h, K, V> {}
+
+impl<'m, 'h, K, V> Clone for FindIter<'m, 'h, K, V> {
+    fn clone(&self) -> Self {
+        FindIter {
+            finder: self.finder.clone(),
+            haystack: self.haystack,
+            pos: self.pos,
+            next_len: self.next_len,
+        }
+    }
+}
+
+impl<'m, 'h, K, V> fmt::Debug for FindIter<'m, 'h, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
@@ -0,0 +1,219 @@
+//! An immutable map with tuple keys grouped by their first component,
+//! constructed at compile time.
+use core::fmt;
+use core::ops::Index;
+use phf_shared::{PhfEq, PhfHash};
+
+#[cfg(feature = "instrument")]
+use crate::instrument::MapStats;
+use crate::ordered_map::{Entries, Keys, Values};
+use crate::{Map, OrderedMap};
+
+/// An immutable map with tuple keys, supporting lookups of every entry whose
+/// key starts with a given first component.
+///
+/// `P` is the type of the first component of `K`. Entries sharing a first
+/// component are stored next to each other, in the order they were defined;
+/// groups are ordered by the first definition of an entry in them.
+///
+/// ## Note
+///
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`GroupedMap::from_raw_parts`] instead.
+pub struct GroupedMap<K: 'static, V: 'static, P: 'static> {
+    #[doc(hidden)]
+    pub map: OrderedMap<K, V>,
+    #[doc(hidden)]
+    pub groups: Map<P, (usize, usize)>,
+}
+
+impl<K, V, P> fmt::Debug for GroupedMap<K, V, P>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_map().entries(self.entries()).finish()
+    }
+}
+
+impl<'a, K, V, P, T: ?Sized> Index<&'a T> for GroupedMap<K, V, P>
+where
+    T: Eq + PhfHash,
+    K: PhfEq<T>,
+{
+    type Output = V;
+
+    fn index(&self, k: &'a T) -> &V {
+        self.get(k).expect("invalid key")
+    }
+}
+
+impl<K, V, P> GroupedMap<K, V, P> {
+    /// Creates a map from the underlying map, whose entries are stored group
+    /// by group, and a map of each first key component to the range of its
+    /// group's entries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a group's range is empty or out of bounds of the entries,
+    /// which fails compilation when the map initializes a `static` or `const`.
+    pub const fn from_raw_parts(map: OrderedMap<K, V>, groups: Map<P, (usize, usize)>) -> Self {
+        let ranges = groups.entries;
+        let mut i = 0;
+        while i < ranges.len() {
+            let (start, end) = ranges[i].1;
+            assert!(
+                start < end && end <= map.len(),
+                "group ranges must be non-empty and in bounds of the entries"
+            );
+            i += 1;
+        }
+        GroupedMap { map, groups }
+    }
+
+    /// Returns the number of entries in the `GroupedMap`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the `GroupedMap` is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of distinct first key components.
+    #[inline]
+    pub const fn group_count(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Returns a reference to the value that `key` maps to.
+    pub fn get<T>(&self, key: &T) -> Option<&V>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.map.get(key)
+    }
+
+    /// Returns a reference to the map's internal static instance of the given
+    /// key.
+    pub fn get_key<T>(&self, key: &T) -> Option<&K>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.map.get_key(key)
+    }
+
+    /// Like `get`, but returns both the key and the value.
+    pub fn get_entry<T>(&self, key: &T) -> Option<(&K, &V)>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.map.get_entry(key)
+    }
+
+    /// Determines if `key` is in the `GroupedMap`.
+    pub fn contains_key<T>(&self, key: &T) -> bool
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Returns an iterator over the entries whose key starts with `prefix`,
+    /// in the order they were defined.
+    ///
+    /// The iterator is empty if no key starts with `prefix`.
+    ///
+    /// ```
+    /// use phf::{GroupedMap, phf_grouped_map};
+    ///
+    /// static MESSAGES: GroupedMap<(&'static str, u32), &'static str, &'static str> =
+    ///     phf_grouped_map! {
+    ///         ("en", 1u32) => "Hello",
+    ///         ("de", 1) => "Hallo",
+    ///         ("en", 2) => "Goodbye",
+    ///     };
+    ///
+    /// let english = MESSAGES.get_group("en").map(|(_, v)| *v).collect::<Vec<_>>();
+    /// assert_eq!(english, ["Hello", "Goodbye"]);
+    /// assert_eq!(MESSAGES.get(&("de", 1)), Some(&"Hallo"));
+    /// ```
+    pub fn get_group<T>(&self, prefix: &T) -> Entries<'_, K, V>
+    where
+        T: Eq + PhfHash + ?Sized,
+        P: PhfEq<T>,
+    {
+        let (start, end) = self.groups.get(prefix).copied().unwrap_or((0, 0));
+        self.map.entries_in(start..end)
+    }
+
+    /// Determines if any key starts with `prefix`.
+    pub fn contains_group<T>(&self, prefix: &T) -> bool
+    where
+        T: Eq + PhfHash + ?Sized,
+        P: PhfEq<T>,
+    {
+        self.groups.contains_key(prefix)
+    }
+
+    /// Returns a snapshot of the lookup counters of the entries of the map,
+    /// with the keys returned group by group.
+    ///
+    /// Lookups by group are counted separately, by
+    /// [`group_stats`](Self::group_stats).
+    ///
+    /// Requires the `instrument` feature.
+    #[cfg(feature = "instrument")]
+    pub fn stats(&self) -> MapStats<'_, K> {
+        self.map.stats()
+    }
+
+    /// Returns a snapshot of the lookup counters of the groups of the map,
+    /// which count the calls to `get_group` and `contains_group`.
+    ///
+    /// Requires the `instrument` feature.
+    #[cfg(feature = "instrument")]
+    pub fn group_stats(&self) -> MapStats<'_, P> {
+        self.groups.stats()
+    }
+
+    /// Returns an iterator over the key/value pairs in the map.
+    ///
+    /// Entries are returned group by group.
+    pub fn entries(&self) -> Entries<'_, K, V> {
+        self.map.entries()
+    }
+
+    /// Returns an iterator over the keys in the map.
+    ///
+    /// Keys are returned group by group.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        self.map.keys()
+    }
+
+    /// Returns an iterator over the values in the map.
+    ///
+    /// Values are returned group by group.
+    pub fn values(&self) -> Values<'_, K, V> {
+        self.map.values()
+    }
+}
+
+impl<'a, K, V, P> IntoIterator for &'a GroupedMap<K, V, P> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Entries<'a, K, V>;
+
+    fn into_iter(self) -> Entries<'a, K, V> {
+        self.entries()
+    }
+}
@@ -0,0 +1,234 @@
+//! Lookup counters for profiling static tables.
+//!
+//! With the `instrument` feature enabled, every [`Map`](crate::Map),
+//! [`Set`](crate::Set), [`OrderedMap`](crate::OrderedMap),
+//! [`OrderedSet`](crate::OrderedSet) and [`RangeMap`](crate::RangeMap)
+//! generated by `phf_macros` or `phf_codegen` counts the hits of each of its
+//! entries and its misses, using relaxed atomic increments. The generating
+//! crate must have its `instrument` feature enabled as well. Tables built out
+//! of these, like [`GroupedMap`](crate::GroupedMap), count lookups through
+//! them.
+//!
+//! The counters of a single table are available from its `stats` method,
+//! such as [`Map::stats`](crate::Map::stats). Tables also register themselves
+//! in a global registry on their first lookup, which [`snapshot`] and [`dump`]
+//! read, so tables that are never looked up don't appear there. Lookups in
+//! empty tables, and in tables generated without the `instrument` feature of
+//! the generating crate, are not counted. Neither are the probes
+//! [`Map::find_iter`](crate::Map::find_iter) and
+//! [`SuggestMap::suggest`](crate::suggest::SuggestMap::suggest) make while
+//! searching.
+//!
+//! Tables generated by `phf_macros` are named after the module and line
+//! they're defined at; tables generated by `phf_codegen` are named after the
+//! module they're included into and the line in the generated file.
+//!
+//! Requires the `instrument` feature.
+use std::io;
+use std::mem;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, Once};
+
+static REGISTRY: Mutex<Vec<&'static Counters>> = Mutex::new(Vec::new());
+
+/// The lookup counters of one table.
+///
+/// ## Note
+///
+/// The fields of this struct are public so that they may be initialized by the
+/// `phf_macros` macros and code generation. They are subject to change at any
+/// time and should never be accessed directly.
+pub struct Counters {
+    #[doc(hidden)]
+    pub name: &'static str,
+    /// Hits per entry, in the order of the table's entries.
+    #[doc(hidden)]
+    pub hits: &'static [AtomicU64],
+    #[doc(hidden)]
+    pub misses: AtomicU64,
+    #[doc(hidden)]
+    pub registered: Once,
+}
+
+/// Counters for tables that have no entries or weren't generated with
+/// counters, and so are never updated.
+pub(crate) static EMPTY: Counters = Counters::new("<empty>", &[]);
+
+impl Counters {
+    #[doc(hidden)]
+    pub const fn new(name: &'static str, hits: &'static [AtomicU64]) -> Self {
+        Counters {
+            name,
+            hits,
+            misses: AtomicU64::new(0),
+            registered: Once::new(),
+        }
+    }
+
+    /// Returns the number of bytes of the counters, or 0 for counters that
+    /// are never updated.
+    pub(crate) fn memory_usage(&self) -> usize {
+        if self.hits.is_empty() {
+            0
+        } else {
+            mem::size_of::<Counters>() + mem::size_of_val(self.hits)
+        }
+    }
+
+    /// Records a lookup that found the entry at `index`, or nothing.
+    #[inline]
+    pub(crate) fn record(&'static self, index: Option<usize>) {
+        if self.hits.is_empty() {
+            return;
+        }
+        self.registered
+            .call_once(|| REGISTRY.lock().unwrap().push(self));
+        match index {
+            Some(index) => self.hits[index].fetch_add(1, Ordering::Relaxed),
+            None => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+    }
+
+    pub(crate) fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn hits(&self) -> impl Iterator<Item = u64> + '_ {
+        self.hits.iter().map(|hits| hits.load(Ordering::Relaxed))
+    }
+
+    fn reset(&self) {
+        for hits in self.hits {
+            hits.store(0, Ordering::Relaxed);
+        }
+        self.misses.store(0, Ordering::Relaxed);
+    }
+}
+
+/// A snapshot of the lookup counters of a table.
+#[derive(Debug, Clone)]
+pub struct MapStats<'a, K> {
+    name: &'static str,
+    hits: Vec<(&'a K, u64)>,
+    misses: u64,
+}
+
+impl<'a, K> MapStats<'a, K> {
+    pub(crate) fn new(counters: &Counters, keys: impl Iterator<Item = &'a K>) -> Self {
+        MapStats {
+            name: counters.name,
+            hits: keys.zip(counters.hits()).collect(),
+            misses: counters.misses(),
+        }
+    }
+
+    /// Returns the name of the table.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the number of hits of each key, in the order of the table's
+    /// entries.
+    pub fn hits(&self) -> &[(&'a K, u64)] {
+        &self.hits
+    }
+
+    /// Returns the number of lookups of keys that are not in the table.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Returns the number of lookups of keys that are in the table.
+    pub fn total_hits(&self) -> u64 {
+        self.hits.iter().map(|(_, hits)| hits).sum()
+    }
+
+    /// Returns an iterator over the keys that were never looked up.
+    pub fn unused_keys(&self) -> impl Iterator<Item = &'a K> + '_ {
+        self.hits
+            .iter()
+            .filter(|(_, hits)| *hits == 0)
+            .map(|(key, _)| *key)
+    }
+}
+
+/// A snapshot of the lookup counters of a registered table.
+///
+/// Unlike [`MapStats`], this doesn't know the table's keys, so entries are
+/// identified by their index in the table.
+#[derive(Debug, Clone)]
+pub struct TableStats {
+    name: &'static str,
+    hits: Vec<u64>,
+    misses: u64,
+}
+
+impl TableStats {
+    /// Returns the name of the table.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the number of hits of each entry, in the order of the table's
+    /// entries.
+    pub fn hits(&self) -> &[u64] {
+        &self.hits
+    }
+
+    /// Returns the number of lookups of keys that are not in the table.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Returns the number of lookups of keys that are in the table.
+    pub fn total_hits(&self) -> u64 {
+        self.hits.iter().sum()
+    }
+
+    /// Returns the number of entries that were never looked up.
+    pub fn unused_entries(&self) -> usize {
+        self.hits.iter().filter(|&&hits| hits == 0).count()
+    }
+}
+
+/// Returns the counters of every table that has been looked up, in the order
+/// of their first lookup.
+pub fn snapshot() -> Vec<TableStats> {
+    REGISTRY
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|counters| TableStats {
+            name: counters.name,
+            hits: counters.hits().collect(),
+            misses: counters.misses(),
+        })
+        .collect()
+}
+
+/// Writes a summary line for every table that has been looked up to `out`.
+///
+/// ```no_run
+/// phf::instrument::dump(&mut std::io::stderr()).unwrap();
+/// ```
+pub fn dump<W: io::Write + ?Sized>(out: &mut W) -> io::Result<()> {
+    for table in snapshot() {
+        writeln!(
+            out,
+            "{}: {} hits, {} misses, {} of {} entries unused",
+            table.name(),
+            table.total_hits(),
+            table.misses(),
+            table.unused_entries(),
+            table.hits().len(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Resets the counters of every table that has been looked up to zero.
+pub fn reset() {
+    for counters in REGISTRY.lock().unwrap().iter() {
+        counters.reset();
+    }
+}
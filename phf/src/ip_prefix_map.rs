@@ -0,0 +1,150 @@
+//! An immutable longest-prefix-match table for IP networks constructed at
+//! compile time.
+use core::fmt;
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::Map;
+
+/// An immutable table mapping IPv4 and IPv6 networks to values, looked up by
+/// the most specific network containing an address.
+///
+/// Networks are stored in one perfect hash table per prefix length, keyed by
+/// network address, so a lookup probes at most one table for each prefix
+/// length present.
+///
+/// ## Note
+///
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`IpPrefixMap::from_raw_parts`] instead.
+pub struct IpPrefixMap<V: 'static> {
+    /// IPv4 tables by prefix length, longest first.
+    #[doc(hidden)]
+    pub v4: &'static [(u8, Map<u32, V>)],
+    /// IPv6 tables by prefix length, longest first.
+    #[doc(hidden)]
+    pub v6: &'static [(u8, Map<u128, V>)],
+}
+
+impl<V> fmt::Debug for IpPrefixMap<V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let v4 = self.v4.iter().flat_map(|(len, map)| {
+            map.entries()
+                .map(move |(&network, value)| ((IpAddr::from(Ipv4Addr::from(network)), len), value))
+        });
+        let v6 = self.v6.iter().flat_map(|(len, map)| {
+            map.entries()
+                .map(move |(&network, value)| ((IpAddr::from(Ipv6Addr::from(network)), len), value))
+        });
+        fmt.debug_map().entries(v4.chain(v6)).finish()
+    }
+}
+
+/// Panics if the prefix lengths of `tables` aren't at most `max` and in
+/// descending order.
+const fn check_prefix_lens<K, V>(tables: &[(u8, Map<K, V>)], max: u8) {
+    let mut i = 0;
+    while i < tables.len() {
+        assert!(tables[i].0 <= max, "prefix length out of range");
+        assert!(
+            i == 0 || tables[i - 1].0 > tables[i].0,
+            "prefix lengths must be in descending order"
+        );
+        i += 1;
+    }
+}
+
+impl<V> IpPrefixMap<V> {
+    /// Creates a table from one map per prefix length, keyed by network
+    /// address, ordered from the longest prefix to the shortest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the prefix lengths are out of range or not in descending
+    /// order, which fails compilation when the table initializes a `static` or
+    /// `const`.
+    pub const fn from_raw_parts(
+        v4: &'static [(u8, Map<u32, V>)],
+        v6: &'static [(u8, Map<u128, V>)],
+    ) -> Self {
+        check_prefix_lens(v4, 32);
+        check_prefix_lens(v6, 128);
+        IpPrefixMap { v4, v6 }
+    }
+
+    /// Returns the number of networks in the `IpPrefixMap`.
+    pub fn len(&self) -> usize {
+        let v4 = self.v4.iter().map(|(_, map)| map.len()).sum::<usize>();
+        let v6 = self.v6.iter().map(|(_, map)| map.len()).sum::<usize>();
+        v4 + v6
+    }
+
+    /// Returns true if the `IpPrefixMap` is empty.
+    pub fn is_empty(&self) -> bool {
+        self.v4.is_empty() && self.v6.is_empty()
+    }
+
+    /// Returns the value of the most specific network containing `addr`.
+    ///
+    /// ```
+    /// use std::net::IpAddr;
+    ///
+    /// use phf::{IpPrefixMap, phf_ip_prefix_map};
+    ///
+    /// static ROUTES: IpPrefixMap<&'static str> = phf_ip_prefix_map! {
+    ///     "0.0.0.0/0" => "default",
+    ///     "10.0.0.0/8" => "internal",
+    ///     "10.1.0.0/16" => "lab",
+    ///     "2001:db8::/32" => "documentation",
+    /// };
+    ///
+    /// let route = |addr: &str| ROUTES.get(addr.parse::<IpAddr>().unwrap());
+    /// assert_eq!(route("10.1.2.3"), Some(&"lab"));
+    /// assert_eq!(route("10.2.3.4"), Some(&"internal"));
+    /// assert_eq!(route("192.0.2.1"), Some(&"default"));
+    /// assert_eq!(route("2001:db8::1"), Some(&"documentation"));
+    /// assert_eq!(route("::1"), None);
+    /// ```
+    pub fn get(&self, addr: IpAddr) -> Option<&V> {
+        self.longest_match(addr).map(|(_, value)| value)
+    }
+
+    /// Like `get`, but only for IPv4 addresses.
+    pub fn get_v4(&self, addr: Ipv4Addr) -> Option<&V> {
+        self.longest_match_v4(addr).map(|(_, value)| value)
+    }
+
+    /// Like `get`, but only for IPv6 addresses.
+    pub fn get_v6(&self, addr: Ipv6Addr) -> Option<&V> {
+        self.longest_match_v6(addr).map(|(_, value)| value)
+    }
+
+    /// Like `get`, but also returns the prefix length of the matching
+    /// network.
+    pub fn longest_match(&self, addr: IpAddr) -> Option<(u8, &V)> {
+        match addr {
+            IpAddr::V4(addr) => self.longest_match_v4(addr),
+            IpAddr::V6(addr) => self.longest_match_v6(addr),
+        }
+    }
+
+    fn longest_match_v4(&self, addr: Ipv4Addr) -> Option<(u8, &V)> {
+        let addr = u32::from(addr);
+        self.v4.iter().find_map(|(len, map)| {
+            let mask = u32::MAX.checked_shl(32 - u32::from(*len)).unwrap_or(0);
+            map.get(&(addr & mask)).map(|value| (*len, value))
+        })
+    }
+
+    fn longest_match_v6(&self, addr: Ipv6Addr) -> Option<(u8, &V)> {
+        let addr = u128::from(addr);
+        self.v6.iter().find_map(|(len, map)| {
+            let mask = u128::MAX.checked_shl(128 - u32::from(*len)).unwrap_or(0);
+            map.get(&(addr & mask)).map(|value| (*len, value))
+        })
+    }
+}
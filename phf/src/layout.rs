@@ -0,0 +1,145 @@
+//! Memory footprint and hash layout of static tables.
+//!
+//! [`Map`](crate::Map), [`Set`](crate::Set), [`OrderedMap`](crate::OrderedMap)
+//! and [`OrderedSet`](crate::OrderedSet) report them through their
+//! `memory_usage` and `layout_stats` methods, which make it possible to
+//! compare the CHD and `ptrhash` layouts of a table or to track its size.
+//! The tables in [`find`](crate::find) and `suggest` report their memory
+//! usage too.
+
+/// The number of bytes of static data a table uses, returned by
+/// `memory_usage`.
+///
+/// Only the slices the table points to are counted, not the table itself or
+/// any data its keys and values point to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoryUsage {
+    pub(crate) entries: usize,
+    pub(crate) displacements: usize,
+    pub(crate) remap: usize,
+    pub(crate) idxs: usize,
+    pub(crate) auxiliary: usize,
+}
+
+impl MemoryUsage {
+    /// Returns the number of bytes in the key/value pairs.
+    pub const fn entries(&self) -> usize {
+        self.entries
+    }
+
+    /// Returns the number of bytes in the per-bucket displacements: the
+    /// `(d1, d2)` pairs of the CHD layout, or the pilots of the `ptrhash`
+    /// layout.
+    pub const fn displacements(&self) -> usize {
+        self.displacements
+    }
+
+    /// Returns the number of bytes in the remap table of the `ptrhash` layout,
+    /// which is 0 with the CHD layout.
+    pub const fn remap(&self) -> usize {
+        self.remap
+    }
+
+    /// Returns the number of bytes in the slot-to-entry indices of ordered
+    /// tables, which is 0 for unordered tables.
+    pub const fn idxs(&self) -> usize {
+        self.idxs
+    }
+
+    /// Returns the number of bytes in the data generated alongside the
+    /// table: the lookup counters of the `instrument` feature, the key lengths
+    /// of a [`FindMap`](crate::find::FindMap) or `FindSet`, and the deletion
+    /// table of a `SuggestMap` or `SuggestSet`.
+    pub const fn auxiliary(&self) -> usize {
+        self.auxiliary
+    }
+
+    /// Returns the total number of bytes.
+    pub const fn total(&self) -> usize {
+        self.entries + self.displacements + self.remap + self.idxs + self.auxiliary
+    }
+
+    /// Adds `bytes` of data generated alongside the table.
+    pub(crate) const fn with_auxiliary(mut self, bytes: usize) -> Self {
+        self.auxiliary += bytes;
+        self
+    }
+}
+
+/// Statistics on the hash layout of a table, returned by `layout_stats`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutStats {
+    pub(crate) format: &'static str,
+    pub(crate) len: usize,
+    pub(crate) buckets: usize,
+    pub(crate) max_displacement: u32,
+    pub(crate) histogram: [usize; 33],
+    pub(crate) remap_len: usize,
+}
+
+impl LayoutStats {
+    pub(crate) fn new(
+        len: usize,
+        remap_len: usize,
+        displacements: impl ExactSizeIterator<Item = u32>,
+    ) -> Self {
+        let mut stats = LayoutStats {
+            format: phf_shared::TABLE_FORMAT,
+            len,
+            buckets: displacements.len(),
+            max_displacement: 0,
+            histogram: [0; 33],
+            remap_len,
+        };
+        for displacement in displacements {
+            stats.max_displacement = stats.max_displacement.max(displacement);
+            stats.histogram[(u32::BITS - displacement.leading_zeros()) as usize] += 1;
+        }
+        stats
+    }
+
+    /// Returns the [table format](phf_shared::TABLE_FORMAT) of the layout,
+    /// which names the algorithm.
+    pub fn format(&self) -> &'static str {
+        self.format
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the table has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of buckets keys are hashed into before they're
+    /// displaced into their slots.
+    pub fn buckets(&self) -> usize {
+        self.buckets
+    }
+
+    /// Returns the largest displacement of any bucket: the `d1` multiplier of
+    /// the CHD layout, or the pilot of the `ptrhash` layout.
+    pub fn max_displacement(&self) -> u32 {
+        self.max_displacement
+    }
+
+    /// Returns the number of buckets by the number of significant bits of
+    /// their displacement.
+    ///
+    /// Element `0` counts the buckets with a displacement of 0, and element
+    /// `i` those with a displacement in `2^(i - 1)..2^i`. The histogram ends
+    /// at the bucket with the largest displacement.
+    pub fn displacement_histogram(&self) -> &[usize] {
+        let used = (u32::BITS - self.max_displacement.leading_zeros()) as usize + 1;
+        &self.histogram[..used]
+    }
+
+    /// Returns the number of slots past the entries that the `ptrhash` layout
+    /// remaps into them, which is 0 with the CHD layout.
+    pub fn remap_len(&self) -> usize {
+        self.remap_len
+    }
+}
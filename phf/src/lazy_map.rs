@@ -0,0 +1,293 @@
+//! An immutable map with lazily initialized values constructed at compile
+//! time.
+//!
+//! Requires the `std` feature.
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::{Index, Range};
+use core::slice;
+use phf_shared::{PhfEq, PhfHash};
+use std::sync::OnceLock;
+
+use crate::Map;
+
+/// An immutable map whose values are produced by an initializer the first
+/// time they are accessed.
+///
+/// Each value is initialized at most once and independently of the others, so
+/// looking up one entry never pays for constructing the rest. This suits
+/// tables of values that can't be built in a `const` context, such as
+/// compiled regular expressions.
+///
+/// If an initializer panics, the panic propagates to the caller and the value
+/// stays uninitialized; the next access runs the initializer again.
+///
+/// ## Note
+///
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`LazyMap::from_raw_parts`] instead.
+pub struct LazyMap<K: 'static, V: 'static> {
+    #[doc(hidden)]
+    pub map: Map<K, fn() -> V>,
+    /// One cell per entry of `map`, allocated on first access.
+    #[doc(hidden)]
+    pub values: OnceLock<Box<[OnceLock<V>]>>,
+}
+
+impl<K, V> fmt::Debug for LazyMap<K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut map = fmt.debug_map();
+        for (index, (key, _)) in self.map.entries.iter().enumerate() {
+            match self.initialized(index) {
+                Some(value) => map.entry(key, value),
+                None => map.entry(key, &format_args!("<uninit>")),
+            };
+        }
+        map.finish()
+    }
+}
+
+impl<'a, K, V, T: ?Sized> Index<&'a T> for LazyMap<K, V>
+where
+    T: Eq + PhfHash,
+    K: PhfEq<T>,
+{
+    type Output = V;
+
+    fn index(&self, k: &'a T) -> &V {
+        self.get(k).expect("invalid key")
+    }
+}
+
+impl<K, V> LazyMap<K, V> {
+    /// Creates a map from a map of its keys to the initializers of their
+    /// values.
+    pub const fn from_raw_parts(map: Map<K, fn() -> V>) -> Self {
+        LazyMap {
+            map,
+            values: OnceLock::new(),
+        }
+    }
+
+    /// Returns the number of entries in the `LazyMap`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns true if the `LazyMap` is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Determines if `key` is in the `LazyMap`.
+    ///
+    /// This does not initialize the value.
+    pub fn contains_key<T>(&self, key: &T) -> bool
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Returns a reference to the value that `key` maps to, initializing it
+    /// if this is the first access.
+    ///
+    /// ```
+    /// use phf::{LazyMap, phf_lazy_map};
+    ///
+    /// static GREETINGS: LazyMap<&'static str, String> = phf_lazy_map! {
+    ///     "en" => || "Hello".repeat(2),
+    ///     "de" => || "Hallo".repeat(2),
+    /// };
+    ///
+    /// assert_eq!(GREETINGS.get("en").map(String::as_str), Some("HelloHello"));
+    /// assert_eq!(GREETINGS.get_if_initialized("de"), None);
+    /// ```
+    pub fn get<T>(&self, key: &T) -> Option<&V>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.get_entry(key).map(|e| e.1)
+    }
+
+    /// Returns a reference to the map's internal static instance of the given
+    /// key.
+    ///
+    /// This does not initialize the value.
+    pub fn get_key<T>(&self, key: &T) -> Option<&K>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.map.get_key(key)
+    }
+
+    /// Like `get`, but returns both the key and the value.
+    pub fn get_entry<T>(&self, key: &T) -> Option<(&K, &V)>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        let index = self.map.find_index(key, |entry| entry.phf_eq(key))?;
+        Some((&self.map.entries[index].0, self.force(index)))
+    }
+
+    /// Like `get`, but returns `None` instead of running the initializer if
+    /// the value has not been initialized yet.
+    pub fn get_if_initialized<T>(&self, key: &T) -> Option<&V>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        let index = self.map.find_index(key, |entry| entry.phf_eq(key))?;
+        self.initialized(index)
+    }
+
+    /// Returns an iterator over the key/value pairs in the map, initializing
+    /// each value as it is reached.
+    ///
+    /// Entries are returned in an arbitrary but fixed order.
+    pub fn entries(&self) -> Entries<'_, K, V> {
+        Entries {
+            map: self,
+            range: 0..self.len(),
+        }
+    }
+
+    /// Returns an iterator over the keys in the map.
+    ///
+    /// Keys are returned in an arbitrary but fixed order. This does not
+    /// initialize any values.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys {
+            iter: self.map.entries.iter(),
+        }
+    }
+
+    fn cells(&self) -> &[OnceLock<V>] {
+        self.values
+            .get_or_init(|| (0..self.len()).map(|_| OnceLock::new()).collect())
+    }
+
+    fn force(&self, index: usize) -> &V {
+        self.cells()[index].get_or_init(self.map.entries[index].1)
+    }
+
+    fn initialized(&self, index: usize) -> Option<&V> {
+        self.values.get()?[index].get()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a LazyMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Entries<'a, K, V>;
+
+    fn into_iter(self) -> Entries<'a, K, V> {
+        self.entries()
+    }
+}
+
+/// An iterator over the key/value pairs in a `LazyMap`.
+pub struct Entries<'a, K: 'static, V: 'static> {
+    map: &'a LazyMap<K, V>,
+    range: Range<usize>,
+}
+
+impl<'a, K, V> Clone for Entries<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map,
+            range: self.range.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for Entries<'a, K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Entries<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        let index = self.range.next()?;
+        Some((&self.map.map.entries[index].0, self.map.force(index)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Entries<'a, K, V> {
+    fn next_back(&mut self) -> Option<(&'a K, &'a V)> {
+        let index = self.range.next_back()?;
+        Some((&self.map.map.entries[index].0, self.map.force(index)))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Entries<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Entries<'a, K, V> {}
+
+/// An iterator over the keys in a `LazyMap`.
+pub struct Keys<'a, K, V> {
+    iter: slice::Iter<'a, (K, fn() -> V)>,
+}
+
+impl<'a, K, V> Clone for Keys<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for Keys<'a, K, V>
+where
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.iter.next().map(|e| &e.0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Keys<'a, K, V> {
+    fn next_back(&mut self) -> Option<&'a K> {
+        self.iter.next_back().map(|e| &e.0)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Keys<'a, K, V> {}
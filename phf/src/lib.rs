@@ -38,6 +38,30 @@
 //! phf = { version = "0.14.0", default-features = false }
 //! ```
 //!
+//! Lookups are inlined into each map type they're used with by default. To
+//! share one copy of the hashing and indexing code between all maps instead,
+//! at a small cost in lookup speed, enable the `size` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! phf = { version = "0.14.0", default-features = false, features = ["size"] }
+//! ```
+//!
+//! For property tests and fuzz targets that feed a table's keys, or near
+//! misses of them, to the code under test, the `proptest` and `arbitrary`
+//! features add the `phf::proptest` and `phf::arbitrary` modules.
+//!
+//! Maps whose keys are only known at runtime can still be built without
+//! allocating, into fixed-size buffers, with [`ArrayMap`] or
+//! [`array_map::build_in_place`].
+//!
+//! String keys can be trimmed, lowercased, or otherwise normalized before they
+//! are hashed, by storing them as [`normalize::Normalized`] keys. The `Nfc`
+//! normalizer requires the `unicode-normalization` feature.
+//!
+//! The `dynamic` feature adds `phf::dynamic::Map`, which keeps the perfect hash
+//! lookup of a static table but supports insertion and removal.
+//!
 //! ## Example (with the `macros` feature enabled)
 //!
 //! ```rust
@@ -90,15 +114,36 @@ extern crate std as core;
 /// Requires the `macros` feature.
 ///
 /// Supported key expressions are:
-/// - literals: bools, (byte) strings, bytes, chars, and integers (integer
+/// - literals: bools, (byte) strings, C strings, bytes, chars, and integers (integer
 ///   literals in the first key's type shape must have suffixes; later
 ///   unsuffixed integers infer from the same position in that first key)
 /// - arrays of `u8` integer literals
 /// - tuples of any supported key expressions, up to 12 elements
 /// - dereferenced byte string literals
+/// - float literals with `f32` or `f64` suffixes, which become [`TotalF32`] or
+///   [`TotalF64`] keys, and `TotalF32(..)` or `TotalF64(..)` of them
+/// - `()`, `None`, and `Some`, `Ok` or `Err` of any supported key expression
+/// - `Ordering::Less`, `Ordering::Equal` and `Ordering::Greater`
+/// - `NonZeroU32::new(1).unwrap()` and the like for every `NonZero` integer,
+///   or `NonZero::new(1u32).unwrap()`
+/// - `Ipv4Addr::new(..)`, `Ipv6Addr::new(..)`, their `LOCALHOST` and
+///   `UNSPECIFIED` constants, `Ipv4Addr::BROADCAST`, and `IpAddr::V4(..)` or
+///   `IpAddr::V6(..)` of those
+/// - `SocketAddrV4::new(..)`, `SocketAddrV6::new(..)`, and `SocketAddr::V4(..)`
+///   or `SocketAddr::V6(..)` of those
+/// - `Duration::new(..)`, `Duration::from_secs(..)`, `from_millis`,
+///   `from_micros`, `from_nanos`, `Duration::ZERO` and `Duration::MAX`
 /// - OR patterns using `|` to map multiple keys to the same value
+/// - `AsciiNoCase(string)` or `AsciiNoCase(byte string)`
+/// - `Normalized::<N, _>::new(string)`, where `N` is one of the normalizers in
+///   [`normalize`] or a tuple of them
 /// - `UniCase::unicode(string)`, `UniCase::ascii(string)`, or `Ascii::new(string)` if the `unicase` feature is enabled
 /// - `UncasedStr::new(string)` if the `uncased` feature is enabled
+/// - `SmolStr::new_static(string)` or `SmolStr::new_inline(string)` if the
+///   `smol_str` feature is enabled
+/// - `CompactString::const_new(string)` if the `compact_str` feature is enabled
+/// - `Uuid::from_u128(integer)` or `Uuid::from_bytes(array)` if the `uuid`
+///   feature is enabled
 ///
 /// All keys must use the same supported key expression type as the first key.
 ///
@@ -136,14 +181,222 @@ extern crate std as core;
 ///     assert_eq!(OPERATORS["plus"], "addition");
 /// }
 /// ```
+///
+/// # Scanning Text
+///
+/// A map with string or byte string keys that starts with `#![find]` is
+/// generated as a [`find::FindMap`], which records the lengths of its keys so
+/// that `find_iter` doesn't have to collect them from the keys first:
+///
+/// ```
+/// use phf::phf_map;
+/// use phf::find::FindMap;
+///
+/// static EMOTICONS: FindMap<&'static str, char> = phf_map! {
+///     #![find]
+///     ":)" => '🙂',
+///     ":(" => '🙁',
+/// };
+///
+/// fn main() {
+///     let found = EMOTICONS.find_iter("hi :)").map(|m| *m.value()).collect::<String>();
+///     assert_eq!(found, "🙂");
+/// }
+/// ```
+///
+/// # Typo Suggestions
+///
+/// A map with string keys that starts with `#![suggest]` is generated as a
+/// [`suggest::SuggestMap`], along with a deletion table for finding the keys
+/// within `max_distance` edits of a query (2 by default). Requires the `std`
+/// feature.
+///
+/// ```
+/// use phf::phf_map;
+/// use phf::suggest::SuggestMap;
+///
+/// static COMMANDS: SuggestMap<&'static str, u32> = phf_map! {
+///     #![suggest(max_distance = 1)]
+///     "build" => 0,
+///     "check" => 1,
+/// };
+///
+/// fn main() {
+///     assert_eq!(COMMANDS.suggest("chek", 1), [(&"check", &1, 1)]);
+/// }
+/// ```
+///
+/// # Default Values
+///
+/// A trailing `_ => value` arm creates a [`MapWithDefault`], which returns
+/// `value` for keys that aren't in the map:
+///
+/// ```
+/// use phf::{phf_map, MapWithDefault};
+///
+/// static PRECEDENCE: MapWithDefault<&'static str, u8> = phf_map! {
+///     "*" | "/" => 2,
+///     "+" | "-" => 1,
+///     _ => 0,
+/// };
+///
+/// fn main() {
+///     assert_eq!(PRECEDENCE["*"], 2);
+///     assert_eq!(PRECEDENCE["?"], 0);
+/// }
+/// ```
+///
+/// # ASCII Case-Insensitive Keys
+///
+/// A leading `#![ascii_case_insensitive]` wraps every string key in an
+/// [`AsciiNoCase`], and rejects keys that only differ in ASCII case. This also
+/// works in the set macros.
+///
+/// ```
+/// use phf::{phf_map, AsciiNoCase, Map};
+///
+/// static HEADERS: Map<AsciiNoCase<&'static str>, u8> = phf_map! {
+///     #![ascii_case_insensitive]
+///     "Content-Type" => 1,
+///     "Content-Length" => 2,
+/// };
+///
+/// fn main() {
+///     assert_eq!(HEADERS.get(&AsciiNoCase("content-type")), Some(&1));
+///     assert_eq!(HEADERS.get(&AsciiNoCase(&b"CONTENT-LENGTH"[..])), Some(&2));
+/// }
+/// ```
+///
+/// # Normalized Keys
+///
+/// A leading `#![normalize(..)]` wraps every string key in a
+/// [`normalize::Normalized`] with the listed normalizers, applied from left to
+/// right. Bare names refer to the normalizers in [`normalize`]. Keys that are
+/// equal after normalization are rejected.
+///
+/// ```
+/// use phf::normalize::{DashUnderscore, Lowercase, Normalized};
+/// use phf::{phf_map, Map};
+///
+/// type Flag<S> = Normalized<(Lowercase, DashUnderscore), S>;
+///
+/// static FLAGS: Map<Flag<&'static str>, u8> = phf_map! {
+///     #![normalize(Lowercase, DashUnderscore)]
+///     "dry-run" => 1,
+///     "no_color" => 2,
+/// };
+///
+/// fn main() {
+///     assert_eq!(FLAGS.get(&Flag::new("Dry_Run")), Some(&1));
+///     assert_eq!(FLAGS.get(&Flag::new("NO-COLOR")), Some(&2));
+/// }
+/// ```
+///
+/// # C Strings and Paths
+///
+/// `c"..."` keys create a `Map<&'static CStr, V>`, which can be looked up with
+/// a `CStr` borrowed from a C string pointer without validating it as UTF-8.
+/// `Path` and `OsStr` can't be built in a `static`, so a static table of paths
+/// is keyed by their bytes, while maps built at runtime, like [`ArrayMap`], can
+/// be keyed by `&Path` directly.
+///
+/// ```
+/// use std::ffi::{c_char, CStr};
+/// use std::path::Path;
+///
+/// use phf::{phf_map, Map};
+///
+/// static EVENTS: Map<&'static CStr, u8> = phf_map! {
+///     c"open" => 1,
+///     c"close" => 2,
+/// };
+///
+/// static LANGUAGES: Map<&'static [u8], &'static str> = phf_map! {
+///     b"Cargo.toml" => "toml",
+///     b"src/main.rs" => "rust",
+/// };
+///
+/// unsafe extern "C" fn on_event(name: *const c_char) -> u8 {
+///     let name = unsafe { CStr::from_ptr(name) };
+///     EVENTS.get(name).copied().unwrap_or(0)
+/// }
+///
+/// fn main() {
+///     assert_eq!(unsafe { on_event(c"close".as_ptr()) }, 2);
+///     let path = Path::new("src/main.rs");
+///     assert_eq!(LANGUAGES.get(path.as_os_str().as_encoded_bytes()), Some(&"rust"));
+/// }
+/// ```
 pub use phf_macros::phf_map;
 
 #[cfg(feature = "macros")]
 /// Macro to create a `static` (compile-time) [`OrderedMap`].
 ///
-/// Requires the `macros` feature. Same usage as [`phf_map`].
+/// Requires the `macros` feature. Same usage as [`phf_map`]; with a `_ => value`
+/// arm, it creates a `MapWithDefault<K, V, OrderedMap<K, V>>`.
 pub use phf_macros::phf_ordered_map;
 
+#[cfg(feature = "macros")]
+/// Macro to create a `static` (compile-time) [`GroupedMap`].
+///
+/// Requires the `macros` feature. Same usage as [`phf_map`], except that every
+/// key must be a tuple; entries are grouped by the first tuple component.
+pub use phf_macros::phf_grouped_map;
+
+#[cfg(feature = "macros")]
+/// Macro to create a `static` (compile-time) [`IpPrefixMap`].
+///
+/// Requires the `macros` feature. Keys are networks in CIDR notation, such as
+/// `"10.0.0.0/8"` or `"2001:db8::/32"`; an address without a prefix length is
+/// a single-host network. See [`IpPrefixMap::get`] for an example.
+pub use phf_macros::phf_ip_prefix_map;
+
+#[cfg(all(feature = "macros", feature = "std"))]
+/// Macro to create a `static` (compile-time) [`LazyMap`].
+///
+/// Requires the `macros` and `std` features. Same usage as [`phf_map`],
+/// except that values are initializers: closures without captures, or paths
+/// to functions, taking no arguments and returning the value. See
+/// [`LazyMap::get`] for an example.
+pub use phf_macros::phf_lazy_map;
+
+#[cfg(feature = "macros")]
+/// Macro to create several `static` [`Map`]s that share one hash key.
+///
+/// Requires the `macros` feature. Each map takes the same entries as
+/// [`phf_map`], except that `#[cfg]` attributes on keys are not supported.
+/// Since the maps share a hash key, a [`PreparedKey`] created from one of them
+/// can be looked up in all of them while only hashing the key once.
+///
+/// # Example
+///
+/// ```
+/// phf::phf_map_family! {
+///     static KEYWORDS: phf::Map<&'static str, u32> = {
+///         "fn" => 0,
+///         "let" => 1,
+///     };
+///     pub static BUILTINS: phf::Map<&'static str, u32> = {
+///         "print" => 0,
+///     };
+/// }
+///
+/// fn main() {
+///     let ident = KEYWORDS.prepare("let");
+///     assert_eq!(KEYWORDS.get_prepared(&ident), Some(&1));
+///     assert_eq!(BUILTINS.get_prepared(&ident), None);
+/// }
+/// ```
+pub use phf_macros::phf_map_family;
+
+#[cfg(feature = "macros")]
+/// Macro to create a `static` (compile-time) [`RangeMap`].
+///
+/// Requires the `macros` feature. Keys are inclusive ranges (`'a'..='z'`) or
+/// single keys (`'_'`) of characters or integers, and must not overlap. See
+/// [`RangeMap::get`] for an example.
+pub use phf_macros::phf_range_map;
+
 #[cfg(feature = "macros")]
 /// Macro to create a `static` (compile-time) [`Set`].
 ///
@@ -184,8 +437,36 @@ pub use phf_macros::phf_ordered_map;
 ///     assert!(KEYWORDS.contains("for"));
 /// }
 /// ```
+///
+/// Like [`phf_map!`], a set of strings or byte strings that starts with
+/// `#![find]` is generated as a [`find::FindSet`], and a set of strings that
+/// starts with `#![suggest]` as a [`suggest::SuggestSet`].
 pub use phf_macros::phf_set;
 
+#[cfg(feature = "macros")]
+/// Macro to create a `static` (compile-time) [`Filter`].
+///
+/// Requires the `macros` feature. Same usage as [`phf_set`]. The fingerprint
+/// width, and so the false-positive rate, is chosen by the type of the
+/// `static`.
+///
+/// # Example
+///
+/// ```
+/// use phf::{Filter, phf_filter};
+///
+/// // Roughly one in 256 other keys is reported as present.
+/// static STOP_WORDS: Filter<u8> = phf_filter! {
+///     "a", "an", "the",
+/// };
+///
+/// fn main() {
+///     assert!(STOP_WORDS.maybe_contains("the"));
+///     assert_eq!(STOP_WORDS.false_positive_rate(), 1.0 / 256.0);
+/// }
+/// ```
+pub use phf_macros::phf_filter;
+
 #[cfg(feature = "macros")]
 /// Macro to create a `static` (compile-time) [`OrderedSet`].
 ///
@@ -259,17 +540,483 @@ macro_rules! __resolve_cfg {
     }};
 }
 
+// `__map` and `__ordered_map` are what generated code uses to call
+// `Map::from_raw_parts` and `OrderedMap::from_raw_parts`. A call would make the
+// entries temporaries that are only `'static` if they can be promoted, which
+// rules out keys like `*b"..."` and values with destructors, so these check
+// the parts by calling `from_raw_parts` on placeholder entries in a `const`
+// and then initialize the fields directly. Since they ship with the runtime,
+// generated code doesn't depend on the fields. The first argument is the
+// `phf_shared::TABLE_FORMAT` of the generator, and tables that don't match
+// any arm, such as tables generated with a different `ptrhash` setting, are
+// reported by their format.
+#[cfg(not(feature = "ptrhash"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __map {
+    (
+        $format:expr,
+        $key:expr,
+        [$($disp:expr),* $(,)?],
+        [$($entry:expr),* $(,)?]
+        $(, $counters:expr)? $(,)?
+    ) => {{
+        const _: () = {
+            $crate::__check_table_format($format);
+            const LEN: usize = <[&str]>::len(&[$(::core::stringify!($entry)),*]);
+            $crate::Map::<(), ()>::from_raw_parts($key, &[$($disp),*], &[((), ()); LEN]);
+        };
+        // Only the `instrument` feature leaves fields to update.
+        #[allow(clippy::needless_update)]
+        $crate::Map {
+            key: $key,
+            disps: &[$($disp),*],
+            entries: &[$($entry),*],
+            ..$crate::__map_base!($($counters)?)
+        }
+    }};
+    ($format:expr, $($rest:tt)*) => {
+        $crate::__mismatched_table($format)
+    };
+}
+
+#[cfg(feature = "ptrhash")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __map {
+    (
+        $format:expr,
+        $key:expr,
+        [$($pilot:expr),* $(,)?],
+        [$($remap:expr),* $(,)?],
+        [$($entry:expr),* $(,)?]
+        $(, $counters:expr)? $(,)?
+    ) => {{
+        const _: () = {
+            $crate::__check_table_format($format);
+            const LEN: usize = <[&str]>::len(&[$(::core::stringify!($entry)),*]);
+            $crate::Map::<(), ()>::from_raw_parts(
+                $key,
+                &[$($pilot),*],
+                &[$($remap),*],
+                &[((), ()); LEN],
+            );
+        };
+        // Only the `instrument` feature leaves fields to update.
+        #[allow(clippy::needless_update)]
+        $crate::Map {
+            key: $key,
+            pilots: &[$($pilot),*],
+            remap: &[$($remap),*],
+            entries: &[$($entry),*],
+            ..$crate::__map_base!($($counters)?)
+        }
+    }};
+    ($format:expr, $($rest:tt)*) => {
+        $crate::__mismatched_table($format)
+    };
+}
+
+// The fields of a `Map` that `__map` doesn't initialize. Without the
+// `instrument` feature, counters from generators that have it are dropped.
+#[cfg(not(feature = "instrument"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __map_base {
+    ($($counters:expr)?) => {
+        $crate::Map::new()
+    };
+}
+
+#[cfg(feature = "instrument")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __map_base {
+    () => {
+        $crate::Map::new()
+    };
+    ($counters:expr) => {
+        $crate::Map::__with_counters_base($counters)
+    };
+}
+
+#[cfg(not(feature = "ptrhash"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ordered_map {
+    (
+        $format:expr,
+        $key:expr,
+        [$($disp:expr),* $(,)?],
+        [$($idx:expr),* $(,)?],
+        [$($entry:expr),* $(,)?]
+        $(, $counters:expr)? $(,)?
+    ) => {{
+        const _: () = {
+            $crate::__check_table_format($format);
+            const LEN: usize = <[&str]>::len(&[$(::core::stringify!($entry)),*]);
+            $crate::OrderedMap::<(), ()>::from_raw_parts(
+                $key,
+                &[$($disp),*],
+                &[$($idx),*],
+                &[((), ()); LEN],
+            );
+        };
+        // Only the `instrument` feature leaves fields to update.
+        #[allow(clippy::needless_update)]
+        $crate::OrderedMap {
+            key: $key,
+            disps: &[$($disp),*],
+            idxs: &[$($idx),*],
+            entries: &[$($entry),*],
+            ..$crate::__ordered_map_base!($($counters)?)
+        }
+    }};
+    ($format:expr, $($rest:tt)*) => {
+        $crate::__mismatched_table($format)
+    };
+}
+
+#[cfg(feature = "ptrhash")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ordered_map {
+    (
+        $format:expr,
+        $key:expr,
+        [$($pilot:expr),* $(,)?],
+        [$($remap:expr),* $(,)?],
+        [$($idx:expr),* $(,)?],
+        [$($entry:expr),* $(,)?]
+        $(, $counters:expr)? $(,)?
+    ) => {{
+        const _: () = {
+            $crate::__check_table_format($format);
+            const LEN: usize = <[&str]>::len(&[$(::core::stringify!($entry)),*]);
+            $crate::OrderedMap::<(), ()>::from_raw_parts(
+                $key,
+                &[$($pilot),*],
+                &[$($remap),*],
+                &[$($idx),*],
+                &[((), ()); LEN],
+            );
+        };
+        // Only the `instrument` feature leaves fields to update.
+        #[allow(clippy::needless_update)]
+        $crate::OrderedMap {
+            key: $key,
+            pilots: &[$($pilot),*],
+            remap: &[$($remap),*],
+            idxs: &[$($idx),*],
+            entries: &[$($entry),*],
+            ..$crate::__ordered_map_base!($($counters)?)
+        }
+    }};
+    ($format:expr, $($rest:tt)*) => {
+        $crate::__mismatched_table($format)
+    };
+}
+
+// Filters hold no entries, so `__filter` can call `Filter::from_raw_parts`
+// directly once the format is checked.
+#[cfg(not(feature = "ptrhash"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __filter {
+    (
+        $format:expr,
+        $key:expr,
+        [$($disp:expr),* $(,)?],
+        [$($fingerprint:expr),* $(,)?] $(,)?
+    ) => {{
+        const _: () = $crate::__check_table_format($format);
+        $crate::Filter::from_raw_parts($key, &[$($disp),*], &[$($fingerprint),*])
+    }};
+    ($format:expr, $($rest:tt)*) => {
+        $crate::__mismatched_table($format)
+    };
+}
+
+#[cfg(feature = "ptrhash")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __filter {
+    (
+        $format:expr,
+        $key:expr,
+        [$($pilot:expr),* $(,)?],
+        [$($remap:expr),* $(,)?],
+        [$($fingerprint:expr),* $(,)?] $(,)?
+    ) => {{
+        const _: () = $crate::__check_table_format($format);
+        $crate::Filter::from_raw_parts(
+            $key,
+            &[$($pilot),*],
+            &[$($remap),*],
+            &[$($fingerprint),*],
+        )
+    }};
+    ($format:expr, $($rest:tt)*) => {
+        $crate::__mismatched_table($format)
+    };
+}
+
+// The fields of an `OrderedMap` that `__ordered_map` doesn't initialize.
+#[cfg(not(feature = "instrument"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ordered_map_base {
+    ($($counters:expr)?) => {
+        $crate::OrderedMap::__empty()
+    };
+}
+
+#[cfg(feature = "instrument")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ordered_map_base {
+    () => {
+        $crate::OrderedMap::__empty()
+    };
+    ($counters:expr) => {
+        $crate::OrderedMap::__with_counters_base($counters)
+    };
+}
+
+// The types below wrap maps built by `__map` or `__ordered_map`, so generated
+// code initializes them with these rather than calling their `from_raw_parts`,
+// for the same reason. Each takes the arguments of its `from_raw_parts`, after
+// the table format for the types with a layout of their own.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __set {
+    ($map:expr $(,)?) => {
+        $crate::Set { map: $map }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ordered_set {
+    ($map:expr $(,)?) => {
+        $crate::OrderedSet { map: $map }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __map_with_default {
+    ($map:expr, $default:expr $(,)?) => {
+        $crate::MapWithDefault {
+            map: $map,
+            default: $default,
+            marker: ::core::marker::PhantomData,
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __find_map {
+    ($map:expr, $key_lengths:expr $(,)?) => {
+        $crate::find::FindMap {
+            map: $map,
+            key_lengths: $key_lengths,
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __find_set {
+    ($set:expr, $key_lengths:expr $(,)?) => {
+        $crate::find::FindSet {
+            set: $set,
+            key_lengths: $key_lengths,
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __suggest_map {
+    ($map:expr, $index:expr $(,)?) => {
+        $crate::suggest::SuggestMap {
+            map: $map,
+            index: $index,
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __suggest_set {
+    ($set:expr, $index:expr $(,)?) => {
+        $crate::suggest::SuggestSet {
+            set: $set,
+            index: $index,
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __lazy_map {
+    ($map:expr $(,)?) => {
+        $crate::LazyMap {
+            map: $map,
+            values: ::std::sync::OnceLock::new(),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __grouped_map {
+    ($format:expr, $map:expr, $groups:expr $(,)?) => {{
+        const _: () = $crate::__check_table_format($format);
+        $crate::GroupedMap {
+            map: $map,
+            groups: $groups,
+        }
+    }};
+    ($format:expr, $($rest:tt)*) => {
+        $crate::__mismatched_table($format)
+    };
+}
+
+// Unlike the maps, the prefix lengths can be checked on placeholder tables.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ip_prefix_map {
+    (
+        $format:expr,
+        [$(($len4:expr, $map4:expr)),* $(,)?],
+        [$(($len6:expr, $map6:expr)),* $(,)?] $(,)?
+    ) => {{
+        const _: () = {
+            $crate::__check_table_format($format);
+            $crate::IpPrefixMap::<()>::from_raw_parts(
+                &[$(($len4, $crate::Map::new())),*],
+                &[$(($len6, $crate::Map::new())),*],
+            );
+        };
+        $crate::IpPrefixMap {
+            v4: &[$(($len4, $map4)),*],
+            v6: &[$(($len6, $map6)),*],
+        }
+    }};
+    ($format:expr, $($rest:tt)*) => {
+        $crate::__mismatched_table($format)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __range_map {
+    (
+        $format:expr,
+        $singletons:expr,
+        [$($range:expr),* $(,)?]
+        $(, $counters:expr)? $(,)?
+    ) => {{
+        const _: () = $crate::__check_table_format($format);
+        // Only the `instrument` feature leaves fields to update.
+        #[allow(clippy::needless_update)]
+        $crate::RangeMap {
+            singletons: $singletons,
+            ranges: &[$($range),*],
+            ..$crate::__range_map_base!($($counters)?)
+        }
+    }};
+    ($format:expr, $($rest:tt)*) => {
+        $crate::__mismatched_table($format)
+    };
+}
+
+// The fields of a `RangeMap` that `__range_map` doesn't initialize.
+#[cfg(not(feature = "instrument"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __range_map_base {
+    ($($counters:expr)?) => {
+        $crate::RangeMap::from_raw_parts($crate::OrderedMap::__empty(), &[])
+    };
+}
+
+#[cfg(feature = "instrument")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __range_map_base {
+    () => {
+        $crate::RangeMap::from_raw_parts($crate::OrderedMap::__empty(), &[])
+    };
+    ($counters:expr) => {
+        $crate::RangeMap::__with_counters_base($counters)
+    };
+}
+
+#[doc(hidden)]
+pub use self::lookup::{__check_table_format, __mismatched_table};
+
+#[doc(inline)]
+pub use self::array_map::ArrayMap;
+#[doc(inline)]
+pub use self::filter::Filter;
+#[doc(inline)]
+pub use self::find::MatchKind;
+#[doc(inline)]
+pub use self::grouped_map::GroupedMap;
+#[doc(inline)]
+pub use self::ip_prefix_map::IpPrefixMap;
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use self::lazy_map::LazyMap;
 #[doc(inline)]
 pub use self::map::Map;
 #[doc(inline)]
+pub use self::map_with_default::MapWithDefault;
+#[doc(inline)]
 pub use self::ordered_map::OrderedMap;
 #[doc(inline)]
 pub use self::ordered_set::OrderedSet;
 #[doc(inline)]
+pub use self::prepared::PreparedKey;
+#[doc(inline)]
+pub use self::range_map::RangeMap;
+#[doc(inline)]
 pub use self::set::Set;
-pub use phf_shared::{PhfEq, PhfHash};
+pub use phf_shared::normalize;
+pub use phf_shared::{AsciiNoCase, PhfEq, PhfHash, TotalF32, TotalF64};
 
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+pub mod array_map;
+#[cfg(feature = "dynamic")]
+pub mod dynamic;
+pub mod filter;
+pub mod find;
+pub mod grouped_map;
+#[cfg(feature = "instrument")]
+pub mod instrument;
+pub mod ip_prefix_map;
+pub mod layout;
+#[cfg(feature = "std")]
+pub mod lazy_map;
+mod lookup;
 pub mod map;
+pub mod map_with_default;
+#[cfg(any(feature = "proptest", feature = "arbitrary"))]
+pub mod near_miss;
 pub mod ordered_map;
 pub mod ordered_set;
+pub mod prepared;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+pub mod range_map;
 pub mod set;
+#[cfg(feature = "std")]
+pub mod suggest;
@@ -0,0 +1,198 @@
+//! The non-generic core of table lookups.
+//!
+//! Turning a hash into a slot index only depends on the table's hash
+//! parameters and length, not on its key and value types, so it's done here
+//! on plain slices rather than in the generic table types; only comparing the
+//! key found at the index stays generic. With the `size` feature, these
+//! functions and the SipHash implementation are never inlined, so all tables
+//! share one copy of them.
+use core::mem;
+use phf_shared::{HashKey, PhfHash};
+
+use crate::layout::{LayoutStats, MemoryUsage};
+
+#[cfg(not(feature = "ptrhash"))]
+pub(crate) type KeyHash = phf_shared::Hashes;
+
+#[cfg(feature = "ptrhash")]
+pub(crate) type KeyHash = u64;
+
+#[cfg(not(feature = "ptrhash"))]
+#[inline]
+pub(crate) fn hash<T: ?Sized + PhfHash>(key: &T, hash_key: &HashKey) -> KeyHash {
+    phf_shared::hash(key, hash_key)
+}
+
+#[cfg(feature = "ptrhash")]
+#[inline]
+pub(crate) fn hash<T: ?Sized + PhfHash>(key: &T, hash_key: &HashKey) -> KeyHash {
+    phf_shared::ptrhash::hash(key, hash_key)
+}
+
+/// Panics if a generated table's `format` differs from the one this crate's
+/// lookups are for.
+#[doc(hidden)]
+pub const fn __check_table_format(format: &str) {
+    if !str_eq(format, phf_shared::TABLE_FORMAT) {
+        panic!(
+            "this table was generated for different hash functions or a different \
+             table layout than this version of `phf` uses; generate it with matching \
+             versions of `phf_shared` and the same `ptrhash` setting"
+        );
+    }
+}
+
+/// The expansion of a generated table whose parts don't match the ones this
+/// crate expects, which fails to compile.
+#[doc(hidden)]
+pub const fn __mismatched_table<T>(format: &str) -> T {
+    __check_table_format(format);
+    panic!("the parts of this table are malformed");
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Panics if `disps` can't be the displacements of a table with `len` slots.
+#[cfg(not(feature = "ptrhash"))]
+pub(crate) const fn check_params(disps: &[(u32, u32)], len: usize) {
+    assert!(
+        disps.is_empty() == (len == 0),
+        "`disps` must be empty exactly when there are no entries"
+    );
+    assert!(
+        disps.len() <= len,
+        "`disps` must not be longer than the entries"
+    );
+}
+
+/// Panics if `pilots` and `remap` can't be the parameters of a table with
+/// `len` slots.
+#[cfg(feature = "ptrhash")]
+pub(crate) const fn check_params(pilots: &[u8], remap: &[u32], len: usize) {
+    assert!(
+        len == 0 || !pilots.is_empty(),
+        "`pilots` must not be empty when there are entries"
+    );
+    assert!(
+        len + remap.len() <= u32::MAX as usize,
+        "too many slots for 32-bit indices"
+    );
+    let mut i = 0;
+    while i < remap.len() {
+        assert!(
+            (remap[i] as usize) < len,
+            "`remap` index out of bounds of the entries"
+        );
+        i += 1;
+    }
+}
+
+/// Panics if `idxs` isn't a mapping of `len` slots to `len` entries.
+pub(crate) const fn check_idxs(idxs: &[usize], len: usize) {
+    assert!(idxs.len() == len, "`idxs` must have one index per entry");
+    let mut i = 0;
+    while i < idxs.len() {
+        assert!(idxs[i] < len, "`idxs` index out of bounds of the entries");
+        i += 1;
+    }
+}
+
+/// The hash parameters of a table with `len` slots.
+#[cfg(not(feature = "ptrhash"))]
+#[derive(Clone, Copy)]
+pub(crate) struct Params<'a> {
+    pub(crate) disps: &'a [(u32, u32)],
+    pub(crate) len: usize,
+}
+
+/// The hash parameters of a table with `len` slots.
+#[cfg(feature = "ptrhash")]
+#[derive(Clone, Copy)]
+pub(crate) struct Params<'a> {
+    pub(crate) key: HashKey,
+    pub(crate) pilots: &'a [u8],
+    pub(crate) remap: &'a [u32],
+    pub(crate) len: usize,
+}
+
+impl Params<'_> {
+    /// Returns the slot of a key with hash `hash`, or `None` if the table is
+    /// empty.
+    #[cfg(not(feature = "ptrhash"))]
+    #[cfg_attr(feature = "size", inline(never))]
+    #[cfg_attr(not(feature = "size"), inline)]
+    pub(crate) fn index(self, hash: &KeyHash) -> Option<usize> {
+        if self.disps.is_empty() || self.len == 0 {
+            return None;
+        }
+        Some(phf_shared::get_index(hash, self.disps, self.len) as usize)
+    }
+
+    /// Returns the slot of a key with hash `hash`, or `None` if the table is
+    /// empty.
+    #[cfg(feature = "ptrhash")]
+    #[cfg_attr(feature = "size", inline(never))]
+    #[cfg_attr(not(feature = "size"), inline)]
+    pub(crate) fn index(self, hash: &KeyHash) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+        Some(
+            phf_shared::ptrhash::get_index(self.key, *hash, self.pilots, self.remap, self.len)
+                as usize,
+        )
+    }
+
+    /// Returns the memory usage of a table with these parameters, whose
+    /// entries and slot-to-entry indices take `entries` and `idxs` bytes.
+    #[cfg(not(feature = "ptrhash"))]
+    pub(crate) fn memory_usage(self, entries: usize, idxs: usize) -> MemoryUsage {
+        MemoryUsage {
+            entries,
+            displacements: mem::size_of_val(self.disps),
+            remap: 0,
+            idxs,
+            auxiliary: 0,
+        }
+    }
+
+    /// Returns the memory usage of a table with these parameters, whose
+    /// entries and slot-to-entry indices take `entries` and `idxs` bytes.
+    #[cfg(feature = "ptrhash")]
+    pub(crate) fn memory_usage(self, entries: usize, idxs: usize) -> MemoryUsage {
+        MemoryUsage {
+            entries,
+            displacements: mem::size_of_val(self.pilots),
+            remap: mem::size_of_val(self.remap),
+            idxs,
+            auxiliary: 0,
+        }
+    }
+
+    #[cfg(not(feature = "ptrhash"))]
+    pub(crate) fn layout_stats(self) -> LayoutStats {
+        LayoutStats::new(self.len, 0, self.disps.iter().map(|&(d1, _)| d1))
+    }
+
+    #[cfg(feature = "ptrhash")]
+    pub(crate) fn layout_stats(self) -> LayoutStats {
+        LayoutStats::new(
+            self.len,
+            self.remap.len(),
+            self.pilots.iter().map(|&pilot| u32::from(pilot)),
+        )
+    }
+}
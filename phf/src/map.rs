@@ -2,9 +2,19 @@
 use core::fmt;
 use core::iter::FusedIterator;
 use core::iter::IntoIterator;
-use core::ops::Index;
+use core::mem;
+use core::ops::{Index, Range};
 use core::slice;
-use phf_shared::{self, HashKey, PhfEq, PhfHash};
+use phf_shared::{self, ByteChunks, HashKey, PhfBorrow, PhfEq, PhfHash};
+
+use crate::find::{FindIter, Finder, Lengths};
+#[cfg(feature = "instrument")]
+use crate::instrument::{Counters, MapStats};
+use crate::layout::{LayoutStats, MemoryUsage};
+use crate::lookup::{self, KeyHash, Params};
+use crate::prepared::PreparedKey;
+#[cfg(feature = "std")]
+use crate::suggest;
 #[cfg(feature = "serde")]
 use serde::ser::{Serialize, SerializeMap, Serializer};
 
@@ -12,9 +22,10 @@ use serde::ser::{Serialize, SerializeMap, Serializer};
 ///
 /// ## Note
 ///
-/// The fields of this struct are public so that they may be initialized by the
-/// `phf_map!` macro and code generation. They are subject to change at any
-/// time and should never be accessed directly.
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`Map::from_raw_parts`] instead.
 #[cfg(not(feature = "ptrhash"))]
 pub struct Map<K: 'static, V: 'static> {
     #[doc(hidden)]
@@ -23,15 +34,19 @@ pub struct Map<K: 'static, V: 'static> {
     pub disps: &'static [(u32, u32)],
     #[doc(hidden)]
     pub entries: &'static [(K, V)],
+    #[cfg(feature = "instrument")]
+    #[doc(hidden)]
+    pub counters: &'static Counters,
 }
 
 /// An immutable map constructed at compile time.
 ///
 /// ## Note
 ///
-/// The fields of this struct are public so that they may be initialized by the
-/// `phf_map!` macro and code generation. They are subject to change at any
-/// time and should never be accessed directly.
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`Map::from_raw_parts`] instead.
 #[cfg(feature = "ptrhash")]
 pub struct Map<K: 'static, V: 'static> {
     #[doc(hidden)]
@@ -42,6 +57,9 @@ pub struct Map<K: 'static, V: 'static> {
     pub remap: &'static [u32],
     #[doc(hidden)]
     pub entries: &'static [(K, V)],
+    #[cfg(feature = "instrument")]
+    #[doc(hidden)]
+    pub counters: &'static Counters,
 }
 
 impl<K, V> fmt::Debug for Map<K, V>
@@ -107,6 +125,8 @@ impl<K, V> Map<K, V> {
             key: 0,
             disps: &[],
             entries: &[],
+            #[cfg(feature = "instrument")]
+            counters: &crate::instrument::EMPTY,
         };
 
         #[cfg(feature = "ptrhash")]
@@ -115,9 +135,97 @@ impl<K, V> Map<K, V> {
             pilots: &[],
             remap: &[],
             entries: &[],
+            #[cfg(feature = "instrument")]
+            counters: &crate::instrument::EMPTY,
         };
     }
 
+    /// Creates a map from the hash parameters and entries computed by
+    /// `phf_generator`.
+    ///
+    /// `phf_map!` and `phf_codegen` construct maps through this rather than by
+    /// initializing their fields, so that generated code keeps compiling when
+    /// the fields of `Map` change. `entries` are in
+    /// slot order, as laid out by `HashState::map`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parts are inconsistent, which fails compilation when the
+    /// map initializes a `static` or `const`.
+    #[cfg(not(feature = "ptrhash"))]
+    pub const fn from_raw_parts(
+        key: HashKey,
+        disps: &'static [(u32, u32)],
+        entries: &'static [(K, V)],
+    ) -> Self {
+        lookup::check_params(disps, entries.len());
+        Self {
+            key,
+            disps,
+            entries,
+            #[cfg(feature = "instrument")]
+            counters: &crate::instrument::EMPTY,
+        }
+    }
+
+    /// Creates a map from the hash parameters and entries computed by
+    /// `phf_generator`.
+    ///
+    /// `phf_map!` and `phf_codegen` construct maps through this rather than by
+    /// initializing their fields, so that generated code keeps compiling when
+    /// the fields of `Map` change. `entries` are in
+    /// slot order, as laid out by `HashState::map`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parts are inconsistent, which fails compilation when the
+    /// map initializes a `static` or `const`.
+    #[cfg(feature = "ptrhash")]
+    pub const fn from_raw_parts(
+        key: HashKey,
+        pilots: &'static [u8],
+        remap: &'static [u32],
+        entries: &'static [(K, V)],
+    ) -> Self {
+        lookup::check_params(pilots, remap, entries.len());
+        Self {
+            key,
+            pilots,
+            remap,
+            entries,
+            #[cfg(feature = "instrument")]
+            counters: &crate::instrument::EMPTY,
+        }
+    }
+
+    /// An empty map with `counters`, for `__map!` to fill in.
+    #[cfg(feature = "instrument")]
+    #[doc(hidden)]
+    pub const fn __with_counters_base(counters: &'static Counters) -> Self {
+        let mut map = Self::new();
+        map.counters = counters;
+        map
+    }
+
+    /// Sets the lookup counters of the map.
+    ///
+    /// Maps created without counters aren't counted.
+    ///
+    /// Requires the `instrument` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `counters` don't have one hit counter per entry.
+    #[cfg(feature = "instrument")]
+    pub const fn with_counters(mut self, counters: &'static Counters) -> Self {
+        assert!(
+            counters.hits.len() == self.entries.len(),
+            "`counters` must have one hit counter per entry"
+        );
+        self.counters = counters;
+        self
+    }
+
     /// Returns the number of entries in the `Map`.
     #[inline]
     pub const fn len(&self) -> usize {
@@ -161,52 +269,296 @@ impl<K, V> Map<K, V> {
     }
 
     /// Like `get`, but returns both the key and the value.
-    #[cfg(not(feature = "ptrhash"))]
     pub fn get_entry<T>(&self, key: &T) -> Option<(&K, &V)>
     where
         T: Eq + PhfHash + ?Sized,
         K: PhfEq<T>,
     {
-        if self.disps.is_empty() {
-            return None;
-        } //Prevent panic on empty map
-        let hashes = phf_shared::hash(key, &self.key);
-        let index = phf_shared::get_index(&hashes, self.disps, self.entries.len());
-        let entry = &self.entries[index as usize];
-        if entry.0.phf_eq(key) {
-            Some((&entry.0, &entry.1))
-        } else {
-            None
-        }
+        self.find_entry(key, |entry| entry.phf_eq(key))
     }
 
-    /// Like `get`, but returns both the key and the value.
-    #[cfg(feature = "ptrhash")]
-    pub fn get_entry<T>(&self, key: &T) -> Option<(&K, &V)>
+    /// Looks up a string key given as a sequence of parts, as if the parts
+    /// were concatenated.
+    ///
+    /// This avoids allocating the joined key, for example when looking up
+    /// dotted paths that are stored split on `.`.
+    ///
+    /// ```
+    /// use phf::phf_map;
+    ///
+    /// static MAP: phf::Map<&'static str, u32> = phf_map! {
+    ///     "server.port" => 8080,
+    /// };
+    ///
+    /// assert_eq!(MAP.get_by_parts(&["server", ".", "port"]), Some(&8080));
+    /// ```
+    pub fn get_by_parts(&self, parts: &[&str]) -> Option<&V>
+    where
+        K: PhfBorrow<str>,
+    {
+        self.get_by_chunks(parts.iter().map(|part| part.as_bytes()))
+    }
+
+    /// Looks up a string or byte string key given as a sequence of byte
+    /// chunks, as if the chunks were concatenated.
+    ///
+    /// The iterator is cloned for each pass over the chunks, so it should be
+    /// cheap to clone. Chunks do not need to fall on UTF-8 character
+    /// boundaries when looking up `str` keys.
+    pub fn get_by_chunks<'a, I, B>(&self, chunks: I) -> Option<&V>
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+        I::IntoIter: Clone,
+        K: PhfBorrow<B>,
+        B: AsRef<[u8]> + ?Sized,
+    {
+        let chunks = ByteChunks::new(chunks.into_iter());
+        self.find_entry(&chunks, |entry| chunks.eq_bytes(entry.borrow().as_ref()))
+            .map(|e| e.1)
+    }
+
+    /// Hashes `key` for repeated lookups.
+    ///
+    /// The returned key can be passed to [`get_prepared`](Self::get_prepared)
+    /// on this map and on every other map generated in the same family, which
+    /// then skip hashing the key again.
+    pub fn prepare<'k, T>(&self, key: &'k T) -> PreparedKey<'k, T>
+    where
+        T: PhfHash + ?Sized,
+    {
+        PreparedKey::new(key, self.key)
+    }
+
+    /// Like `get`, but reuses the hash cached in a [`PreparedKey`].
+    ///
+    /// ```
+    /// phf::phf_map_family! {
+    ///     static KEYWORDS: phf::Map<&'static str, u32> = { "fn" => 0, "let" => 1 };
+    ///     static BUILTINS: phf::Map<&'static str, u32> = { "print" => 0 };
+    /// }
+    ///
+    /// let ident = KEYWORDS.prepare("print");
+    /// assert_eq!(KEYWORDS.get_prepared(&ident), None);
+    /// assert_eq!(BUILTINS.get_prepared(&ident), Some(&0));
+    /// ```
+    pub fn get_prepared<T>(&self, key: &PreparedKey<'_, T>) -> Option<&V>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.get_entry_prepared(key).map(|e| e.1)
+    }
+
+    /// Like `get_prepared`, but returns both the key and the value.
+    pub fn get_entry_prepared<T>(&self, key: &PreparedKey<'_, T>) -> Option<(&K, &V)>
     where
         T: Eq + PhfHash + ?Sized,
         K: PhfEq<T>,
+    {
+        let hash = key.hash_for(self.key);
+        self.find_entry_by_hash(&hash, |entry| entry.phf_eq(key.key()))
+    }
+
+    pub(crate) fn find_entry<T>(&self, key: &T, eq: impl Fn(&K) -> bool) -> Option<(&K, &V)>
+    where
+        T: PhfHash + ?Sized,
+    {
+        let entry = &self.entries[self.find_index(key, eq)?];
+        Some((&entry.0, &entry.1))
+    }
+
+    fn find_entry_by_hash(&self, hash: &KeyHash, eq: impl Fn(&K) -> bool) -> Option<(&K, &V)> {
+        let entry = &self.entries[self.find_index_by_hash(hash, eq)?];
+        Some((&entry.0, &entry.1))
+    }
+
+    /// Returns the index into `entries` of the entry for `key`.
+    pub(crate) fn find_index<T>(&self, key: &T, eq: impl Fn(&K) -> bool) -> Option<usize>
+    where
+        T: PhfHash + ?Sized,
     {
         if self.entries.is_empty() {
             return None;
         }
+        self.find_index_by_hash(&lookup::hash(key, &self.key), eq)
+    }
 
-        let hash = phf_shared::ptrhash::hash(key, &self.key);
-        let index = phf_shared::ptrhash::get_index(
-            self.key,
-            hash,
-            self.pilots,
-            self.remap,
-            self.entries.len(),
-        );
-        let entry = &self.entries[index as usize];
-        if entry.0.phf_eq(key) {
-            Some((&entry.0, &entry.1))
-        } else {
-            None
+    /// Like `find_entry`, but not counted by the lookup counters, for the
+    /// probes `find_iter` and `suggest` make on behalf of a single call.
+    pub(crate) fn probe_entry<T>(&self, key: &T, eq: impl Fn(&K) -> bool) -> Option<(&K, &V)>
+    where
+        T: PhfHash + ?Sized,
+    {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let entry = &self.entries[self.probe_index(&lookup::hash(key, &self.key), eq)?];
+        Some((&entry.0, &entry.1))
+    }
+
+    fn find_index_by_hash(&self, hash: &KeyHash, eq: impl Fn(&K) -> bool) -> Option<usize> {
+        let index = self.probe_index(hash, eq);
+        #[cfg(feature = "instrument")]
+        self.counters.record(index);
+        index
+    }
+
+    fn probe_index(&self, hash: &KeyHash, eq: impl Fn(&K) -> bool) -> Option<usize> {
+        self.params()
+            .index(hash)
+            .filter(|&index| eq(&self.entries[index].0))
+    }
+
+    #[cfg(not(feature = "ptrhash"))]
+    fn params(&self) -> Params<'_> {
+        Params {
+            disps: self.disps,
+            len: self.entries.len(),
+        }
+    }
+
+    #[cfg(feature = "ptrhash")]
+    fn params(&self) -> Params<'_> {
+        Params {
+            key: self.key,
+            pilots: self.pilots,
+            remap: self.remap,
+            len: self.entries.len(),
+        }
+    }
+
+    /// Returns a reusable [`Finder`] that scans text for the keys of this
+    /// map.
+    ///
+    /// This visits every key to collect the key lengths to probe. A
+    /// [`FindMap`](crate::find::FindMap) records them when it's generated.
+    pub fn finder<B>(&self) -> Finder<'_, K, V>
+    where
+        K: PhfBorrow<B>,
+        B: AsRef<[u8]> + ?Sized + 'static,
+    {
+        let lengths = Lengths::new(self.keys().map(|key| key_bytes::<K, B>(key).len()));
+        Finder::new(self, key_bytes::<K, B>, lengths)
+    }
+
+    /// Returns an iterator over the non-overlapping occurrences of keys in
+    /// `haystack`, preferring the longest key at the leftmost position.
+    ///
+    /// Use [`finder`](Self::finder) to choose other match semantics or to
+    /// scan several haystacks, and a [`FindMap`](crate::find::FindMap) to
+    /// avoid visiting every key each time a scan starts.
+    ///
+    /// ```
+    /// use phf::phf_map;
+    ///
+    /// static UNITS: phf::Map<&'static str, u32> = phf_map! {
+    ///     "k" => 1_000,
+    ///     "km" => 1_000,
+    ///     "m" => 1,
+    /// };
+    ///
+    /// let found = UNITS
+    ///     .find_iter("5km or 3m")
+    ///     .map(|m| (m.range(), *m.key()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(found, [(1..3, "km"), (8..9, "m")]);
+    /// ```
+    pub fn find_iter<'h, B, H>(&self, haystack: &'h H) -> FindIter<'_, 'h, K, V>
+    where
+        K: PhfBorrow<B>,
+        B: AsRef<[u8]> + ?Sized + 'static,
+        H: AsRef<[u8]> + ?Sized,
+    {
+        self.finder().find_iter(haystack)
+    }
+
+    /// Returns the keys within `max_distance` edits of `query` along with
+    /// their values and distances, nearest first.
+    ///
+    /// Distances count insertions, deletions, substitutions and transpositions
+    /// of adjacent characters. Keys at the same distance are ordered by their
+    /// string value.
+    ///
+    /// This compares `query` with every key. A
+    /// [`SuggestMap`](crate::suggest::SuggestMap) is generated with a deletion
+    /// table that narrows the comparisons down to a few keys.
+    ///
+    /// Requires the `std` feature.
+    ///
+    /// ```
+    /// use phf::phf_map;
+    ///
+    /// static COMMANDS: phf::Map<&'static str, u32> = phf_map! {
+    ///     "build" => 0,
+    ///     "bench" => 1,
+    ///     "check" => 2,
+    /// };
+    ///
+    /// let suggestions = COMMANDS.suggest("biuld", 2);
+    /// assert_eq!(suggestions, [(&"build", &0, 1)]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn suggest(&self, query: &str, max_distance: usize) -> Vec<(&K, &V, usize)>
+    where
+        K: PhfBorrow<str>,
+    {
+        suggest::entries(self, suggest::suggest(self, None, query, max_distance))
+    }
+
+    /// Returns the number of bytes of static data the map uses.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let usage = self
+            .params()
+            .memory_usage(mem::size_of_val(self.entries), 0);
+        #[cfg(feature = "instrument")]
+        let usage = usage.with_auxiliary(self.counters.memory_usage());
+        usage
+    }
+
+    /// Returns statistics on the hash layout of the map.
+    pub fn layout_stats(&self) -> LayoutStats {
+        self.params().layout_stats()
+    }
+
+    /// Checks that every key of the map can be looked up.
+    ///
+    /// Keys can be unreachable if the map was generated for other hash
+    /// functions than this crate uses, which the generated code can't always
+    /// detect at compile time, for example when a key type's [`PhfHash`]
+    /// implementation changed. Tables generated by `phf_codegen` can be checked
+    /// by a test that [`phf_codegen::validate_test`] writes.
+    ///
+    /// [`phf_codegen::validate_test`]: https://docs.rs/phf_codegen/*/phf_codegen/fn.validate_test.html
+    pub fn validate(&self) -> Result<(), UnreachableKeys<'_, K, V>>
+    where
+        K: PhfHash,
+    {
+        let unreachable = UnreachableKeys {
+            map: self,
+            range: 0..self.entries.len(),
+        };
+        match unreachable.clone().next() {
+            Some(_) => Err(unreachable),
+            None => Ok(()),
         }
     }
 
+    fn is_reachable(&self, index: usize) -> bool
+    where
+        K: PhfHash,
+    {
+        let hash = lookup::hash(&self.entries[index].0, &self.key);
+        self.params().index(&hash) == Some(index)
+    }
+
+    /// Returns a snapshot of the lookup counters of the map.
+    ///
+    /// Requires the `instrument` feature.
+    #[cfg(feature = "instrument")]
+    pub fn stats(&self) -> MapStats<'_, K> {
+        MapStats::new(self.counters, self.keys())
+    }
+
     /// Returns an iterator over the key/value pairs in the map.
     ///
     /// Entries are returned in an arbitrary but fixed order.
@@ -235,6 +587,14 @@ impl<K, V> Map<K, V> {
     }
 }
 
+pub(crate) fn key_bytes<K, B>(key: &K) -> &[u8]
+where
+    K: PhfBorrow<B>,
+    B: AsRef<[u8]> + ?Sized + 'static,
+{
+    key.borrow().as_ref()
+}
+
 impl<'a, K, V> IntoIterator for &'a Map<K, V> {
     type Item = (&'a K, &'a V);
     type IntoIter = Entries<'a, K, V>;
@@ -246,7 +606,7 @@ impl<'a, K, V> IntoIterator for &'a Map<K, V> {
 
 /// An iterator over the key/value pairs in a `Map`.
 pub struct Entries<'a, K, V> {
-    iter: slice::Iter<'a, (K, V)>,
+    pub(crate) iter: slice::Iter<'a, (K, V)>,
 }
 
 impl<'a, K, V> Clone for Entries<'a, K, V> {
@@ -292,7 +652,7 @@ impl<'a, K, V> FusedIterator for Entries<'a, K, V> {}
 
 /// An iterator over the keys in a `Map`.
 pub struct Keys<'a, K, V> {
-    iter: Entries<'a, K, V>,
+    pub(crate) iter: Entries<'a, K, V>,
 }
 
 impl<'a, K, V> Clone for Keys<'a, K, V> {
@@ -337,7 +697,7 @@ impl<'a, K, V> FusedIterator for Keys<'a, K, V> {}
 
 /// An iterator over the values in a `Map`.
 pub struct Values<'a, K, V> {
-    iter: Entries<'a, K, V>,
+    pub(crate) iter: Entries<'a, K, V>,
 }
 
 impl<'a, K, V> Clone for Values<'a, K, V> {
@@ -380,6 +740,65 @@ impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {}
 
 impl<'a, K, V> FusedIterator for Values<'a, K, V> {}
 
+/// The keys of a map that can't be looked up, returned by [`Map::validate`].
+///
+/// Iterating yields the unreachable keys.
+pub struct UnreachableKeys<'a, K: 'static, V: 'static> {
+    map: &'a Map<K, V>,
+    range: Range<usize>,
+}
+
+impl<'a, K, V> Clone for UnreachableKeys<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map,
+            range: self.range.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for UnreachableKeys<'a, K, V>
+where
+    K: PhfHash + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K: PhfHash, V> fmt::Display for UnreachableKeys<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} keys can't be looked up; the table was probably generated \
+             for different hash functions than this version of `phf` uses",
+            self.clone().count(),
+            self.map.len(),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, K: PhfHash + fmt::Debug, V> std::error::Error for UnreachableKeys<'a, K, V> {}
+
+impl<'a, K: PhfHash, V> Iterator for UnreachableKeys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        let map = self.map;
+        self.range
+            .find(|&index| !map.is_reachable(index))
+            .map(|index| &map.entries[index].0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.range.size_hint().1)
+    }
+}
+
+impl<'a, K: PhfHash, V> FusedIterator for UnreachableKeys<'a, K, V> {}
+
 #[cfg(feature = "serde")]
 impl<K, V> Serialize for Map<K, V>
 where
@@ -0,0 +1,197 @@
+//! An immutable map with a default value constructed at compile time.
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Index;
+use phf_shared::{PhfEq, PhfHash};
+
+use crate::{Map, OrderedMap};
+
+/// An immutable map that returns a default value for keys it doesn't contain.
+///
+/// `M` is the underlying map, either a [`Map`] or an [`OrderedMap`]. It's
+/// available from [`map`](Self::map) for iteration and the other lookups the
+/// map type supports.
+///
+/// ## Note
+///
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`MapWithDefault::from_raw_parts`] instead.
+pub struct MapWithDefault<K: 'static, V: 'static, M: 'static = Map<K, V>> {
+    #[doc(hidden)]
+    pub map: M,
+    #[doc(hidden)]
+    pub default: V,
+    #[doc(hidden)]
+    pub marker: PhantomData<&'static K>,
+}
+
+/// A map type that can be wrapped in a [`MapWithDefault`].
+///
+/// This trait is sealed and implemented for [`Map`] and [`OrderedMap`].
+pub trait DefaultableMap<K, V>: sealed::Sealed {
+    #[doc(hidden)]
+    fn map_len(&self) -> usize;
+
+    #[doc(hidden)]
+    fn get_entry<T>(&self, key: &T) -> Option<(&K, &V)>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+impl<K, V> sealed::Sealed for Map<K, V> {}
+
+impl<K, V> DefaultableMap<K, V> for Map<K, V> {
+    #[inline]
+    fn map_len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn get_entry<T>(&self, key: &T) -> Option<(&K, &V)>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.get_entry(key)
+    }
+}
+
+impl<K, V> sealed::Sealed for OrderedMap<K, V> {}
+
+impl<K, V> DefaultableMap<K, V> for OrderedMap<K, V> {
+    #[inline]
+    fn map_len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn get_entry<T>(&self, key: &T) -> Option<(&K, &V)>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.get_entry(key)
+    }
+}
+
+impl<K, V, M> fmt::Debug for MapWithDefault<K, V, M>
+where
+    V: fmt::Debug,
+    M: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("MapWithDefault")
+            .field("map", &self.map)
+            .field("default", &self.default)
+            .finish()
+    }
+}
+
+impl<'a, K, V, M, T: ?Sized> Index<&'a T> for MapWithDefault<K, V, M>
+where
+    T: Eq + PhfHash,
+    K: PhfEq<T>,
+    M: DefaultableMap<K, V>,
+{
+    type Output = V;
+
+    /// Returns the value that `k` maps to, or the default value.
+    fn index(&self, k: &'a T) -> &V {
+        self.get(k)
+    }
+}
+
+impl<K, V, M> MapWithDefault<K, V, M> {
+    /// Creates a map from the underlying map and the value returned for keys
+    /// that aren't in it.
+    pub const fn from_raw_parts(map: M, default: V) -> Self {
+        MapWithDefault {
+            map,
+            default,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the underlying map, which doesn't contain the default value.
+    #[inline]
+    pub const fn map(&self) -> &M {
+        &self.map
+    }
+
+    /// Returns the value returned for keys that aren't in the map.
+    #[inline]
+    pub const fn default_value(&self) -> &V {
+        &self.default
+    }
+}
+
+impl<K, V, M: DefaultableMap<K, V>> MapWithDefault<K, V, M> {
+    /// Returns the number of entries in the map, not counting the default
+    /// value.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.map_len()
+    }
+
+    /// Returns true if the map has no entries besides the default value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a reference to the value that `key` maps to, or to the default
+    /// value if `key` is not in the map.
+    ///
+    /// ```
+    /// use phf::{MapWithDefault, phf_map};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Method {
+    ///     Get,
+    ///     Post,
+    ///     Unknown,
+    /// }
+    ///
+    /// static METHODS: MapWithDefault<&'static str, Method> = phf_map! {
+    ///     "GET" => Method::Get,
+    ///     "POST" => Method::Post,
+    ///     _ => Method::Unknown,
+    /// };
+    ///
+    /// assert_eq!(METHODS.get("GET"), &Method::Get);
+    /// assert_eq!(METHODS["BREW"], Method::Unknown);
+    /// ```
+    pub fn get<T>(&self, key: &T) -> &V
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.get_entry(key).map_or(&self.default, |e| e.1)
+    }
+
+    /// Returns both the key and the value if `key` is in the map, without
+    /// falling back to the default value.
+    pub fn get_entry<T>(&self, key: &T) -> Option<(&K, &V)>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.map.get_entry(key)
+    }
+
+    /// Determines if `key` is in the map.
+    pub fn contains_key<T>(&self, key: &T) -> bool
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        self.get_entry(key).is_some()
+    }
+}
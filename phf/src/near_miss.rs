@@ -0,0 +1,183 @@
+//! Mutating keys into keys that are similar to them.
+//!
+//! Requires the `proptest` or `arbitrary` feature.
+use core::fmt;
+use std::string::String;
+use std::vec::Vec;
+
+/// A key type that can be mutated into "near misses" of itself: keys that are
+/// similar to, but usually not equal to, the original.
+///
+/// Near misses of the keys of a table make good fuzzing inputs for code that
+/// looks them up, as they exercise the paths where a lookup almost succeeds.
+/// A near miss may still happen to be equal to the original or to another key
+/// of the table; filter them with `contains_key` if that matters.
+pub trait NearMiss {
+    /// The type of the mutated keys.
+    type Owned: Clone + fmt::Debug;
+
+    /// Returns the mutation of `self` chosen by `seed`.
+    ///
+    /// The result only depends on `self` and `seed`.
+    fn near_miss(&self, seed: u64) -> Self::Owned;
+}
+
+impl<T: NearMiss + ?Sized> NearMiss for &T {
+    type Owned = T::Owned;
+
+    #[inline]
+    fn near_miss(&self, seed: u64) -> T::Owned {
+        T::near_miss(*self, seed)
+    }
+}
+
+impl NearMiss for str {
+    type Owned = String;
+
+    fn near_miss(&self, seed: u64) -> String {
+        let edit = Edit::new(seed);
+        let mut chars = self.chars().collect::<Vec<_>>();
+        let unit = char::from(b' ' + edit.unit % 95);
+        edit.apply(&mut chars, unit, toggle_char_case);
+        chars.into_iter().collect()
+    }
+}
+
+impl NearMiss for String {
+    type Owned = String;
+
+    #[inline]
+    fn near_miss(&self, seed: u64) -> String {
+        self.as_str().near_miss(seed)
+    }
+}
+
+impl NearMiss for [u8] {
+    type Owned = Vec<u8>;
+
+    fn near_miss(&self, seed: u64) -> Vec<u8> {
+        let edit = Edit::new(seed);
+        let mut bytes = self.to_vec();
+        edit.apply(&mut bytes, edit.unit, toggle_byte_case);
+        bytes
+    }
+}
+
+impl<const N: usize> NearMiss for [u8; N] {
+    type Owned = Vec<u8>;
+
+    #[inline]
+    fn near_miss(&self, seed: u64) -> Vec<u8> {
+        self[..].near_miss(seed)
+    }
+}
+
+impl NearMiss for Vec<u8> {
+    type Owned = Vec<u8>;
+
+    #[inline]
+    fn near_miss(&self, seed: u64) -> Vec<u8> {
+        self[..].near_miss(seed)
+    }
+}
+
+impl NearMiss for char {
+    type Owned = char;
+
+    fn near_miss(&self, seed: u64) -> char {
+        let neighbor = match seed % 3 {
+            0 => (*self as u32).checked_add(1),
+            1 => (*self as u32).checked_sub(1),
+            _ => Some(toggle_char_case(*self) as u32),
+        };
+        neighbor
+            .and_then(char::from_u32)
+            .filter(|c| c != self)
+            .unwrap_or(if *self == 'a' { 'b' } else { 'a' })
+    }
+}
+
+impl NearMiss for bool {
+    type Owned = bool;
+
+    #[inline]
+    fn near_miss(&self, _: u64) -> bool {
+        !self
+    }
+}
+
+macro_rules! int_near_miss {
+    ($($t:ty)*) => ($(
+        impl NearMiss for $t {
+            type Owned = $t;
+
+            fn near_miss(&self, seed: u64) -> $t {
+                match seed % 3 {
+                    0 => self.wrapping_add(1),
+                    1 => self.wrapping_sub(1),
+                    _ => self ^ (1 << ((seed / 3) % u64::from(<$t>::BITS))),
+                }
+            }
+        }
+    )*)
+}
+
+int_near_miss!(u8 i8 u16 i16 u32 i32 u64 i64 u128 i128 usize isize);
+
+/// A single edit of a sequence of characters or bytes, decoded from a seed.
+struct Edit {
+    kind: u8,
+    position: usize,
+    unit: u8,
+}
+
+impl Edit {
+    fn new(seed: u64) -> Edit {
+        Edit {
+            kind: (seed & 7) as u8,
+            position: ((seed >> 3) & 0xffff_ffff) as usize,
+            unit: (seed >> 35) as u8,
+        }
+    }
+
+    /// Applies the edit to `units`, using `unit` for edits that add a unit.
+    ///
+    /// Edits of an empty sequence always insert `unit`, so they never produce
+    /// the original.
+    fn apply<T: Copy>(&self, units: &mut Vec<T>, unit: T, toggle_case: fn(T) -> T) {
+        let len = units.len();
+        if len == 0 {
+            units.push(unit);
+            return;
+        }
+        let i = self.position % len;
+        match self.kind {
+            0 => {
+                units.remove(i);
+            }
+            1 => units.insert(self.position % (len + 1), unit),
+            2 => units[i] = unit,
+            3 if len > 1 => units.swap(i % (len - 1), i % (len - 1) + 1),
+            4 => units[i] = toggle_case(units[i]),
+            5 => units.truncate(i),
+            6 => units.insert(i, units[i]),
+            _ => units.push(unit),
+        }
+    }
+}
+
+fn toggle_char_case(c: char) -> char {
+    if c.is_ascii_lowercase() {
+        c.to_ascii_uppercase()
+    } else {
+        c.to_ascii_lowercase()
+    }
+}
+
+fn toggle_byte_case(b: u8) -> u8 {
+    if b.is_ascii_lowercase() {
+        b.to_ascii_uppercase()
+    } else {
+        b.to_ascii_lowercase()
+    }
+}
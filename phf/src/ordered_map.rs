@@ -2,10 +2,16 @@
 use core::fmt;
 use core::iter::FusedIterator;
 use core::iter::IntoIterator;
-use core::ops::Index;
+use core::mem;
+use core::ops::{Index, Range};
 use core::slice;
 use phf_shared::{self, HashKey, PhfEq, PhfHash};
 
+#[cfg(feature = "instrument")]
+use crate::instrument::{Counters, MapStats};
+use crate::layout::{LayoutStats, MemoryUsage};
+use crate::lookup::{self, Params};
+
 /// An order-preserving immutable map constructed at compile time.
 ///
 /// Unlike a `Map`, iteration order is guaranteed to match the definition
@@ -13,9 +19,10 @@ use phf_shared::{self, HashKey, PhfEq, PhfHash};
 ///
 /// ## Note
 ///
-/// The fields of this struct are public so that they may be initialized by the
-/// `phf_ordered_map!` macro and code generation. They are subject to change at
-/// any time and should never be accessed directly.
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`OrderedMap::from_raw_parts`] instead.
 #[cfg(not(feature = "ptrhash"))]
 pub struct OrderedMap<K: 'static, V: 'static> {
     #[doc(hidden)]
@@ -26,6 +33,9 @@ pub struct OrderedMap<K: 'static, V: 'static> {
     pub idxs: &'static [usize],
     #[doc(hidden)]
     pub entries: &'static [(K, V)],
+    #[cfg(feature = "instrument")]
+    #[doc(hidden)]
+    pub counters: &'static Counters,
 }
 
 /// An order-preserving immutable map constructed at compile time.
@@ -35,9 +45,10 @@ pub struct OrderedMap<K: 'static, V: 'static> {
 ///
 /// ## Note
 ///
-/// The fields of this struct are public so that they may be initialized by the
-/// `phf_ordered_map!` macro and code generation. They are subject to change at
-/// any time and should never be accessed directly.
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`OrderedMap::from_raw_parts`] instead.
 #[cfg(feature = "ptrhash")]
 pub struct OrderedMap<K: 'static, V: 'static> {
     #[doc(hidden)]
@@ -50,6 +61,9 @@ pub struct OrderedMap<K: 'static, V: 'static> {
     pub idxs: &'static [usize],
     #[doc(hidden)]
     pub entries: &'static [(K, V)],
+    #[cfg(feature = "instrument")]
+    #[doc(hidden)]
+    pub counters: &'static Counters,
 }
 
 impl<K, V> fmt::Debug for OrderedMap<K, V>
@@ -105,6 +119,110 @@ where
 }
 
 impl<K, V> OrderedMap<K, V> {
+    /// Creates a map from the hash parameters computed by `phf_generator`
+    /// and the entries in definition order.
+    ///
+    /// `phf_ordered_map!` and `phf_codegen` construct maps through this rather than
+    /// by initializing their fields, so that generated code keeps compiling
+    /// when the fields of `OrderedMap` change.
+    /// `idxs` maps each slot to an index into `entries`, as laid out by
+    /// `HashState::map`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parts are inconsistent, which fails compilation when the
+    /// map initializes a `static` or `const`.
+    #[cfg(not(feature = "ptrhash"))]
+    pub const fn from_raw_parts(
+        key: HashKey,
+        disps: &'static [(u32, u32)],
+        idxs: &'static [usize],
+        entries: &'static [(K, V)],
+    ) -> Self {
+        lookup::check_params(disps, entries.len());
+        lookup::check_idxs(idxs, entries.len());
+        Self {
+            key,
+            disps,
+            idxs,
+            entries,
+            #[cfg(feature = "instrument")]
+            counters: &crate::instrument::EMPTY,
+        }
+    }
+
+    /// Creates a map from the hash parameters computed by `phf_generator`
+    /// and the entries in definition order.
+    ///
+    /// `phf_ordered_map!` and `phf_codegen` construct maps through this rather than
+    /// by initializing their fields, so that generated code keeps compiling
+    /// when the fields of `OrderedMap` change.
+    /// `idxs` maps each slot to an index into `entries`, as laid out by
+    /// `HashState::map`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parts are inconsistent, which fails compilation when the
+    /// map initializes a `static` or `const`.
+    #[cfg(feature = "ptrhash")]
+    pub const fn from_raw_parts(
+        key: HashKey,
+        pilots: &'static [u8],
+        remap: &'static [u32],
+        idxs: &'static [usize],
+        entries: &'static [(K, V)],
+    ) -> Self {
+        lookup::check_params(pilots, remap, entries.len());
+        lookup::check_idxs(idxs, entries.len());
+        Self {
+            key,
+            pilots,
+            remap,
+            idxs,
+            entries,
+            #[cfg(feature = "instrument")]
+            counters: &crate::instrument::EMPTY,
+        }
+    }
+
+    /// An empty map, for `__ordered_map!` to fill in.
+    #[doc(hidden)]
+    pub const fn __empty() -> Self {
+        #[cfg(not(feature = "ptrhash"))]
+        return Self::from_raw_parts(0, &[], &[], &[]);
+
+        #[cfg(feature = "ptrhash")]
+        return Self::from_raw_parts(0, &[], &[], &[], &[]);
+    }
+
+    /// An empty map with `counters`, for `__ordered_map!` to fill in.
+    #[cfg(feature = "instrument")]
+    #[doc(hidden)]
+    pub const fn __with_counters_base(counters: &'static Counters) -> Self {
+        let mut map = Self::__empty();
+        map.counters = counters;
+        map
+    }
+
+    /// Sets the lookup counters of the map.
+    ///
+    /// Maps created without counters aren't counted.
+    ///
+    /// Requires the `instrument` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `counters` don't have one hit counter per entry.
+    #[cfg(feature = "instrument")]
+    pub const fn with_counters(mut self, counters: &'static Counters) -> Self {
+        assert!(
+            counters.hits.len() == self.entries.len(),
+            "`counters` must have one hit counter per entry"
+        );
+        self.counters = counters;
+        self
+    }
+
     /// Returns the number of entries in the `OrderedMap`.
     #[inline]
     pub const fn len(&self) -> usize {
@@ -177,49 +295,77 @@ impl<K, V> OrderedMap<K, V> {
         T: Eq + PhfHash + ?Sized,
         K: PhfEq<T>,
     {
-        #[cfg(not(feature = "ptrhash"))]
-        {
-            if self.disps.is_empty() {
-                return None;
-            }
-
-            let hashes = phf_shared::hash(key, &self.key);
-            let idx_index = phf_shared::get_index(&hashes, self.disps, self.idxs.len());
-            let idx = self.idxs[idx_index as usize];
-            let entry = &self.entries[idx];
-
-            if entry.0.phf_eq(key) {
-                Some((idx, (&entry.0, &entry.1)))
-            } else {
-                None
-            }
+        let entry = self.probe(key);
+        #[cfg(feature = "instrument")]
+        if !self.entries.is_empty() {
+            self.counters.record(entry.map(|(idx, _)| idx));
         }
+        entry
+    }
 
-        #[cfg(feature = "ptrhash")]
-        {
-            if self.entries.is_empty() {
-                return None;
-            }
-
-            let hash = phf_shared::ptrhash::hash(key, &self.key);
-            let idx_index = phf_shared::ptrhash::get_index(
-                self.key,
-                hash,
-                self.pilots,
-                self.remap,
-                self.idxs.len(),
-            );
-            let idx = self.idxs[idx_index as usize];
-            let entry = &self.entries[idx];
-
-            if entry.0.phf_eq(key) {
-                Some((idx, (&entry.0, &entry.1)))
-            } else {
-                None
-            }
+    /// Like `get_internal`, but not counted by the lookup counters.
+    pub(crate) fn probe<T>(&self, key: &T) -> Option<(usize, (&K, &V))>
+    where
+        T: Eq + PhfHash + ?Sized,
+        K: PhfEq<T>,
+    {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        let idx_index = self.params().index(&lookup::hash(key, &self.key))?;
+        let idx = self.idxs[idx_index];
+        let entry = &self.entries[idx];
+
+        if entry.0.phf_eq(key) {
+            Some((idx, (&entry.0, &entry.1)))
+        } else {
+            None
+        }
+    }
+
+    #[cfg(not(feature = "ptrhash"))]
+    fn params(&self) -> Params<'_> {
+        Params {
+            disps: self.disps,
+            len: self.idxs.len(),
         }
     }
 
+    #[cfg(feature = "ptrhash")]
+    fn params(&self) -> Params<'_> {
+        Params {
+            key: self.key,
+            pilots: self.pilots,
+            remap: self.remap,
+            len: self.idxs.len(),
+        }
+    }
+
+    /// Returns a snapshot of the lookup counters of the map, with the keys in
+    /// the order in which they were defined.
+    ///
+    /// Requires the `instrument` feature.
+    #[cfg(feature = "instrument")]
+    pub fn stats(&self) -> MapStats<'_, K> {
+        MapStats::new(self.counters, self.keys())
+    }
+
+    /// Returns the number of bytes of static data the map uses.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let usage = self
+            .params()
+            .memory_usage(mem::size_of_val(self.entries), mem::size_of_val(self.idxs));
+        #[cfg(feature = "instrument")]
+        let usage = usage.with_auxiliary(self.counters.memory_usage());
+        usage
+    }
+
+    /// Returns statistics on the hash layout of the map.
+    pub fn layout_stats(&self) -> LayoutStats {
+        self.params().layout_stats()
+    }
+
     /// Returns an iterator over the key/value pairs in the map.
     ///
     /// Entries are returned in the same order in which they were defined.
@@ -229,6 +375,12 @@ impl<K, V> OrderedMap<K, V> {
         }
     }
 
+    pub(crate) fn entries_in(&self, range: Range<usize>) -> Entries<'_, K, V> {
+        Entries {
+            iter: self.entries[range].iter(),
+        }
+    }
+
     /// Returns an iterator over the keys in the map.
     ///
     /// Keys are returned in the same order in which they were defined.
@@ -1,10 +1,14 @@
 //! An order-preserving immutable set constructed at compile time.
+use crate::layout::{LayoutStats, MemoryUsage};
 use crate::{OrderedMap, PhfHash, ordered_map};
 use core::fmt;
 use core::iter::FusedIterator;
 use core::iter::IntoIterator;
 use phf_shared::PhfEq;
 
+#[cfg(feature = "instrument")]
+use crate::instrument::MapStats;
+
 /// An order-preserving immutable set constructed at compile time.
 ///
 /// Unlike a `Set`, iteration order is guaranteed to match the definition
@@ -12,9 +16,10 @@ use phf_shared::PhfEq;
 ///
 /// ## Note
 ///
-/// The fields of this struct are public so that they may be initialized by the
-/// `phf_ordered_set!` macro and code generation. They are subject to change at
-/// any time and should never be accessed directly.
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`OrderedSet::from_raw_parts`] instead.
 pub struct OrderedSet<T: 'static> {
     #[doc(hidden)]
     pub map: OrderedMap<T, ()>,
@@ -41,6 +46,11 @@ where
 impl<T> Eq for OrderedSet<T> where T: Eq {}
 
 impl<T> OrderedSet<T> {
+    /// Creates a set from a map of its elements to `()`.
+    pub const fn from_raw_parts(map: OrderedMap<T, ()>) -> Self {
+        Self { map }
+    }
+
     /// Returns the number of elements in the `OrderedSet`.
     #[inline]
     pub const fn len(&self) -> usize {
@@ -90,6 +100,24 @@ impl<T> OrderedSet<T> {
         self.map.contains_key(value)
     }
 
+    /// Returns a snapshot of the lookup counters of the set.
+    ///
+    /// See [`OrderedMap::stats`].
+    #[cfg(feature = "instrument")]
+    pub fn stats(&self) -> MapStats<'_, T> {
+        self.map.stats()
+    }
+
+    /// Returns the number of bytes of static data the set uses.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.map.memory_usage()
+    }
+
+    /// Returns statistics on the hash layout of the set.
+    pub fn layout_stats(&self) -> LayoutStats {
+        self.map.layout_stats()
+    }
+
     /// Returns an iterator over the values in the set.
     ///
     /// Values are returned in the same order in which they were defined.
@@ -0,0 +1,69 @@
+//! Lookup keys whose hash is computed once and reused across maps.
+use core::fmt;
+use phf_shared::{HashKey, PhfHash};
+
+use crate::lookup::{KeyHash, hash};
+
+/// A lookup key together with its cached hash.
+///
+/// Maps generated as a family, with `phf_map_family!` or
+/// `phf_codegen::Map::build_family`, share one hash key, so a `PreparedKey`
+/// created from any member can be looked up in every other member without
+/// hashing the key again. Looking a prepared key up in an unrelated map is
+/// still correct but rehashes the key.
+///
+/// Created by [`Map::prepare`](crate::Map::prepare) or
+/// [`Set::prepare`](crate::Set::prepare).
+pub struct PreparedKey<'a, T: ?Sized> {
+    key: &'a T,
+    hash_key: HashKey,
+    hash: KeyHash,
+}
+
+impl<'a, T: ?Sized + PhfHash> PreparedKey<'a, T> {
+    #[inline]
+    pub(crate) fn new(key: &'a T, hash_key: HashKey) -> Self {
+        Self {
+            key,
+            hash_key,
+            hash: hash(key, &hash_key),
+        }
+    }
+
+    /// Returns the cached hash if it was computed with `hash_key`, or
+    /// computes it otherwise.
+    #[inline]
+    pub(crate) fn hash_for(&self, hash_key: HashKey) -> KeyHash {
+        if self.hash_key == hash_key {
+            self.hash
+        } else {
+            hash(self.key, &hash_key)
+        }
+    }
+}
+
+impl<'a, T: ?Sized> PreparedKey<'a, T> {
+    /// Returns the underlying lookup key.
+    #[inline]
+    pub fn key(&self) -> &'a T {
+        self.key
+    }
+}
+
+impl<'a, T: ?Sized> Clone for PreparedKey<'a, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T: ?Sized> Copy for PreparedKey<'a, T> {}
+
+impl<'a, T> fmt::Debug for PreparedKey<'a, T>
+where
+    T: ?Sized + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PreparedKey").field(&self.key).finish()
+    }
+}
@@ -0,0 +1,119 @@
+//! [`proptest`](mod@proptest) strategies that sample static tables.
+//!
+//! ```
+//! use phf::phf_set;
+//! use proptest::prelude::*;
+//!
+//! static KEYWORDS: phf::Set<&'static str> = phf_set! {
+//!     "loop",
+//!     "continue",
+//!     "break",
+//! };
+//!
+//! proptest! {
+//!     # #![proptest_config(ProptestConfig::with_cases(16))]
+//!     fn lex_keyword(keyword in phf::proptest::elements(&KEYWORDS)) {
+//!         prop_assert!(KEYWORDS.contains(*keyword));
+//!     }
+//!
+//!     fn lex_identifier(word in phf::proptest::near_miss_elements(&KEYWORDS)) {
+//!         prop_assume!(!KEYWORDS.contains(&*word));
+//!         // ...
+//!     }
+//! }
+//!
+//! lex_keyword();
+//! lex_identifier();
+//! ```
+//!
+//! Requires the `proptest` feature.
+use ::proptest::prelude::*;
+use ::proptest::sample::select;
+use core::fmt;
+use std::vec::Vec;
+
+use crate::near_miss::NearMiss;
+use crate::{Map, Set};
+
+/// Returns a strategy that yields the keys of `map`.
+///
+/// Shrinks towards the first key in iteration order.
+///
+/// # Panics
+///
+/// Panics if `map` is empty.
+pub fn keys<K, V>(map: &'static Map<K, V>) -> impl Strategy<Value = &'static K>
+where
+    K: fmt::Debug,
+{
+    select(map.keys().collect::<Vec<_>>())
+}
+
+/// Returns a strategy that yields the values of `map`.
+///
+/// Shrinks towards the first value in iteration order.
+///
+/// # Panics
+///
+/// Panics if `map` is empty.
+pub fn values<K, V>(map: &'static Map<K, V>) -> impl Strategy<Value = &'static V>
+where
+    V: fmt::Debug,
+{
+    select(map.values().collect::<Vec<_>>())
+}
+
+/// Returns a strategy that yields the entries of `map`.
+///
+/// Shrinks towards the first entry in iteration order.
+///
+/// # Panics
+///
+/// Panics if `map` is empty.
+pub fn entries<K, V>(map: &'static Map<K, V>) -> impl Strategy<Value = (&'static K, &'static V)>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    select(map.entries().collect::<Vec<_>>())
+}
+
+/// Returns a strategy that yields the elements of `set`.
+///
+/// Shrinks towards the first element in iteration order.
+///
+/// # Panics
+///
+/// Panics if `set` is empty.
+pub fn elements<T>(set: &'static Set<T>) -> impl Strategy<Value = &'static T>
+where
+    T: fmt::Debug,
+{
+    keys(&set.map)
+}
+
+/// Returns a strategy that yields [near misses](NearMiss) of the keys of
+/// `map`.
+///
+/// # Panics
+///
+/// Panics if `map` is empty.
+pub fn near_miss_keys<K, V>(map: &'static Map<K, V>) -> impl Strategy<Value = K::Owned>
+where
+    K: NearMiss + fmt::Debug,
+{
+    (keys(map), any::<u64>()).prop_map(|(key, seed)| key.near_miss(seed))
+}
+
+/// Returns a strategy that yields [near misses](NearMiss) of the elements of
+/// `set`.
+///
+/// # Panics
+///
+/// Panics if `set` is empty.
+pub fn near_miss_elements<T>(set: &'static Set<T>) -> impl Strategy<Value = T::Owned>
+where
+    T: NearMiss + fmt::Debug,
+{
+    near_miss_keys(&set.map)
+}
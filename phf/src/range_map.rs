@@ -0,0 +1,259 @@
+//! An immutable map of disjoint ranges constructed at compile time.
+use core::cmp::Ordering;
+use core::fmt;
+use core::iter::{FusedIterator, Peekable};
+use core::slice;
+use phf_shared::{PhfEq, PhfHash};
+
+#[cfg(feature = "instrument")]
+use crate::instrument::{Counters, MapStats};
+use crate::{OrderedMap, ordered_map};
+
+/// An immutable map of disjoint inclusive ranges of keys to values.
+///
+/// Single-key ranges are looked up through a perfect hash table and longer
+/// ranges are binary searched, so sparse tables of characters or integers
+/// don't need every key in a range enumerated.
+///
+/// ## Note
+///
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`RangeMap::from_raw_parts`] instead.
+pub struct RangeMap<K: 'static, V: 'static> {
+    /// Single-key ranges, in ascending order.
+    #[doc(hidden)]
+    pub singletons: OrderedMap<K, V>,
+    /// `(start, end, value)` for longer ranges, in ascending order.
+    #[doc(hidden)]
+    pub ranges: &'static [(K, K, V)],
+    /// Hits per singleton, then per longer range. The singletons have no
+    /// counters of their own.
+    #[cfg(feature = "instrument")]
+    #[doc(hidden)]
+    pub counters: &'static Counters,
+}
+
+impl<K, V> fmt::Debug for RangeMap<K, V>
+where
+    K: fmt::Debug + Ord,
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_map()
+            .entries(self.iter().map(|(start, end, value)| (start..=end, value)))
+            .finish()
+    }
+}
+
+impl<K, V> RangeMap<K, V> {
+    /// Creates a map from its single-key ranges and its longer ranges, both in
+    /// ascending order.
+    pub const fn from_raw_parts(
+        singletons: OrderedMap<K, V>,
+        ranges: &'static [(K, K, V)],
+    ) -> Self {
+        RangeMap {
+            singletons,
+            ranges,
+            #[cfg(feature = "instrument")]
+            counters: &crate::instrument::EMPTY,
+        }
+    }
+
+    /// An empty map with `counters`, for `__range_map!` to fill in.
+    #[cfg(feature = "instrument")]
+    #[doc(hidden)]
+    pub const fn __with_counters_base(counters: &'static Counters) -> Self {
+        let mut map = Self::from_raw_parts(OrderedMap::__empty(), &[]);
+        map.counters = counters;
+        map
+    }
+
+    /// Sets the lookup counters of the map.
+    ///
+    /// Maps created without counters aren't counted.
+    ///
+    /// Requires the `instrument` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `counters` don't have one hit counter per range.
+    #[cfg(feature = "instrument")]
+    pub const fn with_counters(mut self, counters: &'static Counters) -> Self {
+        assert!(
+            counters.hits.len() == self.len(),
+            "`counters` must have one hit counter per range"
+        );
+        self.counters = counters;
+        self
+    }
+
+    /// Returns the number of ranges in the `RangeMap`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.singletons.len() + self.ranges.len()
+    }
+
+    /// Returns true if the `RangeMap` is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a reference to the value of the range containing `key`.
+    ///
+    /// ```
+    /// use phf::{RangeMap, phf_range_map};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Class {
+    ///     Lower,
+    ///     Upper,
+    ///     Digit,
+    ///     Underscore,
+    /// }
+    ///
+    /// static CLASSES: RangeMap<char, Class> = phf_range_map! {
+    ///     'a'..='z' => Class::Lower,
+    ///     'A'..='Z' => Class::Upper,
+    ///     '0'..='9' => Class::Digit,
+    ///     '_' => Class::Underscore,
+    /// };
+    ///
+    /// assert_eq!(CLASSES.get(&'q'), Some(&Class::Lower));
+    /// assert_eq!(CLASSES.get(&'_'), Some(&Class::Underscore));
+    /// assert_eq!(CLASSES.get(&'-'), None);
+    /// ```
+    pub fn get(&self, key: &K) -> Option<&V>
+    where
+        K: Ord + PhfHash + PhfEq<K>,
+    {
+        self.get_range(key).map(|(_, _, value)| value)
+    }
+
+    /// Like `get`, but also returns the start and end of the range
+    /// containing `key`.
+    pub fn get_range(&self, key: &K) -> Option<(&K, &K, &V)>
+    where
+        K: Ord + PhfHash + PhfEq<K>,
+    {
+        let found = self.find(key);
+        #[cfg(feature = "instrument")]
+        if !self.is_empty() {
+            self.counters.record(found.map(|(idx, _)| idx));
+        }
+        found.map(|(_, range)| range)
+    }
+
+    /// Returns the index of the range containing `key` in the counters, along
+    /// with the range.
+    fn find(&self, key: &K) -> Option<(usize, (&K, &K, &V))>
+    where
+        K: Ord + PhfHash + PhfEq<K>,
+    {
+        if let Some((idx, (key, value))) = self.singletons.probe(key) {
+            return Some((idx, (key, key, value)));
+        }
+
+        let idx = self.ranges.partition_point(|(start, _, _)| start <= key);
+        let idx = idx.checked_sub(1)?;
+        let (start, end, value) = self.ranges.get(idx)?;
+        (key <= end).then_some((self.singletons.len() + idx, (start, end, value)))
+    }
+
+    /// Returns a snapshot of the lookup counters of the map. Ranges are
+    /// identified by their start: the single-key ranges come first, followed
+    /// by the longer ranges, each in ascending order.
+    ///
+    /// Requires the `instrument` feature.
+    #[cfg(feature = "instrument")]
+    pub fn stats(&self) -> MapStats<'_, K> {
+        let starts = self.ranges.iter().map(|(start, _, _)| start);
+        MapStats::new(self.counters, self.singletons.keys().chain(starts))
+    }
+
+    /// Determines if `key` is in a range of the `RangeMap`.
+    pub fn contains_key(&self, key: &K) -> bool
+    where
+        K: Ord + PhfHash + PhfEq<K>,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns an iterator over the `(start, end, value)` triples of the
+    /// ranges in the map, in ascending order. Both bounds are inclusive.
+    pub fn iter(&self) -> Iter<'_, K, V>
+    where
+        K: Ord,
+    {
+        Iter {
+            singletons: self.singletons.entries().peekable(),
+            ranges: self.ranges.iter().peekable(),
+        }
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a RangeMap<K, V> {
+    type Item = (&'a K, &'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+/// An iterator over the ranges in a `RangeMap`.
+pub struct Iter<'a, K, V> {
+    singletons: Peekable<ordered_map::Entries<'a, K, V>>,
+    ranges: Peekable<slice::Iter<'a, (K, K, V)>>,
+}
+
+impl<'a, K, V> Clone for Iter<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            singletons: self.singletons.clone(),
+            ranges: self.ranges.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> fmt::Debug for Iter<'a, K, V>
+where
+    K: fmt::Debug + Ord,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K: Ord, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a K, &'a V)> {
+        let order = match (self.singletons.peek(), self.ranges.peek()) {
+            (Some((key, _)), Some((start, _, _))) => key.cmp(&start),
+            (Some(_), None) => Ordering::Less,
+            (None, _) => Ordering::Greater,
+        };
+        if order == Ordering::Less {
+            self.singletons.next().map(|(key, value)| (key, key, value))
+        } else {
+            self.ranges
+                .next()
+                .map(|(start, end, value)| (start, end, value))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.singletons.len() + self.ranges.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, K: Ord, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+impl<'a, K: Ord, V> FusedIterator for Iter<'a, K, V> {}
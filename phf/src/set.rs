@@ -3,17 +3,23 @@ use core::fmt;
 use core::iter::FusedIterator;
 use core::iter::IntoIterator;
 
-use phf_shared::{PhfEq, PhfHash};
+use phf_shared::{PhfBorrow, PhfEq, PhfHash};
 
-use crate::{Map, map};
+use crate::find::{FindIter, Finder};
+#[cfg(feature = "instrument")]
+use crate::instrument::MapStats;
+use crate::layout::{LayoutStats, MemoryUsage};
+use crate::map::UnreachableKeys;
+use crate::{Map, PreparedKey, map};
 
 /// An immutable set constructed at compile time.
 ///
 /// ## Note
 ///
-/// The fields of this struct are public so that they may be initialized by the
-/// `phf_set!` macro and code generation. They are subject to change at any
-/// time and should never be accessed directly.
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`Set::from_raw_parts`] instead.
 pub struct Set<T: 'static> {
     #[doc(hidden)]
     pub map: Map<T, ()>,
@@ -40,6 +46,11 @@ where
 impl<T> Eq for Set<T> where T: Eq {}
 
 impl<T> Set<T> {
+    /// Creates a set from a map of its elements to `()`.
+    pub const fn from_raw_parts(map: Map<T, ()>) -> Self {
+        Self { map }
+    }
+
     /// Returns the number of elements in the `Set`.
     #[inline]
     pub const fn len(&self) -> usize {
@@ -73,6 +84,92 @@ impl<T> Set<T> {
         self.map.contains_key(value)
     }
 
+    /// Hashes `value` for repeated lookups.
+    ///
+    /// See [`Map::prepare`].
+    pub fn prepare<'k, U>(&self, value: &'k U) -> PreparedKey<'k, U>
+    where
+        U: PhfHash + ?Sized,
+    {
+        self.map.prepare(value)
+    }
+
+    /// Like `contains`, but reuses the hash cached in a [`PreparedKey`].
+    pub fn contains_prepared<U>(&self, value: &PreparedKey<'_, U>) -> bool
+    where
+        U: Eq + PhfHash + ?Sized,
+        T: PhfEq<U>,
+    {
+        self.map.get_entry_prepared(value).is_some()
+    }
+
+    /// Returns a reusable [`Finder`] that scans text for the values of this
+    /// set.
+    pub fn finder<B>(&self) -> Finder<'_, T, ()>
+    where
+        T: PhfBorrow<B>,
+        B: AsRef<[u8]> + ?Sized + 'static,
+    {
+        self.map.finder()
+    }
+
+    /// Returns an iterator over the non-overlapping occurrences of values in
+    /// `haystack`, preferring the longest value at the leftmost position.
+    ///
+    /// See [`Map::find_iter`].
+    pub fn find_iter<'h, B, H>(&self, haystack: &'h H) -> FindIter<'_, 'h, T, ()>
+    where
+        T: PhfBorrow<B>,
+        B: AsRef<[u8]> + ?Sized + 'static,
+        H: AsRef<[u8]> + ?Sized,
+    {
+        self.map.find_iter(haystack)
+    }
+
+    /// Returns the values within `max_distance` edits of `query` along with
+    /// their distances, nearest first.
+    ///
+    /// See [`Map::suggest`].
+    #[cfg(feature = "std")]
+    pub fn suggest(&self, query: &str, max_distance: usize) -> Vec<(&T, usize)>
+    where
+        T: PhfBorrow<str>,
+    {
+        self.map
+            .suggest(query, max_distance)
+            .into_iter()
+            .map(|(value, (), distance)| (value, distance))
+            .collect()
+    }
+
+    /// Returns the number of bytes of static data the set uses.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.map.memory_usage()
+    }
+
+    /// Returns statistics on the hash layout of the set.
+    pub fn layout_stats(&self) -> LayoutStats {
+        self.map.layout_stats()
+    }
+
+    /// Checks that every value of the set can be looked up.
+    ///
+    /// See [`Map::validate`].
+    pub fn validate(&self) -> Result<(), UnreachableKeys<'_, T, ()>>
+    where
+        T: PhfHash,
+    {
+        self.map.validate()
+    }
+
+    /// Returns a snapshot of the lookup counters of the set.
+    ///
+    /// See [`Map::stats`].
+    #[cfg(feature = "instrument")]
+    pub fn stats(&self) -> MapStats<'_, T> {
+        self.map.stats()
+    }
+
     /// Returns an iterator over the values in the set.
     ///
     /// Values are returned in an arbitrary but fixed order.
@@ -0,0 +1,385 @@
+//! "Did you mean" suggestions for string keys.
+//!
+//! [`Map::suggest`] compares a query with every key. A [`SuggestMap`] or
+//! [`SuggestSet`] is generated with a [SymSpell]-style deletion table instead:
+//! it maps every string obtained by deleting up to `max_distance` characters
+//! from a key to the entries of the keys it came from. At lookup time the same
+//! deletions are applied to the query, so finding the keys within a given edit
+//! distance only needs a handful of PHF probes instead of a scan over every
+//! key.
+//!
+//! Requires the `std` feature.
+//!
+//! [SymSpell]: https://github.com/wolfgarbe/SymSpell
+use core::fmt;
+use core::mem;
+use core::ops::Deref;
+use std::collections::HashSet;
+
+use phf_shared::PhfBorrow;
+
+use crate::layout::MemoryUsage;
+use crate::{Map, Set};
+
+/// The deletion table of a [`SuggestMap`] or [`SuggestSet`], generated with
+/// it.
+///
+/// ## Note
+///
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`SuggestIndex::from_raw_parts`] instead.
+pub struct SuggestIndex {
+    #[doc(hidden)]
+    pub max_distance: usize,
+    /// The indices into the map's entries of the keys each deletion comes
+    /// from.
+    #[doc(hidden)]
+    pub deletes: Map<&'static str, &'static [u32]>,
+}
+
+impl SuggestIndex {
+    /// Creates a deletion table for keys within `max_distance` edits, mapping
+    /// each deletion to the slots of the keys it comes from.
+    pub const fn from_raw_parts(
+        max_distance: usize,
+        deletes: Map<&'static str, &'static [u32]>,
+    ) -> Self {
+        SuggestIndex {
+            max_distance,
+            deletes,
+        }
+    }
+
+    /// Returns the largest edit distance the table answers queries for.
+    pub const fn max_distance(&self) -> usize {
+        self.max_distance
+    }
+
+    /// Returns the number of bytes of static data the table uses, including
+    /// the slots each deletion maps to.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let slots = self
+            .deletes
+            .values()
+            .map(|slots| mem::size_of_val(*slots))
+            .sum::<usize>();
+        self.deletes
+            .memory_usage()
+            .with_auxiliary(mem::size_of::<SuggestIndex>() + slots)
+    }
+}
+
+/// A [`Map`] with string keys and a deletion table for finding the keys close
+/// to a query.
+///
+/// `phf_map!` generates a `SuggestMap` for maps that start with `#![suggest]`,
+/// and `phf_codegen` does so for maps built with `suggest_index(..)`. It
+/// dereferences to the underlying [`Map`] for lookups and iteration.
+///
+/// ```
+/// use phf::phf_map;
+///
+/// static COMMANDS: phf::suggest::SuggestMap<&'static str, u32> = phf_map! {
+///     #![suggest]
+///     "build" => 0,
+///     "bench" => 1,
+///     "check" => 2,
+/// };
+///
+/// let suggestions = COMMANDS.suggest("biuld", 2);
+/// assert_eq!(suggestions, [(&"build", &0, 1)]);
+/// assert_eq!(COMMANDS.get("check"), Some(&2));
+/// ```
+///
+/// ## Note
+///
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`SuggestMap::from_raw_parts`] instead.
+pub struct SuggestMap<K: 'static, V: 'static> {
+    #[doc(hidden)]
+    pub map: Map<K, V>,
+    #[doc(hidden)]
+    pub index: &'static SuggestIndex,
+}
+
+impl<K, V> SuggestMap<K, V> {
+    /// Creates a map from the underlying map and its deletion table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` refers to slots past the end of `map`, which fails
+    /// compilation when the map initializes a `static` or `const`.
+    pub const fn from_raw_parts(map: Map<K, V>, index: &'static SuggestIndex) -> Self {
+        check_slots(index, map.len());
+        SuggestMap { map, index }
+    }
+
+    /// Returns the deletion table of the map.
+    pub const fn index(&self) -> &SuggestIndex {
+        self.index
+    }
+
+    /// Returns the keys within `max_distance` edits of `query` along with
+    /// their values and distances, nearest first.
+    ///
+    /// Like [`Map::suggest`], but only compares `query` with the keys that
+    /// share a deletion with it. `max_distance` is capped at the distance the
+    /// table was generated for.
+    pub fn suggest(&self, query: &str, max_distance: usize) -> Vec<(&K, &V, usize)>
+    where
+        K: PhfBorrow<str>,
+    {
+        entries(
+            &self.map,
+            suggest(&self.map, Some(self.index), query, max_distance),
+        )
+    }
+
+    /// Returns the number of bytes of static data the map uses, including
+    /// its deletion table.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.map
+            .memory_usage()
+            .with_auxiliary(self.index.memory_usage().total())
+    }
+}
+
+impl<K, V> Deref for SuggestMap<K, V> {
+    type Target = Map<K, V>;
+
+    fn deref(&self) -> &Map<K, V> {
+        &self.map
+    }
+}
+
+impl<K, V> fmt::Debug for SuggestMap<K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.map.fmt(fmt)
+    }
+}
+
+/// A [`Set`] of strings with a deletion table. See [`SuggestMap`].
+///
+/// ## Note
+///
+/// The fields of this struct are public for compatibility with code generated
+/// by older versions of `phf_macros` and `phf_codegen`, which initialize them
+/// directly. They are subject to change at any time and should never be
+/// accessed directly; use [`SuggestSet::from_raw_parts`] instead.
+pub struct SuggestSet<T: 'static> {
+    #[doc(hidden)]
+    pub set: Set<T>,
+    #[doc(hidden)]
+    pub index: &'static SuggestIndex,
+}
+
+impl<T> SuggestSet<T> {
+    /// Creates a set from the underlying set and its deletion table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` refers to slots past the end of `set`, which fails
+    /// compilation when the set initializes a `static` or `const`.
+    pub const fn from_raw_parts(set: Set<T>, index: &'static SuggestIndex) -> Self {
+        check_slots(index, set.len());
+        SuggestSet { set, index }
+    }
+
+    /// Returns the deletion table of the set.
+    pub const fn index(&self) -> &SuggestIndex {
+        self.index
+    }
+
+    /// Returns the values within `max_distance` edits of `query` along with
+    /// their distances, nearest first.
+    ///
+    /// See [`SuggestMap::suggest`].
+    pub fn suggest(&self, query: &str, max_distance: usize) -> Vec<(&T, usize)>
+    where
+        T: PhfBorrow<str>,
+    {
+        let map = &self.set.map;
+        entries(map, suggest(map, Some(self.index), query, max_distance))
+            .into_iter()
+            .map(|(value, (), distance)| (value, distance))
+            .collect()
+    }
+
+    /// Returns the number of bytes of static data the set uses, including
+    /// its deletion table.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.set
+            .memory_usage()
+            .with_auxiliary(self.index.memory_usage().total())
+    }
+}
+
+impl<T> Deref for SuggestSet<T> {
+    type Target = Set<T>;
+
+    fn deref(&self) -> &Set<T> {
+        &self.set
+    }
+}
+
+impl<T> fmt::Debug for SuggestSet<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.set.fmt(fmt)
+    }
+}
+
+/// Panics if `index` refers to slots of a table with more than `len` entries.
+const fn check_slots(index: &SuggestIndex, len: usize) {
+    let deletes = index.deletes.entries;
+    let mut i = 0;
+    while i < deletes.len() {
+        let slots = deletes[i].1;
+        let mut j = 0;
+        while j < slots.len() {
+            assert!(
+                (slots[j] as usize) < len,
+                "`index` slot out of bounds of the entries"
+            );
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+/// Returns the indices of the keys of `map` within `max_distance` edits of
+/// `query`, along with their distances, nearest first.
+///
+/// Without a deletion table, every key is compared with `query`.
+pub(crate) fn suggest<K, V>(
+    map: &Map<K, V>,
+    index: Option<&SuggestIndex>,
+    query: &str,
+    max_distance: usize,
+) -> Vec<(usize, usize)>
+where
+    K: PhfBorrow<str>,
+{
+    let key = |idx: usize| map.entries[idx].0.borrow();
+    let mut suggestions = Vec::new();
+
+    match index {
+        Some(index) => {
+            let max_distance = max_distance.min(index.max_distance);
+            let mut seen = HashSet::new();
+            for delete in deletes(query, max_distance) {
+                let Some((_, candidates)) = index
+                    .deletes
+                    .probe_entry(delete.as_str(), |key| *key == delete)
+                else {
+                    continue;
+                };
+                for &idx in *candidates {
+                    let idx = idx as usize;
+                    if !seen.insert(idx) {
+                        continue;
+                    }
+                    if let Some(distance) = edit_distance(query, key(idx), max_distance) {
+                        suggestions.push((idx, distance));
+                    }
+                }
+            }
+        }
+        None => {
+            for idx in 0..map.entries.len() {
+                if let Some(distance) = edit_distance(query, key(idx), max_distance) {
+                    suggestions.push((idx, distance));
+                }
+            }
+        }
+    }
+
+    suggestions.sort_unstable_by(|a, b| a.1.cmp(&b.1).then_with(|| key(a.0).cmp(key(b.0))));
+    suggestions
+}
+
+/// Resolves the indices returned by [`suggest`] to the entries of `map`.
+pub(crate) fn entries<K, V>(
+    map: &Map<K, V>,
+    suggestions: Vec<(usize, usize)>,
+) -> Vec<(&K, &V, usize)> {
+    suggestions
+        .into_iter()
+        .map(|(idx, distance)| {
+            let (key, value) = &map.entries[idx];
+            (key, value, distance)
+        })
+        .collect()
+}
+
+/// Returns `s` and every string obtained by deleting up to `max_deletes`
+/// characters from it, without duplicates.
+fn deletes(s: &str, max_deletes: usize) -> Vec<String> {
+    let mut seen = HashSet::new();
+    seen.insert(s.to_owned());
+    let mut all = vec![s.to_owned()];
+    let mut frontier = all.clone();
+
+    for _ in 0..max_deletes {
+        let mut next = Vec::new();
+        for word in &frontier {
+            for (idx, c) in word.char_indices() {
+                let mut delete = String::with_capacity(word.len() - c.len_utf8());
+                delete.push_str(&word[..idx]);
+                delete.push_str(&word[idx + c.len_utf8()..]);
+                if seen.insert(delete.clone()) {
+                    next.push(delete);
+                }
+            }
+        }
+        all.extend(next.iter().cloned());
+        frontier = next;
+    }
+
+    all
+}
+
+/// Returns the optimal string alignment distance between `a` and `b` if it is
+/// at most `max_distance`.
+fn edit_distance(a: &str, b: &str, max_distance: usize) -> Option<usize> {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    if a.len().abs_diff(b.len()) > max_distance {
+        return None;
+    }
+
+    // Three rolling rows: two rows back (for transpositions), previous, current.
+    let mut before = vec![0; b.len() + 1];
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut cur = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        cur[0] = i;
+        let mut row_min = cur[0];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            cur[j] = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cur[j] = cur[j].min(before[j - 2] + 1);
+            }
+            row_min = row_min.min(cur[j]);
+        }
+        if row_min > max_distance {
+            return None;
+        }
+        core::mem::swap(&mut before, &mut prev);
+        core::mem::swap(&mut prev, &mut cur);
+    }
+
+    Some(prev[b.len()]).filter(|&distance| distance <= max_distance)
+}
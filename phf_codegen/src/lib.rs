@@ -143,14 +143,18 @@
 #![doc(html_root_url = "https://docs.rs/phf_codegen/0.14.0")]
 #![allow(clippy::new_without_default)]
 
+use phf_generator::ip::Prefix;
 use phf_shared::{FmtConst, PhfHash};
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt;
 use std::hash::Hash;
+use std::net::{Ipv4Addr, Ipv6Addr};
 
 #[cfg(not(feature = "ptrhash"))]
 use phf_generator::HashState;
+use phf_generator::find::KeyLengths;
 #[cfg(feature = "ptrhash")]
 use phf_generator::ptrhash::HashState;
 
@@ -184,10 +188,88 @@ fn generate_hash_state<H: PhfHash>(keys: &[H]) -> HashState {
     }
 }
 
+/// Returns the source of a `#[test]` that checks that every key of the
+/// generated `phf::Map` or `phf::Set` static `name` can be looked up.
+///
+/// Mismatched versions of `phf_codegen` and `phf` are rejected when the
+/// generated code is compiled, but keys can still be unreachable if, for
+/// example, a key type's `PhfHash` implementation changed; the test fails
+/// for such tables.
+///
+/// ```
+/// use std::fmt::Write;
+///
+/// let mut file = String::new();
+/// writeln!(
+///     &mut file,
+///     "static KEYWORDS: phf::Set<&'static str> = \n{};",
+///     phf_codegen::Set::new().entry("loop").entry("fn").build()
+/// )
+/// .unwrap();
+/// writeln!(&mut file, "{}", phf_codegen::validate_test("KEYWORDS")).unwrap();
+/// ```
+pub fn validate_test(name: &str) -> String {
+    let fn_name = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    format!(
+        "#[test]
+fn validate_{fn_name}() {{
+    if let ::core::result::Result::Err(unreachable) = {name}.validate() {{
+        ::core::panic!(\"`{name}`: {{}}\", unreachable);
+    }}
+}}"
+    )
+}
+
+fn generate_hash_family<H: PhfHash>(keys: &[&[H]]) -> Vec<HashState> {
+    #[cfg(not(feature = "ptrhash"))]
+    {
+        phf_generator::generate_hash_family(keys)
+    }
+
+    #[cfg(feature = "ptrhash")]
+    {
+        phf_generator::ptrhash::generate_hash_family(keys)
+    }
+}
+
+fn generate_fingerprints<H: PhfHash>(keys: &[H], state: &HashState) -> Vec<u64> {
+    #[cfg(not(feature = "ptrhash"))]
+    {
+        phf_generator::generate_fingerprints(keys, state)
+    }
+
+    #[cfg(feature = "ptrhash")]
+    {
+        phf_generator::ptrhash::generate_fingerprints(keys, state)
+    }
+}
+
+fn check_duplicates<K: Hash + Eq + FmtConst>(keys: &[K]) {
+    let mut set = HashSet::new();
+    for key in keys {
+        if !set.insert(key) {
+            panic!("duplicate key `{}`", Delegate(key));
+        }
+    }
+}
+
 /// A builder for the `phf::Map` type.
 pub struct Map<'a, K> {
     keys: Vec<K>,
     values: Vec<Cow<'a, str>>,
+    key_bytes: Option<fn(&K) -> &[u8]>,
+    key_str: Option<fn(&K) -> &str>,
+    max_distance: usize,
+    default: Option<Cow<'a, str>>,
     path: Cow<'a, str>,
 }
 
@@ -207,6 +289,10 @@ impl<'a, K: Hash + PhfHash + Eq + FmtConst> Map<'a, K> {
         Map {
             keys: vec![],
             values: vec![],
+            key_bytes: None,
+            key_str: None,
+            max_distance: 0,
+            default: None,
             path: Cow::Borrowed("::phf"),
         }
     }
@@ -226,149 +312,429 @@ impl<'a, K: Hash + PhfHash + Eq + FmtConst> Map<'a, K> {
         self
     }
 
+    /// Sets the value returned for keys that aren't in the map, making the
+    /// constructed type a `phf::MapWithDefault`.
+    ///
+    /// `value` will be written exactly as provided in the constructed source.
+    pub fn default_value(&mut self, value: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.default = Some(value.into());
+        self
+    }
+
     /// Calculate the hash parameters and return a struct implementing
-    /// [`Display`](::std::fmt::Display) for the constructed `phf::Map`.
+    /// [`Display`](::std::fmt::Display) for the constructed `phf::Map`, or
+    /// `phf::MapWithDefault` if a default value was set.
     ///
     /// With the `quote` feature enabled, the returned value also implements
     /// `quote::ToTokens`.
     ///
     /// # Panics
     ///
-    /// Panics if there are any duplicate keys.
+    /// Panics if there are any duplicate keys, if both
+    /// [`record_key_lengths`](Self::record_key_lengths) and
+    /// [`suggest_index`](Self::suggest_index) were called, or if either was
+    /// called along with [`default_value`](Self::default_value).
     pub fn build(&self) -> DisplayMap<'_, K> {
-        let mut set = HashSet::new();
-        for key in &self.keys {
-            if !set.insert(key) {
-                panic!("duplicate key `{}`", Delegate(key));
-            }
-        }
+        check_duplicates(&self.keys);
 
         let state = generate_hash_state(&self.keys);
 
         DisplayMap {
-            state,
             path: &self.path,
             keys: &self.keys,
             values: &self.values,
+            key_lengths: self.key_lengths(),
+            suggest_index: self.deletion_table(&state),
+            default: self.default(),
+            state,
+        }
+    }
+
+    /// Like [`build`](Self::build), but calculates hash parameters shared by
+    /// all of `maps`.
+    ///
+    /// The constructed maps use the same hash key, so a `phf::PreparedKey`
+    /// created from one of them can be looked up in all of them while only
+    /// hashing the key once.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`build`](Self::build) for any of
+    /// the maps.
+    pub fn build_family<'b>(maps: &[&'b Map<'a, K>]) -> Vec<DisplayMap<'b, K>> {
+        for map in maps {
+            check_duplicates(&map.keys);
+        }
+
+        let keys = maps.iter().map(|map| &map.keys[..]).collect::<Vec<_>>();
+        let states = generate_hash_family(&keys);
+
+        maps.iter()
+            .zip(states)
+            .map(|(map, state)| DisplayMap {
+                path: &map.path,
+                keys: &map.keys,
+                values: &map.values,
+                key_lengths: map.key_lengths(),
+                suggest_index: map.deletion_table(&state),
+                default: map.default(),
+                state,
+            })
+            .collect()
+    }
+
+    fn default(&self) -> Option<&str> {
+        let default = self.default.as_deref()?;
+        assert!(
+            self.key_bytes.is_none() && self.key_str.is_none(),
+            "a map with a default value can't record its key lengths or have a deletion table"
+        );
+        Some(default)
+    }
+
+    fn key_lengths(&self) -> Option<KeyLengths> {
+        let key_bytes = self.key_bytes?;
+        assert!(
+            self.key_str.is_none(),
+            "a map can't record its key lengths and have a deletion table"
+        );
+        Some(phf_generator::find::key_lengths(
+            self.keys.iter().map(|key| key_bytes(key).len()),
+        ))
+    }
+
+    /// Returns the deletion table of the map for `phf::suggest::SuggestMap`,
+    /// with each deletion mapped to the slots in `state` of the keys it comes
+    /// from.
+    fn deletion_table(&self, state: &HashState) -> Option<SuggestIndex> {
+        let key_str = self.key_str?;
+        let keys = self.keys.iter().map(key_str).collect::<Vec<_>>();
+
+        let mut slots = vec![0; keys.len()];
+        for (slot, &idx) in state.map.iter().enumerate() {
+            slots[idx] = slot as u32;
         }
+
+        let (deletes, candidates) =
+            phf_generator::suggest::generate_deletes(&keys, self.max_distance)
+                .into_iter()
+                .map(|(delete, idxs)| {
+                    let candidates = idxs.iter().map(|&idx| slots[idx]).collect::<Vec<_>>();
+                    (delete, Cow::Owned(format!("&{:?}", candidates)))
+                })
+                .unzip::<_, _, Vec<_>, Vec<_>>();
+        let state = generate_hash_state(&deletes);
+
+        Some(SuggestIndex {
+            max_distance: self.max_distance,
+            state,
+            deletes,
+            candidates,
+        })
+    }
+}
+
+impl<'a, K: Hash + PhfHash + Eq + FmtConst + AsRef<[u8]>> Map<'a, K> {
+    /// Records the lengths of the keys, so that the constructed map is a
+    /// `phf::find::FindMap` whose `find_iter` only probes the lengths some
+    /// key has rather than visiting every key to collect them.
+    pub fn record_key_lengths(&mut self) -> &mut Self {
+        self.key_bytes = Some(key_bytes::<K>);
+        self
+    }
+}
+
+impl<'a, K: Hash + PhfHash + Eq + FmtConst + AsRef<str>> Map<'a, K> {
+    /// Generates a deletion table along with the map, so that the constructed
+    /// map is a `phf::suggest::SuggestMap` whose `suggest` only compares a
+    /// query with the keys that share a deletion with it.
+    ///
+    /// The table answers queries up to `max_distance` edits away. Each
+    /// additional edit grows it considerably, as every key contributes an
+    /// entry for each way of deleting up to `max_distance` of its characters.
+    pub fn suggest_index(&mut self, max_distance: usize) -> &mut Self {
+        self.key_str = Some(key_str::<K>);
+        self.max_distance = max_distance;
+        self
     }
 }
 
+fn key_bytes<K: AsRef<[u8]>>(key: &K) -> &[u8] {
+    key.as_ref()
+}
+
+fn key_str<K: AsRef<str>>(key: &K) -> &str {
+    key.as_ref()
+}
+
+/// The deletion table of a map, with its hash parameters.
+struct SuggestIndex {
+    max_distance: usize,
+    state: HashState,
+    deletes: Vec<String>,
+    candidates: Vec<Cow<'static, str>>,
+}
+
 /// An adapter for printing a [`Map`](Map).
 pub struct DisplayMap<'a, K> {
     path: &'a str,
     state: HashState,
     keys: &'a [K],
     values: &'a [Cow<'a, str>],
+    key_lengths: Option<KeyLengths>,
+    suggest_index: Option<SuggestIndex>,
+    default: Option<&'a str>,
 }
 
 impl<'a, K: FmtConst + 'a> fmt::Display for DisplayMap<'a, K> {
-    #[cfg(not(feature = "ptrhash"))]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // funky formatting here for nice output
-        write!(
-            f,
-            "{}::Map {{
-    key: {:?},
-    disps: &[",
-            self.path, self.state.key
-        )?;
+        fmt_with_default(f, self.path, self.default, |f| {
+            self.fmt_wrapped(f, "Map", |f| {
+                fmt_map(f, self.path, &self.state, self.keys, self.values)
+            })
+        })
+    }
+}
 
-        // write map displacements
-        for &(d1, d2) in &self.state.disps {
-            write!(
-                f,
-                "
-        ({}, {}),",
-                d1, d2
-            )?;
+impl<'a, K: FmtConst + 'a> DisplayMap<'a, K> {
+    /// Writes `table`, a `phf::Map` or `phf::Set` as named by `kind`, wrapped
+    /// in its `find` or `suggest` counterpart if key lengths or a deletion
+    /// table were generated.
+    fn fmt_wrapped(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        kind: &str,
+        table: impl FnOnce(&mut fmt::Formatter<'_>) -> fmt::Result,
+    ) -> fmt::Result {
+        let kind = kind.to_ascii_lowercase();
+        if let Some(key_lengths) = &self.key_lengths {
+            write!(f, "{}::__find_{}!(", self.path, kind)?;
+            table(f)?;
+            fmt_key_lengths(f, self.path, key_lengths)
+        } else if let Some(index) = &self.suggest_index {
+            write!(f, "{}::__suggest_{}!(", self.path, kind)?;
+            table(f)?;
+            fmt_suggest_index(f, self.path, index)
+        } else {
+            table(f)
         }
+    }
+}
+
+/// Writes the map `fmt_map` writes, wrapped in a `phf::MapWithDefault` if
+/// there's a default value.
+fn fmt_with_default(
+    f: &mut fmt::Formatter<'_>,
+    path: &str,
+    default: Option<&str>,
+    fmt_map: impl FnOnce(&mut fmt::Formatter<'_>) -> fmt::Result,
+) -> fmt::Result {
+    let Some(default) = default else {
+        return fmt_map(f);
+    };
+    write!(f, "{}::__map_with_default!(", path)?;
+    fmt_map(f)?;
+    write!(f, ", {})", default)
+}
 
+#[cfg(not(feature = "ptrhash"))]
+fn fmt_map<K: FmtConst>(
+    f: &mut fmt::Formatter<'_>,
+    path: &str,
+    state: &HashState,
+    keys: &[K],
+    values: &[Cow<'_, str>],
+) -> fmt::Result {
+    // funky formatting here for nice output
+    write!(
+        f,
+        "{}::__map!(
+    {:?},
+    {:?},
+    [",
+        path,
+        phf_shared::TABLE_FORMAT,
+        state.key
+    )?;
+
+    // write map displacements
+    for &(d1, d2) in &state.disps {
         write!(
             f,
             "
-    ],
-    entries: &[",
+        ({}, {}),",
+            d1, d2
         )?;
+    }
 
-        // write map entries
-        for &idx in &self.state.map {
-            write!(
-                f,
-                "
-        ({}, {}),",
-                Delegate(&self.keys[idx]),
-                &self.values[idx]
-            )?;
-        }
+    write!(
+        f,
+        "
+    ],
+    [",
+    )?;
 
+    // write map entries
+    for &idx in &state.map {
         write!(
             f,
             "
-    ],
-}}"
-        )
+        ({}, {}),",
+            Delegate(&keys[idx]),
+            &values[idx]
+        )?;
     }
 
-    #[cfg(feature = "ptrhash")]
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+        f,
+        "
+    ],"
+    )?;
+    fmt_counters(f, path, keys.len())?;
+    write!(f, "\n)")
+}
+
+#[cfg(feature = "ptrhash")]
+fn fmt_map<K: FmtConst>(
+    f: &mut fmt::Formatter<'_>,
+    path: &str,
+    state: &HashState,
+    keys: &[K],
+    values: &[Cow<'_, str>],
+) -> fmt::Result {
+    write!(
+        f,
+        "{}::__map!(
+    {:?},
+    {:?},
+    [",
+        path,
+        phf_shared::TABLE_FORMAT,
+        state.seed
+    )?;
+
+    for &pilot in &state.pilots {
         write!(
             f,
-            "{}::Map {{
-    key: {:?},
-    pilots: &[",
-            self.path, self.state.seed
+            "
+        {},",
+            pilot
         )?;
+    }
 
-        for &pilot in &self.state.pilots {
-            write!(
-                f,
-                "
-        {},",
-                pilot
-            )?;
-        }
+    write!(
+        f,
+        "
+    ],
+    [",
+    )?;
 
+    for &index in &state.remap {
         write!(
             f,
             "
-    ],
-    remap: &[",
+        {},",
+            index
         )?;
+    }
 
-        for &index in &self.state.remap {
-            write!(
-                f,
-                "
-        {},",
-                index
-            )?;
-        }
+    write!(
+        f,
+        "
+    ],
+    [",
+    )?;
 
+    for &idx in &state.map {
         write!(
             f,
             "
-    ],
-    entries: &[",
+        ({}, {}),",
+            Delegate(&keys[idx]),
+            &values[idx]
         )?;
+    }
 
-        for &idx in &self.state.map {
-            write!(
-                f,
-                "
-        ({}, {}),",
-                Delegate(&self.keys[idx]),
-                &self.values[idx]
-            )?;
-        }
+    write!(
+        f,
+        "
+    ],"
+    )?;
+    fmt_counters(f, path, keys.len())?;
+    write!(f, "\n)")
+}
 
-        write!(
-            f,
-            "
-    ],
-}}"
-        )
+/// Writes the trailing `counters` argument of `phf::__map!` and similar
+/// macros for a table counting lookups of `len` entries, or nothing without the
+/// `instrument` feature.
+///
+/// The counters live in their own statics rather than in the map, so that maps
+/// can still be stored in promoted constants, as `phf::IpPrefixMap` does.
+fn fmt_counters(f: &mut fmt::Formatter<'_>, path: &str, len: usize) -> fmt::Result {
+    if !cfg!(feature = "instrument") {
+        return Ok(());
     }
+    // Clippy flags `[const { .. }; 0]` in case the initializer has side
+    // effects.
+    let hits = if len == 0 {
+        "[]".to_owned()
+    } else {
+        format!(
+            "[const {{ ::std::sync::atomic::AtomicU64::new(0) }}; {}]",
+            len
+        )
+    };
+    write!(
+        f,
+        "
+    {{
+        static HITS: [::std::sync::atomic::AtomicU64; {len}] = {hits};
+        static COUNTERS: {path}::instrument::Counters = {path}::instrument::Counters::new(
+            ::core::concat!(::core::module_path!(), \":\", ::core::line!()),
+            &HITS,
+        );
+        &COUNTERS
+    }},",
+        len = len,
+        hits = hits,
+        path = path,
+    )
+}
+
+/// Writes the `key_lengths` argument of `phf::__find_map!` or `__find_set!`,
+/// closing the call.
+fn fmt_key_lengths(
+    f: &mut fmt::Formatter<'_>,
+    path: &str,
+    key_lengths: &KeyLengths,
+) -> fmt::Result {
+    write!(
+        f,
+        ",
+    &{}::find::KeyLengths::from_raw_parts({:?}, &{:?}, {}, {}),
+)",
+        path, key_lengths.small, key_lengths.long, key_lengths.min, key_lengths.max
+    )
+}
+
+/// Writes the `index` argument of `phf::__suggest_map!` or `__suggest_set!`,
+/// closing the call.
+fn fmt_suggest_index(f: &mut fmt::Formatter<'_>, path: &str, index: &SuggestIndex) -> fmt::Result {
+    write!(
+        f,
+        ",
+    &{}::suggest::SuggestIndex::from_raw_parts(
+        {},
+        ",
+        path, index.max_distance
+    )?;
+    fmt_map(f, path, &index.state, &index.deletes, &index.candidates)?;
+    write!(
+        f,
+        ",
+    ),
+)"
+    )
 }
 
 #[cfg(feature = "quote")]
@@ -423,12 +789,48 @@ impl<'a, T: Hash + PhfHash + Eq + FmtConst> Set<'a, T> {
     ///
     /// # Panics
     ///
-    /// Panics if there are any duplicate keys.
+    /// Panics if there are any duplicate keys, if both
+    /// [`record_key_lengths`](Self::record_key_lengths) and
+    /// [`suggest_index`](Self::suggest_index) were called, or if either was
+    /// called along with [`default_value`](Self::default_value).
     pub fn build(&self) -> DisplaySet<'_, T> {
         DisplaySet {
             inner: self.map.build(),
         }
     }
+
+    /// Like [`build`](Self::build), but calculates hash parameters shared by
+    /// all of `sets`. See [`Map::build_family`].
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`build`](Self::build) for any of
+    /// the sets.
+    pub fn build_family<'b>(sets: &[&'b Set<'a, T>]) -> Vec<DisplaySet<'b, T>> {
+        let maps = sets.iter().map(|set| &set.map).collect::<Vec<_>>();
+        Map::build_family(&maps)
+            .into_iter()
+            .map(|inner| DisplaySet { inner })
+            .collect()
+    }
+}
+
+impl<'a, T: Hash + PhfHash + Eq + FmtConst + AsRef<[u8]>> Set<'a, T> {
+    /// Records the lengths of the values, so that the constructed set is a
+    /// `phf::find::FindSet`. See [`Map::record_key_lengths`].
+    pub fn record_key_lengths(&mut self) -> &mut Self {
+        self.map.record_key_lengths();
+        self
+    }
+}
+
+impl<'a, T: Hash + PhfHash + Eq + FmtConst + AsRef<str>> Set<'a, T> {
+    /// Generates a deletion table along with the set, so that the constructed
+    /// set is a `phf::suggest::SuggestSet`. See [`Map::suggest_index`].
+    pub fn suggest_index(&mut self, max_distance: usize) -> &mut Self {
+        self.map.suggest_index(max_distance);
+        self
+    }
 }
 
 /// An adapter for printing a [`Set`](Set).
@@ -438,7 +840,12 @@ pub struct DisplaySet<'a, T> {
 
 impl<'a, T: FmtConst + 'a> fmt::Display for DisplaySet<'a, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}::Set {{ map: {} }}", self.inner.path, self.inner)
+        let inner = &self.inner;
+        inner.fmt_wrapped(f, "Set", |f| {
+            write!(f, "{}::__set!(", inner.path)?;
+            fmt_map(f, inner.path, &inner.state, inner.keys, inner.values)?;
+            write!(f, ")")
+        })
     }
 }
 
@@ -449,19 +856,17 @@ impl<'a, T: FmtConst + 'a> quote::ToTokens for DisplaySet<'a, T> {
     }
 }
 
-/// A builder for the `phf::OrderedMap` type.
-pub struct OrderedMap<'a, K> {
-    keys: Vec<K>,
-    values: Vec<Cow<'a, str>>,
+/// A builder for the `phf::Filter` type.
+pub struct Filter<'a, T> {
+    keys: Vec<T>,
     path: Cow<'a, str>,
 }
 
-impl<'a, K: Hash + PhfHash + Eq + FmtConst> OrderedMap<'a, K> {
-    /// Constructs a enw `phf::OrderedMap` builder.
+impl<'a, T: Hash + PhfHash + Eq + FmtConst> Filter<'a, T> {
+    /// Constructs a new `phf::Filter` builder.
     pub fn new() -> Self {
-        OrderedMap {
+        Filter {
             keys: vec![],
-            values: vec![],
             path: Cow::Borrowed("::phf"),
         }
     }
@@ -472,17 +877,18 @@ impl<'a, K: Hash + PhfHash + Eq + FmtConst> OrderedMap<'a, K> {
         self
     }
 
-    /// Adds an entry to the builder.
-    ///
-    /// `value` will be written exactly as provided in the constructed source.
-    pub fn entry(&mut self, key: K, value: impl Into<Cow<'a, str>>) -> &mut Self {
+    /// Adds a key to the builder.
+    pub fn entry(&mut self, key: T) -> &mut Self {
         self.keys.push(key);
-        self.values.push(value.into());
         self
     }
 
-    /// Calculate the hash parameters and return a struct implementing
-    /// [`Display`](::std::fmt::Display) for the constructed `phf::OrderedMap`.
+    /// Calculate the hash parameters and fingerprints and return a struct
+    /// implementing [`Display`](::std::fmt::Display) for the constructed
+    /// `phf::Filter`.
+    ///
+    /// The fingerprint width is chosen by the type the generated value is
+    /// assigned to, e.g. `phf::Filter<u8>`.
     ///
     /// With the `quote` feature enabled, the returned value also implements
     /// `quote::ToTokens`.
@@ -490,42 +896,39 @@ impl<'a, K: Hash + PhfHash + Eq + FmtConst> OrderedMap<'a, K> {
     /// # Panics
     ///
     /// Panics if there are any duplicate keys.
-    pub fn build(&self) -> DisplayOrderedMap<'_, K> {
-        let mut set = HashSet::new();
-        for key in &self.keys {
-            if !set.insert(key) {
-                panic!("duplicate key `{}`", Delegate(key));
-            }
-        }
+    pub fn build(&self) -> DisplayFilter<'_> {
+        check_duplicates(&self.keys);
 
         let state = generate_hash_state(&self.keys);
+        let fingerprints = generate_fingerprints(&self.keys, &state);
 
-        DisplayOrderedMap {
-            state,
+        DisplayFilter {
             path: &self.path,
-            keys: &self.keys,
-            values: &self.values,
+            state,
+            fingerprints,
         }
     }
 }
 
-/// An adapter for printing a [`OrderedMap`](OrderedMap).
-pub struct DisplayOrderedMap<'a, K> {
+/// An adapter for printing a [`Filter`](Filter).
+pub struct DisplayFilter<'a> {
     path: &'a str,
     state: HashState,
-    keys: &'a [K],
-    values: &'a [Cow<'a, str>],
+    fingerprints: Vec<u64>,
 }
 
-impl<'a, K: FmtConst + 'a> fmt::Display for DisplayOrderedMap<'a, K> {
+impl<'a> fmt::Display for DisplayFilter<'a> {
     #[cfg(not(feature = "ptrhash"))]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{}::OrderedMap {{
-    key: {:?},
-    disps: &[",
-            self.path, self.state.key
+            "{}::__filter!(
+    {:?},
+    {:?},
+    [",
+            self.path,
+            phf_shared::TABLE_FORMAT,
+            self.state.key
         )?;
         for &(d1, d2) in &self.state.disps {
             write!(
@@ -535,53 +938,21 @@ impl<'a, K: FmtConst + 'a> fmt::Display for DisplayOrderedMap<'a, K> {
                 d1, d2
             )?;
         }
-        write!(
-            f,
-            "
-    ],
-    idxs: &[",
-        )?;
-        for &idx in &self.state.map {
-            write!(
-                f,
-                "
-        {},",
-                idx
-            )?;
-        }
-        write!(
-            f,
-            "
-    ],
-    entries: &[",
-        )?;
-        for (key, value) in self.keys.iter().zip(self.values.iter()) {
-            write!(
-                f,
-                "
-        ({}, {}),",
-                Delegate(key),
-                value
-            )?;
-        }
-        write!(
-            f,
-            "
-    ],
-}}"
-        )
+        self.fmt_fingerprints(f)
     }
 
     #[cfg(feature = "ptrhash")]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{}::OrderedMap {{
-    key: {:?},
-    pilots: &[",
-            self.path, self.state.seed
+            "{}::__filter!(
+    {:?},
+    {:?},
+    [",
+            self.path,
+            phf_shared::TABLE_FORMAT,
+            self.state.seed
         )?;
-
         for &pilot in &self.state.pilots {
             write!(
                 f,
@@ -590,14 +961,12 @@ impl<'a, K: FmtConst + 'a> fmt::Display for DisplayOrderedMap<'a, K> {
                 pilot
             )?;
         }
-
         write!(
             f,
             "
     ],
-    remap: &[",
+    [",
         )?;
-
         for &index in &self.state.remap {
             write!(
                 f,
@@ -606,59 +975,302 @@ impl<'a, K: FmtConst + 'a> fmt::Display for DisplayOrderedMap<'a, K> {
                 index
             )?;
         }
+        self.fmt_fingerprints(f)
+    }
+}
 
+impl<'a> DisplayFilter<'a> {
+    fn fmt_fingerprints(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
             "
     ],
-    idxs: &[",
-        )?;
-
-        for &idx in &self.state.map {
-            write!(
-                f,
-                "
-        {},",
-                idx
-            )?;
-        }
-
-        write!(
-            f,
-            "
-    ],
-    entries: &[",
+    [",
         )?;
-
-        for (key, value) in self.keys.iter().zip(self.values.iter()) {
+        // Truncated to the filter's fingerprint type by `as _`.
+        for &fingerprint in &self.fingerprints {
             write!(
                 f,
                 "
-        ({}, {}),",
-                Delegate(key),
-                value
+        {:#x}u64 as _,",
+                fingerprint
             )?;
         }
-
         write!(
             f,
             "
     ],
-}}"
+)"
         )
     }
 }
 
 #[cfg(feature = "quote")]
-impl<'a, K: FmtConst + 'a> quote::ToTokens for DisplayOrderedMap<'a, K> {
+impl<'a> quote::ToTokens for DisplayFilter<'a> {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         write_tokens(tokens, self);
     }
 }
 
-/// A builder for the `phf::OrderedSet` type.
-pub struct OrderedSet<'a, T> {
-    map: OrderedMap<'a, T>,
+/// A builder for the `phf::OrderedMap` type.
+pub struct OrderedMap<'a, K> {
+    keys: Vec<K>,
+    values: Vec<Cow<'a, str>>,
+    default: Option<Cow<'a, str>>,
+    path: Cow<'a, str>,
+}
+
+impl<'a, K: Hash + PhfHash + Eq + FmtConst> OrderedMap<'a, K> {
+    /// Constructs a enw `phf::OrderedMap` builder.
+    pub fn new() -> Self {
+        OrderedMap {
+            keys: vec![],
+            values: vec![],
+            default: None,
+            path: Cow::Borrowed("::phf"),
+        }
+    }
+
+    /// Set the path to the `phf` crate from the global namespace
+    pub fn phf_path(&mut self, path: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Adds an entry to the builder.
+    ///
+    /// `value` will be written exactly as provided in the constructed source.
+    pub fn entry(&mut self, key: K, value: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.keys.push(key);
+        self.values.push(value.into());
+        self
+    }
+
+    /// Sets the value returned for keys that aren't in the map, making the
+    /// constructed type a `phf::MapWithDefault`.
+    ///
+    /// `value` will be written exactly as provided in the constructed source.
+    pub fn default_value(&mut self, value: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.default = Some(value.into());
+        self
+    }
+
+    /// Calculate the hash parameters and return a struct implementing
+    /// [`Display`](::std::fmt::Display) for the constructed `phf::OrderedMap`,
+    /// or `phf::MapWithDefault` if a default value was set.
+    ///
+    /// With the `quote` feature enabled, the returned value also implements
+    /// `quote::ToTokens`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are any duplicate keys.
+    pub fn build(&self) -> DisplayOrderedMap<'_, K> {
+        check_duplicates(&self.keys);
+
+        let state = generate_hash_state(&self.keys);
+
+        DisplayOrderedMap {
+            state,
+            path: &self.path,
+            keys: &self.keys,
+            values: &self.values,
+            default: self.default.as_deref(),
+        }
+    }
+}
+
+/// An adapter for printing a [`OrderedMap`](OrderedMap).
+pub struct DisplayOrderedMap<'a, K> {
+    path: &'a str,
+    state: HashState,
+    keys: &'a [K],
+    values: &'a [Cow<'a, str>],
+    default: Option<&'a str>,
+}
+
+impl<'a, K: FmtConst + 'a> fmt::Display for DisplayOrderedMap<'a, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_with_default(f, self.path, self.default, |f| {
+            fmt_ordered_map(f, self.path, &self.state, self.keys, self.values, true)
+        })
+    }
+}
+
+#[cfg(not(feature = "ptrhash"))]
+fn fmt_ordered_map<K: FmtConst>(
+    f: &mut fmt::Formatter<'_>,
+    path: &str,
+    state: &HashState,
+    keys: &[K],
+    values: &[Cow<'_, str>],
+    counted: bool,
+) -> fmt::Result {
+    write!(
+        f,
+        "{}::__ordered_map!(
+    {:?},
+    {:?},
+    [",
+        path,
+        phf_shared::TABLE_FORMAT,
+        state.key
+    )?;
+    for &(d1, d2) in &state.disps {
+        write!(
+            f,
+            "
+        ({}, {}),",
+            d1, d2
+        )?;
+    }
+    write!(
+        f,
+        "
+    ],
+    [",
+    )?;
+    for &idx in &state.map {
+        write!(
+            f,
+            "
+        {},",
+            idx
+        )?;
+    }
+    write!(
+        f,
+        "
+    ],
+    [",
+    )?;
+    for (key, value) in keys.iter().zip(values.iter()) {
+        write!(
+            f,
+            "
+        ({}, {}),",
+            Delegate(key),
+            value
+        )?;
+    }
+    write!(
+        f,
+        "
+    ],"
+    )?;
+    if counted {
+        fmt_counters(f, path, keys.len())?;
+    }
+    write!(
+        f,
+        "
+)"
+    )
+}
+
+#[cfg(feature = "ptrhash")]
+fn fmt_ordered_map<K: FmtConst>(
+    f: &mut fmt::Formatter<'_>,
+    path: &str,
+    state: &HashState,
+    keys: &[K],
+    values: &[Cow<'_, str>],
+    counted: bool,
+) -> fmt::Result {
+    write!(
+        f,
+        "{}::__ordered_map!(
+    {:?},
+    {:?},
+    [",
+        path,
+        phf_shared::TABLE_FORMAT,
+        state.seed
+    )?;
+
+    for &pilot in &state.pilots {
+        write!(
+            f,
+            "
+        {},",
+            pilot
+        )?;
+    }
+
+    write!(
+        f,
+        "
+    ],
+    [",
+    )?;
+
+    for &index in &state.remap {
+        write!(
+            f,
+            "
+        {},",
+            index
+        )?;
+    }
+
+    write!(
+        f,
+        "
+    ],
+    [",
+    )?;
+
+    for &idx in &state.map {
+        write!(
+            f,
+            "
+        {},",
+            idx
+        )?;
+    }
+
+    write!(
+        f,
+        "
+    ],
+    [",
+    )?;
+
+    for (key, value) in keys.iter().zip(values.iter()) {
+        write!(
+            f,
+            "
+        ({}, {}),",
+            Delegate(key),
+            value
+        )?;
+    }
+
+    write!(
+        f,
+        "
+    ],"
+    )?;
+    if counted {
+        fmt_counters(f, path, keys.len())?;
+    }
+    write!(
+        f,
+        "
+)"
+    )
+}
+
+#[cfg(feature = "quote")]
+impl<'a, K: FmtConst + 'a> quote::ToTokens for DisplayOrderedMap<'a, K> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        write_tokens(tokens, self);
+    }
+}
+
+/// A builder for the `phf::OrderedSet` type.
+pub struct OrderedSet<'a, T> {
+    map: OrderedMap<'a, T>,
 }
 
 impl<'a, T: Hash + PhfHash + Eq + FmtConst> OrderedSet<'a, T> {
@@ -703,17 +1315,552 @@ pub struct DisplayOrderedSet<'a, T> {
 }
 
 impl<'a, T: FmtConst + 'a> fmt::Display for DisplayOrderedSet<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::__ordered_set!({})", self.inner.path, self.inner)
+    }
+}
+
+#[cfg(feature = "quote")]
+impl<'a, T: FmtConst + 'a> quote::ToTokens for DisplayOrderedSet<'a, T> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        write_tokens(tokens, self);
+    }
+}
+
+/// A tuple key whose first component identifies its group in a
+/// [`GroupedMap`].
+pub trait GroupKey {
+    /// The type of the first tuple component.
+    type Group: Hash + PhfHash + Eq + FmtConst;
+
+    /// Returns the first tuple component.
+    fn group(&self) -> &Self::Group;
+}
+
+macro_rules! group_key_impl {
+    ($first:ident $(, $rest:ident)*) => {
+        impl<$first: Hash + PhfHash + Eq + FmtConst, $($rest),*> GroupKey for ($first, $($rest,)*) {
+            type Group = $first;
+
+            fn group(&self) -> &$first {
+                &self.0
+            }
+        }
+    };
+}
+
+group_key_impl!(A);
+group_key_impl!(A, B);
+group_key_impl!(A, B, C);
+group_key_impl!(A, B, C, D);
+group_key_impl!(A, B, C, D, E);
+group_key_impl!(A, B, C, D, E, F);
+group_key_impl!(A, B, C, D, E, F, G);
+group_key_impl!(A, B, C, D, E, F, G, H);
+group_key_impl!(A, B, C, D, E, F, G, H, I);
+group_key_impl!(A, B, C, D, E, F, G, H, I, J);
+group_key_impl!(A, B, C, D, E, F, G, H, I, J, K);
+group_key_impl!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// A builder for the `phf::GroupedMap` type.
+pub struct GroupedMap<'a, K> {
+    keys: Vec<K>,
+    values: Vec<Cow<'a, str>>,
+    path: Cow<'a, str>,
+}
+
+impl<'a, K: Hash + PhfHash + Eq + FmtConst + GroupKey> GroupedMap<'a, K> {
+    /// Constructs a new `phf::GroupedMap` builder.
+    pub fn new() -> Self {
+        GroupedMap {
+            keys: vec![],
+            values: vec![],
+            path: Cow::Borrowed("::phf"),
+        }
+    }
+
+    /// Set the path to the `phf` crate from the global namespace
+    pub fn phf_path(&mut self, path: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Adds an entry to the builder.
+    ///
+    /// `value` will be written exactly as provided in the constructed source.
+    pub fn entry(&mut self, key: K, value: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.keys.push(key);
+        self.values.push(value.into());
+        self
+    }
+
+    /// Calculate the hash parameters and return a struct implementing
+    /// [`Display`](::std::fmt::Display) for the constructed `phf::GroupedMap`.
+    ///
+    /// Entries are grouped by the first component of their key, keeping the
+    /// order in which they were added.
+    ///
+    /// With the `quote` feature enabled, the returned value also implements
+    /// `quote::ToTokens`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are any duplicate keys.
+    pub fn build(&self) -> DisplayGroupedMap<'_, K> {
+        check_duplicates(&self.keys);
+
+        let mut groups = Vec::<&K::Group>::new();
+        let mut members = Vec::<Vec<usize>>::new();
+        let mut group_of = HashMap::<&K::Group, usize>::new();
+        for (idx, key) in self.keys.iter().enumerate() {
+            let group = *group_of.entry(key.group()).or_insert_with(|| {
+                groups.push(key.group());
+                members.push(Vec::new());
+                groups.len() - 1
+            });
+            members[group].push(idx);
+        }
+
+        let order = members.concat();
+        let keys = order.iter().map(|&idx| &self.keys[idx]).collect::<Vec<_>>();
+        let values = order
+            .iter()
+            .map(|&idx| Cow::Borrowed(&*self.values[idx]))
+            .collect::<Vec<_>>();
+        let state = generate_hash_state(&keys);
+
+        let mut start = 0;
+        let ranges = members
+            .iter()
+            .map(|members| {
+                let end = start + members.len();
+                let range = Cow::Owned(format!("({}, {})", start, end));
+                start = end;
+                range
+            })
+            .collect();
+        let group_state = generate_hash_state(&groups);
+
+        DisplayGroupedMap {
+            path: &self.path,
+            state,
+            keys,
+            values,
+            group_state,
+            groups,
+            ranges,
+        }
+    }
+}
+
+/// An adapter for printing a [`GroupedMap`](GroupedMap).
+pub struct DisplayGroupedMap<'a, K: GroupKey> {
+    path: &'a str,
+    state: HashState,
+    keys: Vec<&'a K>,
+    values: Vec<Cow<'a, str>>,
+    group_state: HashState,
+    groups: Vec<&'a K::Group>,
+    ranges: Vec<Cow<'a, str>>,
+}
+
+impl<'a, K: FmtConst + GroupKey + 'a> fmt::Display for DisplayGroupedMap<'a, K> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{}::OrderedSet {{ map: {} }}",
-            self.inner.path, self.inner
-        )
+            "{}::__grouped_map!({:?}, ",
+            self.path,
+            phf_shared::TABLE_FORMAT
+        )?;
+        fmt_ordered_map(f, self.path, &self.state, &self.keys, &self.values, true)?;
+        write!(f, ", ")?;
+        fmt_map(f, self.path, &self.group_state, &self.groups, &self.ranges)?;
+        write!(f, ")")
     }
 }
 
 #[cfg(feature = "quote")]
-impl<'a, T: FmtConst + 'a> quote::ToTokens for DisplayOrderedSet<'a, T> {
+impl<'a, K: FmtConst + GroupKey + 'a> quote::ToTokens for DisplayGroupedMap<'a, K> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        write_tokens(tokens, self);
+    }
+}
+
+/// A builder for the `phf::IpPrefixMap` type.
+pub struct IpPrefixMap<'a> {
+    prefixes: Vec<Prefix>,
+    values: Vec<Cow<'a, str>>,
+    path: Cow<'a, str>,
+}
+
+impl<'a> IpPrefixMap<'a> {
+    /// Constructs a new `phf::IpPrefixMap` builder.
+    pub fn new() -> Self {
+        IpPrefixMap {
+            prefixes: vec![],
+            values: vec![],
+            path: Cow::Borrowed("::phf"),
+        }
+    }
+
+    /// Set the path to the `phf` crate from the global namespace
+    pub fn phf_path(&mut self, path: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Adds a network in CIDR notation, such as `10.0.0.0/8` or
+    /// `2001:db8::/32`, to the builder. An address without a prefix length is
+    /// a single-host network.
+    ///
+    /// `value` will be written exactly as provided in the constructed source.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cidr` is not a valid network.
+    pub fn entry(&mut self, cidr: &str, value: impl Into<Cow<'a, str>>) -> &mut Self {
+        match phf_generator::ip::parse_cidr(cidr) {
+            Ok(prefix) => self.prefixes.push(prefix),
+            Err(err) => panic!("{}", err),
+        }
+        self.values.push(value.into());
+        self
+    }
+
+    /// Calculate the hash parameters and return a struct implementing
+    /// [`Display`](::std::fmt::Display) for the constructed `phf::IpPrefixMap`.
+    ///
+    /// With the `quote` feature enabled, the returned value also implements
+    /// `quote::ToTokens`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are any duplicate networks.
+    pub fn build(&self) -> DisplayIpPrefixMap<'_> {
+        let mut seen = HashSet::new();
+        let mut v4 = BTreeMap::<_, (Vec<u32>, Vec<Cow<'_, str>>)>::new();
+        let mut v6 = BTreeMap::<_, (Vec<u128>, Vec<Cow<'_, str>>)>::new();
+        for (prefix, value) in self.prefixes.iter().zip(&self.values) {
+            let value = Cow::Borrowed(&**value);
+            match *prefix {
+                Prefix::V4 { network, len } => {
+                    if !seen.insert(prefix) {
+                        panic!("duplicate network `{}/{}`", Ipv4Addr::from(network), len);
+                    }
+                    let (keys, values) = v4.entry(Reverse(len)).or_default();
+                    keys.push(network);
+                    values.push(value);
+                }
+                Prefix::V6 { network, len } => {
+                    if !seen.insert(prefix) {
+                        panic!("duplicate network `{}/{}`", Ipv6Addr::from(network), len);
+                    }
+                    let (keys, values) = v6.entry(Reverse(len)).or_default();
+                    keys.push(network);
+                    values.push(value);
+                }
+            }
+        }
+
+        DisplayIpPrefixMap {
+            path: &self.path,
+            v4: v4
+                .into_iter()
+                .map(|(Reverse(len), (keys, values))| PrefixTable::new(len, keys, values))
+                .collect(),
+            v6: v6
+                .into_iter()
+                .map(|(Reverse(len), (keys, values))| PrefixTable::new(len, keys, values))
+                .collect(),
+        }
+    }
+}
+
+/// The networks of one prefix length.
+struct PrefixTable<'a, K> {
+    len: u8,
+    state: HashState,
+    keys: Vec<K>,
+    values: Vec<Cow<'a, str>>,
+}
+
+impl<'a, K: PhfHash + FmtConst> PrefixTable<'a, K> {
+    fn new(len: u8, keys: Vec<K>, values: Vec<Cow<'a, str>>) -> Self {
+        PrefixTable {
+            len,
+            state: generate_hash_state(&keys),
+            keys,
+            values,
+        }
+    }
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>, path: &str) -> fmt::Result {
+        write!(f, "\n        ({}, ", self.len)?;
+        fmt_map(f, path, &self.state, &self.keys, &self.values)?;
+        write!(f, "),")
+    }
+}
+
+/// An adapter for printing a [`IpPrefixMap`](IpPrefixMap).
+pub struct DisplayIpPrefixMap<'a> {
+    path: &'a str,
+    v4: Vec<PrefixTable<'a, u32>>,
+    v6: Vec<PrefixTable<'a, u128>>,
+}
+
+impl<'a> fmt::Display for DisplayIpPrefixMap<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}::__ip_prefix_map!(\n    {:?},\n    [",
+            self.path,
+            phf_shared::TABLE_FORMAT
+        )?;
+        for table in &self.v4 {
+            table.fmt(f, self.path)?;
+        }
+        write!(f, "\n    ],\n    [")?;
+        for table in &self.v6 {
+            table.fmt(f, self.path)?;
+        }
+        write!(f, "\n    ],\n)")
+    }
+}
+
+#[cfg(feature = "quote")]
+impl<'a> quote::ToTokens for DisplayIpPrefixMap<'a> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        write_tokens(tokens, self);
+    }
+}
+
+/// A builder for the `phf::RangeMap` type.
+pub struct RangeMap<'a, K> {
+    ranges: Vec<(K, K)>,
+    values: Vec<Cow<'a, str>>,
+    path: Cow<'a, str>,
+}
+
+impl<'a, K: Hash + PhfHash + Eq + Ord + FmtConst> RangeMap<'a, K> {
+    /// Constructs a new `phf::RangeMap` builder.
+    pub fn new() -> Self {
+        RangeMap {
+            ranges: vec![],
+            values: vec![],
+            path: Cow::Borrowed("::phf"),
+        }
+    }
+
+    /// Set the path to the `phf` crate from the global namespace
+    pub fn phf_path(&mut self, path: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Adds the inclusive range `start..=end` to the builder.
+    ///
+    /// `value` will be written exactly as provided in the constructed source.
+    pub fn range(&mut self, start: K, end: K, value: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.ranges.push((start, end));
+        self.values.push(value.into());
+        self
+    }
+
+    /// Adds a single key to the builder.
+    ///
+    /// `value` will be written exactly as provided in the constructed source.
+    pub fn entry(&mut self, key: K, value: impl Into<Cow<'a, str>>) -> &mut Self
+    where
+        K: Clone,
+    {
+        self.range(key.clone(), key, value)
+    }
+
+    /// Calculate the hash parameters and return a struct implementing
+    /// [`Display`](::std::fmt::Display) for the constructed `phf::RangeMap`.
+    ///
+    /// With the `quote` feature enabled, the returned value also implements
+    /// `quote::ToTokens`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any range starts after its end or overlaps another range.
+    pub fn build(&self) -> DisplayRangeMap<'_, K> {
+        let mut order = (0..self.ranges.len()).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| self.ranges[a].0.cmp(&self.ranges[b].0));
+
+        for (start, end) in &self.ranges {
+            if start > end {
+                panic!(
+                    "range start `{}` is greater than its end `{}`",
+                    Delegate(start),
+                    Delegate(end)
+                );
+            }
+        }
+        for pair in order.windows(2) {
+            let (a_start, a_end) = &self.ranges[pair[0]];
+            let (b_start, b_end) = &self.ranges[pair[1]];
+            if a_end >= b_start {
+                panic!(
+                    "range `{}..={}` overlaps range `{}..={}`",
+                    Delegate(a_start),
+                    Delegate(a_end),
+                    Delegate(b_start),
+                    Delegate(b_end)
+                );
+            }
+        }
+
+        let (singletons, ranges): (Vec<_>, Vec<_>) = order
+            .into_iter()
+            .partition(|&idx| self.ranges[idx].0 == self.ranges[idx].1);
+        let singleton_keys = singletons
+            .iter()
+            .map(|&idx| &self.ranges[idx].0)
+            .collect::<Vec<_>>();
+        let singleton_values = singletons
+            .iter()
+            .map(|&idx| Cow::Borrowed(&*self.values[idx]))
+            .collect::<Vec<_>>();
+        let state = generate_hash_state(&singleton_keys);
+
+        DisplayRangeMap {
+            path: &self.path,
+            state,
+            singleton_keys,
+            singleton_values,
+            ranges: ranges
+                .into_iter()
+                .map(|idx| (&self.ranges[idx].0, &self.ranges[idx].1, &*self.values[idx]))
+                .collect(),
+        }
+    }
+}
+
+/// An adapter for printing a [`RangeMap`](RangeMap).
+pub struct DisplayRangeMap<'a, K> {
+    path: &'a str,
+    state: HashState,
+    singleton_keys: Vec<&'a K>,
+    singleton_values: Vec<Cow<'a, str>>,
+    ranges: Vec<(&'a K, &'a K, &'a str)>,
+}
+
+impl<'a, K: FmtConst + 'a> fmt::Display for DisplayRangeMap<'a, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}::__range_map!(\n    {:?},\n    ",
+            self.path,
+            phf_shared::TABLE_FORMAT
+        )?;
+        // The singletons are counted by the `RangeMap`'s counters.
+        fmt_ordered_map(
+            f,
+            self.path,
+            &self.state,
+            &self.singleton_keys,
+            &self.singleton_values,
+            false,
+        )?;
+        write!(f, ",\n    [")?;
+        for &(start, end, value) in &self.ranges {
+            write!(
+                f,
+                "
+        ({}, {}, {}),",
+                Delegate(start),
+                Delegate(end),
+                value
+            )?;
+        }
+        write!(f, "\n    ],")?;
+        fmt_counters(f, self.path, self.singleton_keys.len() + self.ranges.len())?;
+        write!(f, "\n)")
+    }
+}
+
+#[cfg(feature = "quote")]
+impl<'a, K: FmtConst + 'a> quote::ToTokens for DisplayRangeMap<'a, K> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        write_tokens(tokens, self);
+    }
+}
+
+/// A builder for the `phf::LazyMap` type.
+pub struct LazyMap<'a, K> {
+    keys: Vec<K>,
+    values: Vec<Cow<'a, str>>,
+    path: Cow<'a, str>,
+}
+
+impl<'a, K: Hash + PhfHash + Eq + FmtConst> LazyMap<'a, K> {
+    /// Constructs a new `phf::LazyMap` builder.
+    pub fn new() -> Self {
+        LazyMap {
+            keys: vec![],
+            values: vec![],
+            path: Cow::Borrowed("::phf"),
+        }
+    }
+
+    /// Set the path to the `phf` crate from the global namespace
+    pub fn phf_path(&mut self, path: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Adds an entry to the builder.
+    ///
+    /// `init` will be written exactly as provided in the constructed source.
+    /// It must be a closure without captures, or a path to a function, that
+    /// takes no arguments and returns the value.
+    pub fn entry(&mut self, key: K, init: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.keys.push(key);
+        self.values.push(init.into());
+        self
+    }
+
+    /// Calculate the hash parameters and return a struct implementing
+    /// [`Display`](::std::fmt::Display) for the constructed `phf::LazyMap`.
+    ///
+    /// With the `quote` feature enabled, the returned value also implements
+    /// `quote::ToTokens`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are any duplicate keys.
+    pub fn build(&self) -> DisplayLazyMap<'_, K> {
+        check_duplicates(&self.keys);
+
+        DisplayLazyMap {
+            path: &self.path,
+            state: generate_hash_state(&self.keys),
+            keys: &self.keys,
+            values: &self.values,
+        }
+    }
+}
+
+/// An adapter for printing a [`LazyMap`](LazyMap).
+pub struct DisplayLazyMap<'a, K> {
+    path: &'a str,
+    state: HashState,
+    keys: &'a [K],
+    values: &'a [Cow<'a, str>],
+}
+
+impl<'a, K: FmtConst + 'a> fmt::Display for DisplayLazyMap<'a, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::__lazy_map!(", self.path)?;
+        fmt_map(f, self.path, &self.state, self.keys, self.values)?;
+        write!(f, ")")
+    }
+}
+
+#[cfg(feature = "quote")]
+impl<'a, K: FmtConst + 'a> quote::ToTokens for DisplayLazyMap<'a, K> {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         write_tokens(tokens, self);
     }
@@ -3,6 +3,7 @@ use std::fs::File;
 use std::io::{self, BufWriter, Write};
 use std::path::Path;
 
+use phf_shared::normalize::{DashUnderscore, Normalized, Trim};
 use quote::{ToTokens, quote};
 use uncased::UncasedStr;
 use unicase::{Ascii, UniCase};
@@ -31,6 +32,9 @@ fn main() -> io::Result<()> {
             .build()
     )?;
 
+    writeln!(&mut file, "{}", phf_codegen::validate_test("MAP"))?;
+    writeln!(&mut file, "{}", phf_codegen::validate_test("SET"))?;
+
     writeln!(
         &mut file,
         "static ORDERED_MAP: ::phf::OrderedMap<u32, &'static str> = \n{};",
@@ -247,6 +251,252 @@ fn main() -> io::Result<()> {
             .build()
     )?;
 
+    // Test std key types
+    writeln!(
+        &mut file,
+        "static STD_KEY_MAP: ::phf::Map<(Option<::std::net::IpAddr>, ::std::time::Duration), u32> = \n{};",
+        phf_codegen::Map::new()
+            .entry(
+                (
+                    Some(std::net::IpAddr::from([10, 0, 0, 1])),
+                    std::time::Duration::from_millis(1500)
+                ),
+                "1"
+            )
+            .entry(
+                (
+                    Some(std::net::Ipv6Addr::LOCALHOST.into()),
+                    std::time::Duration::ZERO
+                ),
+                "2"
+            )
+            .entry((None, std::time::Duration::from_secs(3)), "3")
+            .build()
+    )?;
+
+    // Test float keys
+    writeln!(
+        &mut file,
+        "static FLOAT_KEY_MAP: ::phf::Map<TotalF64, u32> = \n{};",
+        phf_codegen::Map::new()
+            .entry(phf_shared::TotalF64(0.1), "1")
+            .entry(phf_shared::TotalF64(-0.0), "2")
+            .entry(phf_shared::TotalF64(f64::NEG_INFINITY), "3")
+            .build()
+    )?;
+
+    // Test ASCII case-insensitive keys
+    writeln!(
+        &mut file,
+        "static ASCII_NO_CASE_MAP: ::phf::Map<AsciiNoCase<&'static str>, u32> = \n{};",
+        phf_codegen::Map::new()
+            .entry(phf_shared::AsciiNoCase("Content-Type"), "1")
+            .entry(phf_shared::AsciiNoCase("ETag"), "2")
+            .build()
+    )?;
+
+    // Test owned and shared key types
+    writeln!(
+        &mut file,
+        "static COW_KEY_MAP: ::phf::Map<::std::borrow::Cow<'static, str>, u32> = \n{};",
+        phf_codegen::Map::new()
+            .entry(std::borrow::Cow::<str>::Owned("owned".into()), "1")
+            .entry(std::borrow::Cow::Borrowed("borrowed"), "2")
+            .build()
+    )?;
+
+    writeln!(
+        &mut file,
+        "static COW_BYTES_MAP: ::phf::Map<::std::borrow::Cow<'static, [u8]>, u32> = \n{};",
+        phf_codegen::Map::new()
+            .entry(std::borrow::Cow::<[u8]>::Borrowed(b"bytes"), "1")
+            .build()
+    )?;
+
+    writeln!(
+        &mut file,
+        "static ARC_KEY_MAP: ::phf::Map<&'static str, u32> = \n{};",
+        phf_codegen::Map::new()
+            .entry(std::sync::Arc::<str>::from("shared"), "1")
+            .entry("boxed".into(), "2")
+            .build()
+    )?;
+
+    // Test buffer and identifier keys
+    writeln!(
+        &mut file,
+        "static SMOL_STR_MAP: ::phf::Map<SmolStr, u32> = \n{};",
+        phf_codegen::Map::new()
+            .entry(smol_str::SmolStr::new("inline"), "1")
+            .entry(smol_str::SmolStr::new("a".repeat(40)), "2")
+            .build()
+    )?;
+
+    writeln!(
+        &mut file,
+        "static UUID_MAP: ::phf::Map<Uuid, u32> = \n{};",
+        phf_codegen::Map::new()
+            .entry(uuid::Uuid::from_u128(1), "1")
+            .entry(uuid::Uuid::from_u128(u128::MAX), "2")
+            .build()
+    )?;
+
+    // Test C string keys
+    writeln!(
+        &mut file,
+        "static C_STR_MAP: ::phf::Map<&'static ::std::ffi::CStr, u32> = \n{};",
+        phf_codegen::Map::new()
+            .entry(c"open", "1")
+            .entry(c"quote\"\xff", "2")
+            .build()
+    )?;
+
+    // Test normalized keys
+    writeln!(
+        &mut file,
+        "static NORMALIZED_MAP: ::phf::Map<Normalized<(Trim, DashUnderscore), &'static str>, u32> = \n{};",
+        phf_codegen::Map::new()
+            .entry(Normalized::<(Trim, DashUnderscore), _>::new("dry-run"), "1")
+            .entry(Normalized::new(" no_color "), "2")
+            .build()
+    )?;
+
+    let mut keywords = phf_codegen::Map::new();
+    keywords.entry("fn", "0").entry("let", "1").entry("if", "2");
+    let mut builtins = phf_codegen::Map::new();
+    builtins.entry("print", "10").entry("len", "11");
+    let family = phf_codegen::Map::build_family(&[&keywords, &builtins]);
+    writeln!(
+        &mut file,
+        "static FAMILY_KEYWORDS: ::phf::Map<&'static str, u32> = \n{};",
+        family[0]
+    )?;
+    writeln!(
+        &mut file,
+        "static FAMILY_BUILTINS: ::phf::Map<&'static str, u32> = \n{};",
+        family[1]
+    )?;
+
+    let mut reserved = phf_codegen::Set::new();
+    reserved.entry("fn").entry("async");
+    let mut deprecated = phf_codegen::Set::new();
+    deprecated.entry("typeof");
+    let family = phf_codegen::Set::build_family(&[&reserved, &deprecated]);
+    writeln!(
+        &mut file,
+        "static FAMILY_RESERVED: ::phf::Set<&'static str> = \n{};",
+        family[0]
+    )?;
+    writeln!(
+        &mut file,
+        "static FAMILY_DEPRECATED: ::phf::Set<&'static str> = \n{};",
+        family[1]
+    )?;
+
+    let long_words = ["a".repeat(300), "b".repeat(400), "cat".to_owned()];
+    let mut recorded = phf_codegen::Set::new();
+    let mut unrecorded = phf_codegen::Set::new();
+    for word in &long_words {
+        recorded.entry(word.clone());
+        unrecorded.entry(word.clone());
+    }
+    writeln!(
+        &mut file,
+        "static LONG_WORDS: ::phf::find::FindSet<&'static str> = \n{};",
+        recorded.record_key_lengths().build()
+    )?;
+    writeln!(
+        &mut file,
+        "static LONG_WORDS_UNRECORDED: ::phf::Set<&'static str> = \n{};",
+        unrecorded.build()
+    )?;
+
+    let mut commands = phf_codegen::Map::new();
+    let mut command_set = phf_codegen::Set::new();
+    for (i, command) in ["build", "bench", "check"].into_iter().enumerate() {
+        commands.entry(command, i.to_string());
+        command_set.entry(command);
+    }
+    writeln!(
+        &mut file,
+        "static COMMANDS: ::phf::suggest::SuggestMap<&'static str, usize> = \n{};",
+        commands.suggest_index(1).build()
+    )?;
+    writeln!(
+        &mut file,
+        "static COMMAND_SET: ::phf::suggest::SuggestSet<&'static str> = \n{};",
+        command_set.suggest_index(2).build()
+    )?;
+
+    let mut messages = phf_codegen::GroupedMap::new();
+    messages
+        .entry(("en", 1u32), "\"Hello\"")
+        .entry(("de", 1u32), "\"Hallo\"")
+        .entry(("en", 2u32), "\"Goodbye\"");
+    writeln!(
+        &mut file,
+        "static MESSAGES: ::phf::GroupedMap<(&'static str, u32), &'static str, &'static str> = \n{};",
+        messages.build()
+    )?;
+
+    writeln!(
+        &mut file,
+        "static NETWORKS: ::phf::IpPrefixMap<u32> = \n{};",
+        phf_codegen::IpPrefixMap::new()
+            .entry("0.0.0.0/0", "0")
+            .entry("10.0.0.0/8", "8")
+            .entry("10.1.0.0/16", "16")
+            .entry("fe80::/10", "10")
+            .build()
+    )?;
+
+    let mut deny_list = phf_codegen::Filter::new();
+    deny_list.entry("evil.example").entry("spam.example");
+    writeln!(
+        &mut file,
+        "static DENY_LIST: ::phf::Filter<u16> = \n{};",
+        deny_list.build()
+    )?;
+
+    writeln!(
+        &mut file,
+        "static CHAR_CLASSES: ::phf::RangeMap<char, u8> = \n{};",
+        phf_codegen::RangeMap::new()
+            .range('a', 'z', "1")
+            .range('0', '9', "2")
+            .entry('_', "3")
+            .build()
+    )?;
+
+    writeln!(
+        &mut file,
+        "static LAZY_MAP: ::phf::LazyMap<&'static str, String> = \n{};",
+        phf_codegen::LazyMap::new()
+            .entry("greeting", "|| \"hello\".repeat(2)")
+            .entry("empty", "String::new")
+            .build()
+    )?;
+
+    writeln!(
+        &mut file,
+        "static STATUS_WITH_DEFAULT: ::phf::MapWithDefault<u16, &'static str> = \n{};",
+        phf_codegen::Map::new()
+            .entry(200, "\"OK\"")
+            .entry(404, "\"Not Found\"")
+            .default_value("\"Unknown\"")
+            .build()
+    )?;
+
+    writeln!(
+        &mut file,
+        "static ORDERED_WITH_DEFAULT: ::phf::MapWithDefault<&'static str, u32, ::phf::OrderedMap<&'static str, u32>> = \n{};",
+        phf_codegen::OrderedMap::new()
+            .entry("one", "1")
+            .entry("two", "2")
+            .default_value("0")
+            .build()
+    )?;
+
     let mut quoted_map_tokens = phf_codegen::Map::new();
     quoted_map_tokens
         .entry(1u32, "\"a\"")
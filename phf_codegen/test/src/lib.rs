@@ -1,7 +1,11 @@
 #[cfg(test)]
 mod test {
+    use phf::normalize::{DashUnderscore, Normalized, Trim};
+    use phf::{AsciiNoCase, TotalF64};
+    use smol_str::SmolStr;
     use uncased::UncasedStr;
     use unicase::{Ascii, UniCase};
+    use uuid::Uuid;
 
     include!(concat!(env!("OUT_DIR"), "/codegen.rs"));
 
@@ -237,6 +241,244 @@ mod test {
         assert!(!MIXED_TUPLE_MAP.contains_key(&(true, 4u8, "missing")));
     }
 
+    #[test]
+    fn std_key_map() {
+        use std::net::IpAddr;
+        use std::time::Duration;
+
+        let localhost: IpAddr = "::1".parse().unwrap();
+        assert_eq!(
+            1,
+            STD_KEY_MAP[&(
+                Some("10.0.0.1".parse().unwrap()),
+                Duration::from_secs_f64(1.5)
+            )]
+        );
+        assert_eq!(2, STD_KEY_MAP[&(Some(localhost), Duration::ZERO)]);
+        assert_eq!(3, STD_KEY_MAP[&(None, Duration::from_secs(3))]);
+        assert!(!STD_KEY_MAP.contains_key(&(Some(localhost), Duration::from_secs(3))));
+    }
+
+    #[test]
+    fn float_key_map() {
+        assert_eq!(1, FLOAT_KEY_MAP[&TotalF64(0.1)]);
+        assert_eq!(2, FLOAT_KEY_MAP[&TotalF64(-0.0)]);
+        assert_eq!(3, FLOAT_KEY_MAP[&TotalF64(f64::NEG_INFINITY)]);
+        assert!(!FLOAT_KEY_MAP.contains_key(&TotalF64(0.0)));
+    }
+
+    #[test]
+    fn ascii_no_case_map() {
+        assert_eq!(1, ASCII_NO_CASE_MAP[&AsciiNoCase("content-type")]);
+        assert_eq!(2, ASCII_NO_CASE_MAP[&AsciiNoCase(&b"etag"[..])]);
+        assert!(!ASCII_NO_CASE_MAP.contains_key(&AsciiNoCase("Content-Length")));
+    }
+
+    #[test]
+    fn owned_key_maps() {
+        assert_eq!(1, COW_KEY_MAP["owned"]);
+        assert_eq!(2, COW_KEY_MAP["borrowed"]);
+        assert_eq!(1, COW_BYTES_MAP[&b"bytes"[..]]);
+        assert_eq!(1, ARC_KEY_MAP["shared"]);
+        assert!(!ARC_KEY_MAP.contains_key("owned"));
+    }
+
+    #[test]
+    fn buffer_key_maps() {
+        assert_eq!(1, SMOL_STR_MAP["inline"]);
+        assert_eq!(2, SMOL_STR_MAP[&*"a".repeat(40)]);
+        assert_eq!(1, UUID_MAP[&Uuid::from_u128(1)]);
+        assert_eq!(2, UUID_MAP[&[0xff; 16]]);
+        assert!(!UUID_MAP.contains_key(&Uuid::nil()));
+    }
+
+    #[test]
+    fn c_str_map() {
+        assert_eq!(1, C_STR_MAP[c"open"]);
+        assert_eq!(2, C_STR_MAP[c"quote\"\xff"]);
+        assert!(!C_STR_MAP.contains_key(c"close"));
+    }
+
+    #[test]
+    fn normalized_map() {
+        assert_eq!(1, NORMALIZED_MAP[&Normalized::new("dry_run ")]);
+        assert_eq!(2, NORMALIZED_MAP[&Normalized::new("no-color")]);
+        assert!(!NORMALIZED_MAP.contains_key(&Normalized::new("dry run")));
+    }
+
+    #[test]
+    fn map_family() {
+        let prepared = FAMILY_KEYWORDS.prepare("print");
+        assert_eq!(None, FAMILY_KEYWORDS.get_prepared(&prepared));
+        assert_eq!(Some(&10), FAMILY_BUILTINS.get_prepared(&prepared));
+
+        let prepared = FAMILY_BUILTINS.prepare("let");
+        assert_eq!(Some(&1), FAMILY_KEYWORDS.get_prepared(&prepared));
+        assert_eq!(None, FAMILY_BUILTINS.get_prepared(&prepared));
+    }
+
+    #[test]
+    fn set_family() {
+        let prepared = FAMILY_RESERVED.prepare("typeof");
+        assert!(!FAMILY_RESERVED.contains_prepared(&prepared));
+        assert!(FAMILY_DEPRECATED.contains_prepared(&prepared));
+        assert!(FAMILY_KEYWORDS.get_prepared(&prepared).is_none());
+        assert!(FAMILY_RESERVED.contains_prepared(&FAMILY_DEPRECATED.prepare("async")));
+    }
+
+    #[test]
+    fn find_iter_long_keys() {
+        use phf::MatchKind;
+
+        let text = format!("the {} and {}", "a".repeat(301), "b".repeat(400));
+        let found = |finder: phf::find::Finder<'_, &'static str, ()>, kind| {
+            finder
+                .match_kind(kind)
+                .find_iter(&text)
+                .map(|m| m.range())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            found(LONG_WORDS.finder(), MatchKind::LeftmostLongest),
+            [4..304, 310..710]
+        );
+        assert_eq!(
+            found(LONG_WORDS.finder(), MatchKind::Overlapping),
+            [4..304, 5..305, 310..710]
+        );
+        for kind in [
+            MatchKind::LeftmostLongest,
+            MatchKind::LeftmostShortest,
+            MatchKind::Overlapping,
+        ] {
+            assert_eq!(
+                found(LONG_WORDS.finder(), kind),
+                found(LONG_WORDS_UNRECORDED.finder(), kind)
+            );
+        }
+        assert!(LONG_WORDS.contains("cat"));
+    }
+
+    #[test]
+    fn suggest_index() {
+        assert_eq!(1, COMMANDS.index().max_distance());
+        assert_eq!(COMMANDS.suggest("bld", 2), []);
+        assert_eq!(COMMANDS.suggest("bulid", 2), [(&"build", &0, 1)]);
+        assert_eq!(COMMANDS.suggest("bech", 1), [(&"bench", &1, 1)]);
+        assert_eq!(COMMANDS.get("check"), Some(&2));
+
+        assert_eq!(COMMAND_SET.suggest("bld", 2), [(&"build", 2)]);
+        assert_eq!(
+            COMMAND_SET.suggest("bld", 2),
+            COMMAND_SET.set.suggest("bld", 2)
+        );
+        assert!(COMMAND_SET.contains("bench"));
+    }
+
+    #[test]
+    fn grouped_map() {
+        assert_eq!(Some(&"Hallo"), MESSAGES.get(&("de", 1)));
+        let en = MESSAGES
+            .get_group("en")
+            .map(|(_, v)| *v)
+            .collect::<Vec<_>>();
+        assert_eq!(en, ["Hello", "Goodbye"]);
+        assert!(!MESSAGES.contains_group("fr"));
+    }
+
+    #[test]
+    fn ip_prefix_map() {
+        let get = |addr: &str| NETWORKS.get(addr.parse().unwrap());
+        assert_eq!(Some(&16), get("10.1.0.1"));
+        assert_eq!(Some(&8), get("10.2.0.1"));
+        assert_eq!(Some(&0), get("127.0.0.1"));
+        assert_eq!(Some(&10), get("fe80::1"));
+        assert_eq!(None, get("::1"));
+    }
+
+    #[test]
+    fn filter() {
+        assert_eq!(2, DENY_LIST.len());
+        assert!(DENY_LIST.maybe_contains("evil.example"));
+        assert!(DENY_LIST.maybe_contains("spam.example"));
+        assert!(!DENY_LIST.maybe_contains("rust-lang.org"));
+    }
+
+    #[test]
+    fn range_map() {
+        assert_eq!(Some(&1), CHAR_CLASSES.get(&'k'));
+        assert_eq!(Some(&2), CHAR_CLASSES.get(&'0'));
+        assert_eq!(Some(&3), CHAR_CLASSES.get(&'_'));
+        assert_eq!(None, CHAR_CLASSES.get(&'A'));
+        let ranges = CHAR_CLASSES.iter().collect::<Vec<_>>();
+        assert_eq!(
+            ranges,
+            [(&'0', &'9', &2), (&'_', &'_', &3), (&'a', &'z', &1)]
+        );
+    }
+
+    #[test]
+    fn lazy_map() {
+        assert_eq!(None, LAZY_MAP.get_if_initialized("greeting"));
+        assert_eq!("hellohello", LAZY_MAP["greeting"]);
+        assert_eq!(Some(""), LAZY_MAP.get("empty").map(String::as_str));
+        assert!(!LAZY_MAP.contains_key("missing"));
+    }
+
+    #[test]
+    fn map_with_default() {
+        assert_eq!(2, STATUS_WITH_DEFAULT.len());
+        assert_eq!("OK", STATUS_WITH_DEFAULT[&200]);
+        assert_eq!("Unknown", STATUS_WITH_DEFAULT[&418]);
+        assert_eq!(None, STATUS_WITH_DEFAULT.get_entry(&418));
+        assert_eq!("0", ORDERED_WITH_DEFAULT.get("three").to_string());
+        assert_eq!(
+            ["one", "two"],
+            *ORDERED_WITH_DEFAULT
+                .map()
+                .keys()
+                .copied()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "instrument")]
+    #[test]
+    fn instrument() {
+        assert_eq!("a", MAP[&1]);
+        assert!(!MAP.contains_key(&100));
+        let stats = MAP.stats();
+        assert!(stats.name().starts_with("phf_codegen_test::test:"));
+        // Other tests look up `MAP` concurrently.
+        assert!(
+            stats
+                .hits()
+                .iter()
+                .any(|&(&key, hits)| key == 1 && hits > 0)
+        );
+        assert!(stats.misses() > 0);
+    }
+
+    #[cfg(feature = "instrument")]
+    #[test]
+    fn instrument_ordered_and_ranges() {
+        // Other tests look up these tables concurrently.
+        assert!(ORDERED_SET.contains(&2));
+        let stats = ORDERED_SET.stats();
+        assert!(
+            stats
+                .hits()
+                .iter()
+                .any(|&(&key, hits)| key == 2 && hits > 0)
+        );
+
+        assert_eq!(Some(&3), CHAR_CLASSES.get(&'_'));
+        let stats = CHAR_CLASSES.stats();
+        assert_eq!(CHAR_CLASSES.len(), stats.hits().len());
+        assert_eq!(&'_', stats.hits()[0].0);
+        assert!(stats.hits()[0].1 > 0);
+    }
+
     #[test]
     fn quote_token_interop() {
         assert_eq!("a", TO_TOKEN_STREAM_MAP[&1]);
@@ -0,0 +1,45 @@
+//! Key lengths backing `phf::find::KeyLengths`.
+
+/// Key lengths below this are recorded in a bitset, longer ones in a list.
+pub const SMALL_LENGTHS: usize = 256;
+
+/// The distinct lengths of a map's keys, in bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyLengths {
+    /// Bit `len % 64` of word `len / 64` is set if a key is `len` bytes long,
+    /// for lengths below [`SMALL_LENGTHS`].
+    pub small: [u64; SMALL_LENGTHS / 64],
+    /// The lengths of at least [`SMALL_LENGTHS`] bytes, in ascending order.
+    pub long: Vec<usize>,
+    /// The shortest length, or 0 if there are none.
+    pub min: usize,
+    /// The longest length, or 0 if there are none.
+    pub max: usize,
+}
+
+/// Records the key lengths `lens`.
+///
+/// Empty keys are skipped, since `phf::find::FindIter` never reports them.
+pub fn key_lengths(lens: impl IntoIterator<Item = usize>) -> KeyLengths {
+    let mut lengths = KeyLengths {
+        small: [0; SMALL_LENGTHS / 64],
+        long: Vec::new(),
+        min: usize::MAX,
+        max: 0,
+    };
+    for len in lens.into_iter().filter(|&len| len > 0) {
+        if len < SMALL_LENGTHS {
+            lengths.small[len / 64] |= 1 << (len % 64);
+        } else {
+            lengths.long.push(len);
+        }
+        lengths.min = lengths.min.min(len);
+        lengths.max = lengths.max.max(len);
+    }
+    lengths.long.sort_unstable();
+    lengths.long.dedup();
+    if lengths.max == 0 {
+        lengths.min = 0;
+    }
+    lengths
+}
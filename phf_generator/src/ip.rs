@@ -0,0 +1,47 @@
+//! CIDR parsing backing `phf::IpPrefixMap`.
+use std::net::IpAddr;
+
+/// A network parsed from CIDR notation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Prefix {
+    V4 { network: u32, len: u8 },
+    V6 { network: u128, len: u8 },
+}
+
+/// Parses a network in CIDR notation, such as `10.0.0.0/8` or
+/// `2001:db8::/32`.
+///
+/// An address without a prefix length is a single-host network. The address
+/// must not have bits set beyond the prefix length.
+pub fn parse_cidr(cidr: &str) -> Result<Prefix, String> {
+    let (addr, len) = match cidr.split_once('/') {
+        Some((addr, len)) => (addr, Some(len)),
+        None => (cidr, None),
+    };
+    let addr = addr
+        .parse::<IpAddr>()
+        .map_err(|_| format!("invalid IP address `{}`", addr))?;
+    let max_len = if addr.is_ipv4() { 32 } else { 128 };
+    let len = match len {
+        Some(len) => len
+            .parse::<u8>()
+            .ok()
+            .filter(|&len| len <= max_len)
+            .ok_or_else(|| format!("invalid prefix length `{}`", len))?,
+        None => max_len,
+    };
+
+    let prefix = match addr {
+        IpAddr::V4(addr) => {
+            let network = u32::from(addr);
+            let mask = u32::MAX.checked_shl(32 - u32::from(len)).unwrap_or(0);
+            (network & !mask == 0).then_some(Prefix::V4 { network, len })
+        }
+        IpAddr::V6(addr) => {
+            let network = u128::from(addr);
+            let mask = u128::MAX.checked_shl(128 - u32::from(len)).unwrap_or(0);
+            (network & !mask == 0).then_some(Prefix::V6 { network, len })
+        }
+    };
+    prefix.ok_or_else(|| format!("`{}` has bits set beyond its prefix length", cidr))
+}
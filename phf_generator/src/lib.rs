@@ -13,8 +13,11 @@ const DEFAULT_LAMBDA: usize = 3;
 const FIXED_SEED: u64 = 1234567890;
 const EMPTY_SLOT: usize = usize::MAX;
 
+pub mod find;
+pub mod ip;
 #[cfg(feature = "ptrhash")]
 pub mod ptrhash;
+pub mod suggest;
 
 pub struct HashState {
     pub key: HashKey,
@@ -48,6 +51,59 @@ where
         .expect("failed to solve PHF")
 }
 
+/// Generates a hash state for each set of entries, all sharing a single hash
+/// key.
+///
+/// Maps built from these states can reuse one hash computation for lookups in
+/// every member of the family.
+pub fn generate_hash_family<H: PhfHash>(entries: &[&[H]]) -> Vec<HashState> {
+    generate_hash_family_with_hash_fn(entries, phf_shared::hash)
+}
+
+pub fn generate_hash_family_with_hash_fn<T, F>(entries: &[&[T]], hash_fn: F) -> Vec<HashState>
+where
+    F: Fn(&T, &HashKey) -> Hashes,
+{
+    let mut generators = entries
+        .iter()
+        .map(|entries| Generator::new(entries.len()))
+        .collect::<Vec<_>>();
+    let mut rng = Rng::with_seed(FIXED_SEED);
+
+    let key = iter::repeat_with(|| rng.u64(..))
+        .find(|key| {
+            generators
+                .iter_mut()
+                .zip(entries)
+                .all(|(generator, entries)| {
+                    let hashes = entries.iter().map(|entry| hash_fn(entry, key));
+                    generator.reset(hashes);
+
+                    generator.try_generate_hash()
+                })
+        })
+        .expect("failed to solve PHF");
+
+    generators
+        .into_iter()
+        .map(|generator| HashState {
+            key,
+            disps: generator.disps,
+            map: generator.map,
+        })
+        .collect()
+}
+
+/// Returns a fingerprint of the entry at each index of `state`, for building
+/// filters that store fingerprints instead of keys.
+pub fn generate_fingerprints<H: PhfHash>(entries: &[H], state: &HashState) -> Vec<u64> {
+    state
+        .map
+        .iter()
+        .map(|&idx| phf_shared::fingerprint(&phf_shared::hash(&entries[idx], &state.key)))
+        .collect()
+}
+
 struct Bucket {
     idx: usize,
     start: usize,
@@ -70,7 +126,7 @@ impl Generator {
     fn new(table_len: usize) -> Self {
         let hashes = Vec::with_capacity(table_len);
 
-        let buckets_len = (table_len + DEFAULT_LAMBDA - 1) / DEFAULT_LAMBDA;
+        let buckets_len = table_len.div_ceil(DEFAULT_LAMBDA);
         let buckets: Vec<_> = (0..buckets_len)
             .map(|i| Bucket {
                 idx: i,
@@ -1,7 +1,7 @@
 use core::cmp;
 
 use fastrand::Rng;
-use phf_shared::ptrhash::{fast_reduct32, hash as ptrhash_hash, hash_pilot};
+use phf_shared::ptrhash::{fast_reduct32, fingerprint, hash as ptrhash_hash, hash_pilot};
 use phf_shared::{HashKey, PhfHash};
 
 use crate::FIXED_SEED;
@@ -48,6 +48,63 @@ where
     }
 }
 
+/// Generates a hash state for each set of entries, all sharing a single seed.
+///
+/// Maps built from these states can reuse one hash computation for lookups in
+/// every member of the family.
+pub fn generate_hash_family<H: PhfHash>(entries: &[&[H]]) -> Vec<HashState> {
+    generate_hash_family_with_hash_fn(entries, ptrhash_hash)
+}
+
+pub fn generate_hash_family_with_hash_fn<T, F>(entries: &[&[T]], hash_fn: F) -> Vec<HashState>
+where
+    F: Fn(&T, &HashKey) -> u64,
+{
+    let mut rng = Rng::with_seed(FIXED_SEED);
+    let mut hashes = entries
+        .iter()
+        .map(|entries| vec![0; entries.len()])
+        .collect::<Vec<_>>();
+
+    'seed: loop {
+        let seed = rng.u64(..);
+        let mut states = Vec::with_capacity(entries.len());
+
+        for (hashes, entries) in hashes.iter_mut().zip(entries) {
+            if entries.is_empty() {
+                states.push(HashState {
+                    seed,
+                    pilots: vec![],
+                    remap: vec![],
+                    map: vec![],
+                });
+                continue;
+            }
+
+            for (hash, entry) in hashes.iter_mut().zip(*entries) {
+                *hash = hash_fn(entry, &seed);
+            }
+
+            match try_generate_hash(seed, hashes) {
+                Some(state) => states.push(state),
+                None => continue 'seed,
+            }
+        }
+
+        return states;
+    }
+}
+
+/// Returns a fingerprint of the entry at each index of `state`, for building
+/// filters that store fingerprints instead of keys.
+pub fn generate_fingerprints<H: PhfHash>(entries: &[H], state: &HashState) -> Vec<u64> {
+    state
+        .map
+        .iter()
+        .map(|&idx| fingerprint(ptrhash_hash(&entries[idx], &state.seed)))
+        .collect()
+}
+
 #[derive(Default)]
 struct Bucket {
     keys: Vec<usize>,
@@ -215,6 +272,7 @@ fn adjusted_buckets_len(keys_len: u32) -> u32 {
     len + 3
 }
 
+#[allow(clippy::too_many_arguments)]
 fn try_place_bucket(
     buckets: &[Bucket],
     slots: &mut [Option<Slot>],
@@ -0,0 +1,44 @@
+//! Deletion tables backing `phf::suggest::SuggestIndex`.
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Builds a SymSpell-style deletion table for `keys`.
+///
+/// Every string obtained by deleting up to `max_distance` characters from a
+/// key (including the key itself) is mapped to the indices of the keys that
+/// produce it. The table is sorted by deletion string so generated code is
+/// deterministic.
+pub fn generate_deletes<S: AsRef<str>>(
+    keys: &[S],
+    max_distance: usize,
+) -> Vec<(String, Vec<usize>)> {
+    let mut table = BTreeMap::<String, BTreeSet<usize>>::new();
+    for (idx, key) in keys.iter().enumerate() {
+        for delete in deletes(key.as_ref(), max_distance) {
+            table.entry(delete).or_default().insert(idx);
+        }
+    }
+    table
+        .into_iter()
+        .map(|(delete, idxs)| (delete, idxs.into_iter().collect()))
+        .collect()
+}
+
+fn deletes(s: &str, max_deletes: usize) -> BTreeSet<String> {
+    let mut all = BTreeSet::from([s.to_owned()]);
+    let mut frontier = vec![s.to_owned()];
+
+    for _ in 0..max_deletes {
+        let mut next = Vec::new();
+        for word in &frontier {
+            for (idx, c) in word.char_indices() {
+                let delete = format!("{}{}", &word[..idx], &word[idx + c.len_utf8()..]);
+                if all.insert(delete.clone()) {
+                    next.push(delete);
+                }
+            }
+        }
+        frontier = next;
+    }
+
+    all
+}
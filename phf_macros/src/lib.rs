@@ -5,15 +5,20 @@
 
 #[cfg(not(feature = "ptrhash"))]
 use phf_generator::HashState;
+use phf_generator::ip::Prefix;
 #[cfg(feature = "ptrhash")]
 use phf_generator::ptrhash::HashState;
-use phf_shared::PhfHash;
+use phf_shared::{PhfHash, TotalF32, TotalF64};
 use proc_macro::TokenStream;
-use quote::{ToTokens, quote};
-use std::collections::HashSet;
+use quote::{ToTokens, format_ident, quote};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ffi::CString;
 use std::hash::Hasher;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::time::Duration;
 use syn::punctuated::Punctuated;
-use syn::{BinOp, Error, Expr, ExprLit, Lit, Token, UnOp, parse_macro_input};
+use syn::{BinOp, Error, Expr, ExprLit, Lit, RangeLimits, Token, UnOp, parse_macro_input};
 #[cfg(feature = "uncased")]
 use uncased_::Uncased;
 #[cfg(feature = "unicase")]
@@ -22,10 +27,26 @@ use unicase_::{Ascii, UniCase};
 mod parse;
 use parse::AsMapEntry;
 
+/// Returns the value of the associated constant `name` of a float type.
+macro_rules! float_const {
+    ($float:ident, $name:expr) => {
+        match $name {
+            "INFINITY" => Some($float::INFINITY),
+            "NEG_INFINITY" => Some($float::NEG_INFINITY),
+            "NAN" => Some($float::NAN),
+            "MAX" => Some($float::MAX),
+            "MIN" => Some($float::MIN),
+            "MIN_POSITIVE" => Some($float::MIN_POSITIVE),
+            "EPSILON" => Some($float::EPSILON),
+            _ => None,
+        }
+    };
+}
 #[derive(Hash, PartialEq, Eq, Clone)]
 enum ParsedKey {
     Str(String),
     Binary(Vec<u8>),
+    CStr(CString),
     Char(char),
     I8(i8),
     I16(i16),
@@ -40,13 +61,37 @@ enum ParsedKey {
     U128(u128),
     Usize(usize),
     Bool(bool),
+    // Bit patterns, so that keys compare like `TotalF32` and `TotalF64`.
+    F32(u32),
+    F64(u64),
     Tuple(Vec<ParsedKey>),
+    Option(Option<Box<ParsedKey>>),
+    Result(Result<Box<ParsedKey>, Box<ParsedKey>>),
+    Ordering(Ordering),
+    NonZero(Box<ParsedKey>),
+    Ipv4Addr(Ipv4Addr),
+    Ipv6Addr(Ipv6Addr),
+    IpAddr(IpAddr),
+    SocketAddrV4(SocketAddrV4),
+    SocketAddrV6(SocketAddrV6),
+    SocketAddr(SocketAddr),
+    Duration(Duration),
+    // A lowercased `Str` or `Binary` key.
+    AsciiNoCase(Box<ParsedKey>),
+    // The names of the normalizers, and the normalized string.
+    Normalized(Vec<String>, String),
     #[cfg(feature = "unicase")]
     UniCase(UniCase<String>),
     #[cfg(feature = "unicase")]
     UniCaseAscii(Ascii<String>),
     #[cfg(feature = "uncased")]
     Uncased(Uncased<'static>),
+    #[cfg(feature = "smol_str")]
+    SmolStr(String),
+    #[cfg(feature = "compact_str")]
+    CompactString(String),
+    #[cfg(feature = "uuid")]
+    Uuid(u128),
 }
 
 impl PhfHash for ParsedKey {
@@ -57,6 +102,7 @@ impl PhfHash for ParsedKey {
         match self {
             ParsedKey::Str(s) => s.phf_hash(state),
             ParsedKey::Binary(s) => s.phf_hash(state),
+            ParsedKey::CStr(s) => s.as_c_str().phf_hash(state),
             ParsedKey::Char(s) => s.phf_hash(state),
             ParsedKey::I8(s) => s.phf_hash(state),
             ParsedKey::I16(s) => s.phf_hash(state),
@@ -71,17 +117,38 @@ impl PhfHash for ParsedKey {
             ParsedKey::U128(s) => s.phf_hash(state),
             ParsedKey::Usize(s) => s.phf_hash(state),
             ParsedKey::Bool(s) => s.phf_hash(state),
+            ParsedKey::F32(s) => TotalF32::from_bits(*s).phf_hash(state),
+            ParsedKey::F64(s) => TotalF64::from_bits(*s).phf_hash(state),
             ParsedKey::Tuple(elements) => {
                 for element in elements {
                     element.phf_hash(state);
                 }
             }
+            ParsedKey::Option(s) => s.as_deref().phf_hash(state),
+            ParsedKey::Result(s) => s.as_deref().map_err(|e| &**e).phf_hash(state),
+            ParsedKey::Ordering(s) => s.phf_hash(state),
+            ParsedKey::NonZero(s) => s.phf_hash(state),
+            ParsedKey::Ipv4Addr(s) => s.phf_hash(state),
+            ParsedKey::Ipv6Addr(s) => s.phf_hash(state),
+            ParsedKey::IpAddr(s) => s.phf_hash(state),
+            ParsedKey::SocketAddrV4(s) => s.phf_hash(state),
+            ParsedKey::SocketAddrV6(s) => s.phf_hash(state),
+            ParsedKey::SocketAddr(s) => s.phf_hash(state),
+            ParsedKey::Duration(s) => s.phf_hash(state),
+            ParsedKey::AsciiNoCase(s) => s.phf_hash(state),
+            ParsedKey::Normalized(_, s) => s.phf_hash(state),
             #[cfg(feature = "unicase")]
             ParsedKey::UniCase(s) => s.phf_hash(state),
             #[cfg(feature = "unicase")]
             ParsedKey::UniCaseAscii(s) => s.phf_hash(state),
             #[cfg(feature = "uncased")]
             ParsedKey::Uncased(s) => s.phf_hash(state),
+            #[cfg(feature = "smol_str")]
+            ParsedKey::SmolStr(s) => s.phf_hash(state),
+            #[cfg(feature = "compact_str")]
+            ParsedKey::CompactString(s) => s.phf_hash(state),
+            #[cfg(feature = "uuid")]
+            ParsedKey::Uuid(s) => s.to_be_bytes().phf_hash(state),
         }
     }
 }
@@ -91,6 +158,7 @@ impl ParsedKey {
         match (self, other) {
             (ParsedKey::Str(_), ParsedKey::Str(_))
             | (ParsedKey::Binary(_), ParsedKey::Binary(_))
+            | (ParsedKey::CStr(_), ParsedKey::CStr(_))
             | (ParsedKey::Char(_), ParsedKey::Char(_))
             | (ParsedKey::I8(_), ParsedKey::I8(_))
             | (ParsedKey::I16(_), ParsedKey::I16(_))
@@ -104,7 +172,29 @@ impl ParsedKey {
             | (ParsedKey::U64(_), ParsedKey::U64(_))
             | (ParsedKey::U128(_), ParsedKey::U128(_))
             | (ParsedKey::Usize(_), ParsedKey::Usize(_))
-            | (ParsedKey::Bool(_), ParsedKey::Bool(_)) => true,
+            | (ParsedKey::Bool(_), ParsedKey::Bool(_))
+            | (ParsedKey::F32(_), ParsedKey::F32(_))
+            | (ParsedKey::F64(_), ParsedKey::F64(_))
+            | (ParsedKey::Ordering(_), ParsedKey::Ordering(_))
+            | (ParsedKey::Ipv4Addr(_), ParsedKey::Ipv4Addr(_))
+            | (ParsedKey::Ipv6Addr(_), ParsedKey::Ipv6Addr(_))
+            | (ParsedKey::IpAddr(_), ParsedKey::IpAddr(_))
+            | (ParsedKey::SocketAddrV4(_), ParsedKey::SocketAddrV4(_))
+            | (ParsedKey::SocketAddrV6(_), ParsedKey::SocketAddrV6(_))
+            | (ParsedKey::SocketAddr(_), ParsedKey::SocketAddr(_))
+            | (ParsedKey::Duration(_), ParsedKey::Duration(_)) => true,
+            (ParsedKey::Option(Some(left)), ParsedKey::Option(Some(right)))
+            | (ParsedKey::Result(Ok(left)), ParsedKey::Result(Ok(right)))
+            | (ParsedKey::Result(Err(left)), ParsedKey::Result(Err(right)))
+            | (ParsedKey::NonZero(left), ParsedKey::NonZero(right))
+            | (ParsedKey::AsciiNoCase(left), ParsedKey::AsciiNoCase(right)) => {
+                left.has_same_type_as(right)
+            }
+            // `None` and the other variant of a `Result` don't constrain the
+            // contained types.
+            (ParsedKey::Option(_), ParsedKey::Option(_))
+            | (ParsedKey::Result(_), ParsedKey::Result(_)) => true,
+            (ParsedKey::Normalized(left, _), ParsedKey::Normalized(right, _)) => left == right,
             (ParsedKey::Tuple(left), ParsedKey::Tuple(right)) => {
                 left.len() == right.len()
                     && left
@@ -118,10 +208,51 @@ impl ParsedKey {
             (ParsedKey::UniCaseAscii(_), ParsedKey::UniCaseAscii(_)) => true,
             #[cfg(feature = "uncased")]
             (ParsedKey::Uncased(_), ParsedKey::Uncased(_)) => true,
+            #[cfg(feature = "smol_str")]
+            (ParsedKey::SmolStr(_), ParsedKey::SmolStr(_)) => true,
+            #[cfg(feature = "compact_str")]
+            (ParsedKey::CompactString(_), ParsedKey::CompactString(_)) => true,
+            #[cfg(feature = "uuid")]
+            (ParsedKey::Uuid(_), ParsedKey::Uuid(_)) => true,
             _ => false,
         }
     }
 
+    /// Returns whether an integer key is zero, or `None` for other keys.
+    fn is_zero(&self) -> Option<bool> {
+        match *self {
+            ParsedKey::I8(v) => Some(v == 0),
+            ParsedKey::I16(v) => Some(v == 0),
+            ParsedKey::I32(v) => Some(v == 0),
+            ParsedKey::I64(v) => Some(v == 0),
+            ParsedKey::I128(v) => Some(v == 0),
+            ParsedKey::Isize(v) => Some(v == 0),
+            ParsedKey::U8(v) => Some(v == 0),
+            ParsedKey::U16(v) => Some(v == 0),
+            ParsedKey::U32(v) => Some(v == 0),
+            ParsedKey::U64(v) => Some(v == 0),
+            ParsedKey::U128(v) => Some(v == 0),
+            ParsedKey::Usize(v) => Some(v == 0),
+            _ => None,
+        }
+    }
+
+    fn from_float(s: &syn::LitFloat, hint: Option<&ParsedKey>) -> syn::Result<ParsedKey> {
+        match (s.suffix(), hint) {
+            ("f32", _) | ("", Some(ParsedKey::F32(_))) => {
+                Ok(ParsedKey::F32(s.base10_parse::<f32>()?.to_bits()))
+            }
+            ("f64", _) | ("", Some(ParsedKey::F64(_))) => {
+                Ok(ParsedKey::F64(s.base10_parse::<f64>()?.to_bits()))
+            }
+            ("", _) => Err(Error::new_spanned(
+                s,
+                "float key literals in the first key must have an explicit type suffix",
+            )),
+            _ => Err(Error::new_spanned(s, "unsupported float literal suffix")),
+        }
+    }
+
     fn from_unsuffixed_int(s: &syn::LitInt, hint: Option<&ParsedKey>) -> syn::Result<ParsedKey> {
         match hint {
             Some(ParsedKey::I8(_)) => Ok(ParsedKey::I8(s.base10_parse::<u8>().unwrap() as i8)),
@@ -156,6 +287,7 @@ impl ParsedKey {
             Expr::Lit(lit) => match &lit.lit {
                 Lit::Str(s) => Ok(ParsedKey::Str(s.value())),
                 Lit::ByteStr(s) => Ok(ParsedKey::Binary(s.value())),
+                Lit::CStr(s) => Ok(ParsedKey::CStr(s.value())),
                 Lit::Byte(s) => Ok(ParsedKey::U8(s.value())),
                 Lit::Char(s) => Ok(ParsedKey::Char(s.value())),
                 Lit::Int(s) => match s.suffix() {
@@ -178,6 +310,7 @@ impl ParsedKey {
                     _ => Err(Error::new_spanned(s, "unsupported integer literal suffix")),
                 },
                 Lit::Bool(s) => Ok(ParsedKey::Bool(s.value)),
+                Lit::Float(s) => ParsedKey::from_float(s, hint),
                 _ => Err(Error::new_spanned(expr, "unsupported key expression")),
             },
             Expr::Array(array) => {
@@ -233,6 +366,8 @@ impl ParsedKey {
                         ParsedKey::I64(v) => Ok(ParsedKey::I64(try_negate!(v))),
                         ParsedKey::I128(v) => Ok(ParsedKey::I128(try_negate!(v))),
                         ParsedKey::Isize(v) => Ok(ParsedKey::Isize(try_negate!(v))),
+                        ParsedKey::F32(v) => Ok(ParsedKey::F32(v ^ (1 << 31))),
+                        ParsedKey::F64(v) => Ok(ParsedKey::F64(v ^ (1 << 63))),
                         _ => Err(Error::new_spanned(expr, "unsupported key expression")),
                     },
                     UnOp::Deref(_) => {
@@ -263,51 +398,246 @@ impl ParsedKey {
                 Ok(ParsedKey::Tuple(elements))
             }
             Expr::Group(group) => ParsedKey::from_expr(&group.expr, hint),
-            Expr::Call(call) if call.args.len() == 1 => {
-                let last;
-                let last_ahead;
-
-                if let Expr::Path(ep) = call.func.as_ref() {
-                    let mut segments = ep.path.segments.iter();
-                    last = segments
-                        .next_back()
-                        .ok_or_else(|| Error::new_spanned(expr, "unsupported key expression"))?
-                        .ident
-                        .to_string();
-                    last_ahead = segments
-                        .next_back()
-                        .ok_or_else(|| Error::new_spanned(expr, "unsupported key expression"))?
-                        .ident
-                        .to_string();
-                } else {
+            Expr::Path(path) => {
+                let (prev, last) = path_tail(&path.path);
+                match (prev.as_deref(), &*last) {
+                    (None | Some("Option"), "None") => Ok(ParsedKey::Option(None)),
+                    (Some("Ordering"), "Less") => Ok(ParsedKey::Ordering(Ordering::Less)),
+                    (Some("Ordering"), "Equal") => Ok(ParsedKey::Ordering(Ordering::Equal)),
+                    (Some("Ordering"), "Greater") => Ok(ParsedKey::Ordering(Ordering::Greater)),
+                    (Some("Ipv4Addr"), "LOCALHOST") => Ok(ParsedKey::Ipv4Addr(Ipv4Addr::LOCALHOST)),
+                    (Some("Ipv4Addr"), "UNSPECIFIED") => {
+                        Ok(ParsedKey::Ipv4Addr(Ipv4Addr::UNSPECIFIED))
+                    }
+                    (Some("Ipv4Addr"), "BROADCAST") => Ok(ParsedKey::Ipv4Addr(Ipv4Addr::BROADCAST)),
+                    (Some("Ipv6Addr"), "LOCALHOST") => Ok(ParsedKey::Ipv6Addr(Ipv6Addr::LOCALHOST)),
+                    (Some("Ipv6Addr"), "UNSPECIFIED") => {
+                        Ok(ParsedKey::Ipv6Addr(Ipv6Addr::UNSPECIFIED))
+                    }
+                    (Some("f32"), name) => float_const!(f32, name)
+                        .map(|v| ParsedKey::F32(v.to_bits()))
+                        .ok_or_else(|| Error::new_spanned(expr, "unsupported key expression")),
+                    (Some("f64"), name) => float_const!(f64, name)
+                        .map(|v| ParsedKey::F64(v.to_bits()))
+                        .ok_or_else(|| Error::new_spanned(expr, "unsupported key expression")),
+                    (Some("Duration"), "ZERO") => Ok(ParsedKey::Duration(Duration::ZERO)),
+                    (Some("Duration"), "MAX") => Ok(ParsedKey::Duration(Duration::MAX)),
+                    _ => Err(Error::new_spanned(expr, "unsupported key expression")),
+                }
+            }
+            Expr::MethodCall(call) if call.method == "unwrap" && call.args.is_empty() => {
+                // `NonZeroU32::new(1).unwrap()` or `NonZero::new(1u32).unwrap()`
+                let receiver = ungroup(&call.receiver);
+                let Expr::Call(new) = receiver else {
+                    return Err(Error::new_spanned(expr, "unsupported key expression"));
+                };
+                let Expr::Path(func) = &*new.func else {
+                    return Err(Error::new_spanned(expr, "unsupported key expression"));
+                };
+                let (prev, last) = path_tail(&func.path);
+                let inner_hint =
+                    match prev.as_deref() {
+                        Some("NonZero") => match hint {
+                            Some(ParsedKey::NonZero(inner)) => Some((**inner).clone()),
+                            _ => None,
+                        },
+                        Some(name) => Some(nonzero_hint(name).ok_or_else(|| {
+                            Error::new_spanned(expr, "unsupported key expression")
+                        })?),
+                        None => return Err(Error::new_spanned(expr, "unsupported key expression")),
+                    };
+                if last != "new" || new.args.len() != 1 {
                     return Err(Error::new_spanned(expr, "unsupported key expression"));
                 }
-
-                let mut arg = call.args.first().unwrap();
-
-                while let Expr::Group(group) = arg {
-                    arg = &group.expr;
+                let inner = ParsedKey::from_expr(&new.args[0], inner_hint.as_ref())?;
+                if prev.as_deref() != Some("NonZero")
+                    && !inner_hint.is_some_and(|hint| inner.has_same_type_as(&hint))
+                {
+                    return Err(Error::new_spanned(
+                        &new.args[0],
+                        "integer type does not match the `NonZero` type",
+                    ));
+                }
+                match inner.is_zero() {
+                    Some(false) => Ok(ParsedKey::NonZero(Box::new(inner))),
+                    Some(true) => Err(Error::new_spanned(
+                        &new.args[0],
+                        "`NonZero` keys can't be zero",
+                    )),
+                    None => Err(Error::new_spanned(
+                        &new.args[0],
+                        "unsupported key expression",
+                    )),
                 }
+            }
+            Expr::Call(call) => {
+                let Expr::Path(func) = call.func.as_ref() else {
+                    return Err(Error::new_spanned(expr, "unsupported key expression"));
+                };
+                let (prev, last) = path_tail(&func.path);
+                let args = call.args.iter().collect::<Vec<_>>();
 
-                let _value = match arg {
-                    Expr::Lit(ExprLit {
-                        attrs: _,
-                        lit: Lit::Str(s),
-                    }) => s.value(),
-                    _ => {
-                        return Err(Error::new_spanned(expr, "unsupported key expression"));
+                match (prev.as_deref(), &*last, &*args) {
+                    (None | Some("Option"), "Some", [arg]) => {
+                        let hint = match hint {
+                            Some(ParsedKey::Option(Some(hint))) => Some(&**hint),
+                            _ => None,
+                        };
+                        Ok(ParsedKey::Option(Some(Box::new(ParsedKey::from_expr(
+                            arg, hint,
+                        )?))))
                     }
-                };
+                    (None | Some("Result"), "Ok", [arg]) => {
+                        let hint = match hint {
+                            Some(ParsedKey::Result(Ok(hint))) => Some(&**hint),
+                            _ => None,
+                        };
+                        Ok(ParsedKey::Result(Ok(Box::new(ParsedKey::from_expr(
+                            arg, hint,
+                        )?))))
+                    }
+                    (None | Some("Result"), "Err", [arg]) => {
+                        let hint = match hint {
+                            Some(ParsedKey::Result(Err(hint))) => Some(&**hint),
+                            _ => None,
+                        };
+                        Ok(ParsedKey::Result(Err(Box::new(ParsedKey::from_expr(
+                            arg, hint,
+                        )?))))
+                    }
+                    (Some("Ipv4Addr"), "new", [a, b, c, d]) => Ok(ParsedKey::Ipv4Addr(
+                        Ipv4Addr::new(int_arg(a)?, int_arg(b)?, int_arg(c)?, int_arg(d)?),
+                    )),
+                    (Some("Ipv6Addr"), "new", [a, b, c, d, e, f, g, h]) => {
+                        Ok(ParsedKey::Ipv6Addr(Ipv6Addr::new(
+                            int_arg(a)?,
+                            int_arg(b)?,
+                            int_arg(c)?,
+                            int_arg(d)?,
+                            int_arg(e)?,
+                            int_arg(f)?,
+                            int_arg(g)?,
+                            int_arg(h)?,
+                        )))
+                    }
+                    (Some("IpAddr"), "V4", [arg]) => {
+                        Ok(ParsedKey::IpAddr(IpAddr::V4(ipv4_arg(arg)?)))
+                    }
+                    (Some("IpAddr"), "V6", [arg]) => {
+                        Ok(ParsedKey::IpAddr(IpAddr::V6(ipv6_arg(arg)?)))
+                    }
+                    (Some("SocketAddrV4"), "new", [ip, port]) => Ok(ParsedKey::SocketAddrV4(
+                        SocketAddrV4::new(ipv4_arg(ip)?, int_arg(port)?),
+                    )),
+                    (Some("SocketAddrV6"), "new", [ip, port, flowinfo, scope_id]) => {
+                        Ok(ParsedKey::SocketAddrV6(SocketAddrV6::new(
+                            ipv6_arg(ip)?,
+                            int_arg(port)?,
+                            int_arg(flowinfo)?,
+                            int_arg(scope_id)?,
+                        )))
+                    }
+                    (Some("SocketAddr"), "V4", [arg]) => match ParsedKey::from_expr(arg, None)? {
+                        ParsedKey::SocketAddrV4(addr) => Ok(ParsedKey::SocketAddr(addr.into())),
+                        _ => Err(Error::new_spanned(arg, "expected a `SocketAddrV4`")),
+                    },
+                    (Some("SocketAddr"), "V6", [arg]) => match ParsedKey::from_expr(arg, None)? {
+                        ParsedKey::SocketAddrV6(addr) => Ok(ParsedKey::SocketAddr(addr.into())),
+                        _ => Err(Error::new_spanned(arg, "expected a `SocketAddrV6`")),
+                    },
+                    (_, "TotalF32", [arg]) | (Some("TotalF32"), "new", [arg]) => {
+                        match ParsedKey::from_expr(arg, Some(&ParsedKey::F32(0)))? {
+                            key @ ParsedKey::F32(_) => Ok(key),
+                            _ => Err(Error::new_spanned(arg, "expected an `f32` literal")),
+                        }
+                    }
+                    (_, "TotalF64", [arg]) | (Some("TotalF64"), "new", [arg]) => {
+                        match ParsedKey::from_expr(arg, Some(&ParsedKey::F64(0)))? {
+                            key @ ParsedKey::F64(_) => Ok(key),
+                            _ => Err(Error::new_spanned(arg, "expected an `f64` literal")),
+                        }
+                    }
+                    (Some("TotalF32"), "from_bits", [arg]) => Ok(ParsedKey::F32(int_arg(arg)?)),
+                    (Some("TotalF64"), "from_bits", [arg]) => Ok(ParsedKey::F64(int_arg(arg)?)),
+                    (_, "AsciiNoCase", [arg]) | (Some("AsciiNoCase"), "new", [arg]) => {
+                        match ParsedKey::from_expr(arg, None)? {
+                            ParsedKey::Str(s) => Ok(ParsedKey::Str(s.to_ascii_lowercase())),
+                            ParsedKey::Binary(s) => Ok(ParsedKey::Binary(s.to_ascii_lowercase())),
+                            _ => Err(Error::new_spanned(arg, "expected a string literal")),
+                        }
+                        .map(|key| ParsedKey::AsciiNoCase(Box::new(key)))
+                    }
+                    (Some("Normalized"), "new", [arg]) => normalized_key(&func.path, arg),
+                    #[cfg(feature = "smol_str")]
+                    (Some("SmolStr"), "new_static" | "new_inline", [arg]) => {
+                        match ParsedKey::from_expr(arg, None)? {
+                            ParsedKey::Str(s) => Ok(ParsedKey::SmolStr(s)),
+                            _ => Err(Error::new_spanned(arg, "expected a string literal")),
+                        }
+                    }
+                    #[cfg(feature = "compact_str")]
+                    (Some("CompactString"), "const_new", [arg]) => {
+                        match ParsedKey::from_expr(arg, None)? {
+                            ParsedKey::Str(s) => Ok(ParsedKey::CompactString(s)),
+                            _ => Err(Error::new_spanned(arg, "expected a string literal")),
+                        }
+                    }
+                    #[cfg(feature = "uuid")]
+                    (Some("Uuid"), "from_u128", [arg]) => Ok(ParsedKey::Uuid(int_arg(arg)?)),
+                    #[cfg(feature = "uuid")]
+                    (Some("Uuid"), "from_bytes", [arg]) => {
+                        match ParsedKey::from_expr(arg, Some(&ParsedKey::Binary(Vec::new())))? {
+                            ParsedKey::Binary(s) => s
+                                .try_into()
+                                .map(|bytes| ParsedKey::Uuid(u128::from_be_bytes(bytes)))
+                                .map_err(|_| Error::new_spanned(arg, "expected 16 bytes")),
+                            _ => Err(Error::new_spanned(arg, "expected an array of 16 bytes")),
+                        }
+                    }
+                    (Some("Duration"), "new", [secs, nanos]) => {
+                        let nanos: u32 = int_arg(nanos)?;
+                        let secs: u64 = int_arg(secs)?;
+                        secs.checked_add(u64::from(nanos / 1_000_000_000))
+                            .map(|_| ParsedKey::Duration(Duration::new(secs, nanos)))
+                            .ok_or_else(|| Error::new_spanned(expr, "overflow in `Duration::new`"))
+                    }
+                    (Some("Duration"), "from_secs", [arg]) => {
+                        Ok(ParsedKey::Duration(Duration::from_secs(int_arg(arg)?)))
+                    }
+                    (Some("Duration"), "from_millis", [arg]) => {
+                        Ok(ParsedKey::Duration(Duration::from_millis(int_arg(arg)?)))
+                    }
+                    (Some("Duration"), "from_micros", [arg]) => {
+                        Ok(ParsedKey::Duration(Duration::from_micros(int_arg(arg)?)))
+                    }
+                    (Some("Duration"), "from_nanos", [arg]) => {
+                        Ok(ParsedKey::Duration(Duration::from_nanos(int_arg(arg)?)))
+                    }
+                    (Some(_prev), _, [arg]) => {
+                        let _value = match ungroup(arg) {
+                            Expr::Lit(ExprLit {
+                                attrs: _,
+                                lit: Lit::Str(s),
+                            }) => s.value(),
+                            _ => {
+                                return Err(Error::new_spanned(expr, "unsupported key expression"));
+                            }
+                        };
 
-                match (&*last_ahead, &*last) {
-                    #[cfg(feature = "unicase")]
-                    ("UniCase", "unicode") => Ok(ParsedKey::UniCase(UniCase::unicode(_value))),
-                    #[cfg(feature = "unicase")]
-                    ("UniCase", "ascii") => Ok(ParsedKey::UniCase(UniCase::ascii(_value))),
-                    #[cfg(feature = "unicase")]
-                    ("Ascii", "new") => Ok(ParsedKey::UniCaseAscii(Ascii::new(_value))),
-                    #[cfg(feature = "uncased")]
-                    ("UncasedStr", "new") => Ok(ParsedKey::Uncased(Uncased::new(_value))),
+                        match (_prev, &*last) {
+                            #[cfg(feature = "unicase")]
+                            ("UniCase", "unicode") => {
+                                Ok(ParsedKey::UniCase(UniCase::unicode(_value)))
+                            }
+                            #[cfg(feature = "unicase")]
+                            ("UniCase", "ascii") => Ok(ParsedKey::UniCase(UniCase::ascii(_value))),
+                            #[cfg(feature = "unicase")]
+                            ("Ascii", "new") => Ok(ParsedKey::UniCaseAscii(Ascii::new(_value))),
+                            #[cfg(feature = "uncased")]
+                            ("UncasedStr", "new") => Ok(ParsedKey::Uncased(Uncased::new(_value))),
+                            _ => Err(Error::new_spanned(expr, "unsupported key expression")),
+                        }
+                    }
                     _ => Err(Error::new_spanned(expr, "unsupported key expression")),
                 }
             }
@@ -316,6 +646,166 @@ impl ParsedKey {
     }
 }
 
+/// Wraps bare float keys, which aren't `Eq`, in `TotalF32` or `TotalF64`.
+fn wrap_float_literals(expr: &Expr, key: &ParsedKey) -> Expr {
+    // Anything but a float literal or constant is a `TotalF32` or `TotalF64`
+    // constructor.
+    let is_bare = !matches!(ungroup(expr), Expr::Call(_));
+    match key {
+        ParsedKey::F32(_) if is_bare => syn::parse_quote!(phf::TotalF32(#expr)),
+        ParsedKey::F64(_) if is_bare => syn::parse_quote!(phf::TotalF64(#expr)),
+        ParsedKey::Tuple(keys) => match ungroup(expr) {
+            Expr::Tuple(tuple) => {
+                let mut tuple = tuple.clone();
+                for (elem, key) in tuple.elems.iter_mut().zip(keys) {
+                    *elem = wrap_float_literals(elem, key);
+                }
+                Expr::Tuple(tuple)
+            }
+            _ => expr.clone(),
+        },
+        ParsedKey::Option(Some(key)) | ParsedKey::Result(Ok(key)) | ParsedKey::Result(Err(key)) => {
+            match ungroup(expr) {
+                Expr::Call(call) if call.args.len() == 1 => {
+                    let mut call = call.clone();
+                    call.args[0] = wrap_float_literals(&call.args[0], key);
+                    Expr::Call(call)
+                }
+                _ => expr.clone(),
+            }
+        }
+        _ => expr.clone(),
+    }
+}
+
+fn ungroup(mut expr: &Expr) -> &Expr {
+    while let Expr::Group(group) = expr {
+        expr = &group.expr;
+    }
+    expr
+}
+
+/// Returns the last segment of `path` and the one before it, if any.
+fn path_tail(path: &syn::Path) -> (Option<String>, String) {
+    let mut segments = path.segments.iter().rev().map(|s| s.ident.to_string());
+    let last = segments.next().unwrap_or_default();
+    (segments.next(), last)
+}
+
+/// Returns a key of the integer type wrapped by the `NonZero` type `name`.
+fn nonzero_hint(name: &str) -> Option<ParsedKey> {
+    Some(match name {
+        "NonZeroI8" => ParsedKey::I8(0),
+        "NonZeroI16" => ParsedKey::I16(0),
+        "NonZeroI32" => ParsedKey::I32(0),
+        "NonZeroI64" => ParsedKey::I64(0),
+        "NonZeroI128" => ParsedKey::I128(0),
+        "NonZeroIsize" => ParsedKey::Isize(0),
+        "NonZeroU8" => ParsedKey::U8(0),
+        "NonZeroU16" => ParsedKey::U16(0),
+        "NonZeroU32" => ParsedKey::U32(0),
+        "NonZeroU64" => ParsedKey::U64(0),
+        "NonZeroU128" => ParsedKey::U128(0),
+        "NonZeroUsize" => ParsedKey::Usize(0),
+        _ => return None,
+    })
+}
+
+/// Parses an integer literal argument of a constructor.
+fn int_arg<T>(expr: &Expr) -> syn::Result<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    match ungroup(expr) {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(s), ..
+        }) => s.base10_parse(),
+        expr => Err(Error::new_spanned(expr, "expected an integer literal")),
+    }
+}
+
+/// Parses the key of `Normalized::<N, _>::new(string)`, normalizing it with
+/// the built-in normalizers `N` names.
+fn normalized_key(func: &syn::Path, arg: &Expr) -> syn::Result<ParsedKey> {
+    let segments = &func.segments;
+    let normalizer = match &segments[segments.len() - 2].arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(syn::GenericArgument::Type(ty)) => ty,
+            _ => return Err(Error::new_spanned(args, "expected a normalizer type")),
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                func,
+                "expected `Normalized::<normalizer, _>::new`",
+            ));
+        }
+    };
+    let mut names = Vec::new();
+    normalizer_names(normalizer, &mut names)?;
+    let key = match ParsedKey::from_expr(arg, None)? {
+        ParsedKey::Str(key) => key,
+        _ => return Err(Error::new_spanned(arg, "expected a string literal")),
+    };
+    let key = names.iter().fold(key, |key, name| normalize(name, &key));
+    Ok(ParsedKey::Normalized(names, key))
+}
+
+/// Collects the names of the normalizers in `ty`, a normalizer or a tuple of
+/// them.
+fn normalizer_names(ty: &syn::Type, names: &mut Vec<String>) -> syn::Result<()> {
+    match ty {
+        syn::Type::Tuple(tuple) => {
+            for elem in &tuple.elems {
+                normalizer_names(elem, names)?;
+            }
+        }
+        syn::Type::Paren(paren) => normalizer_names(&paren.elem, names)?,
+        syn::Type::Group(group) => normalizer_names(&group.elem, names)?,
+        syn::Type::Path(path) if path.qself.is_none() => {
+            let (_, name) = path_tail(&path.path);
+            match &*name {
+                "Trim" | "Lowercase" | "AsciiLowercase" | "DashUnderscore" => names.push(name),
+                #[cfg(feature = "unicode-normalization")]
+                "Nfc" => names.push(name),
+                _ => return Err(Error::new_spanned(ty, "unsupported normalizer")),
+            }
+        }
+        _ => return Err(Error::new_spanned(ty, "unsupported normalizer")),
+    }
+    Ok(())
+}
+
+/// Applies the built-in normalizer `name` to `key`.
+fn normalize(name: &str, key: &str) -> String {
+    use phf_shared::normalize::*;
+
+    let chars = key.chars();
+    match name {
+        "Trim" => Trim::normalize(chars).collect(),
+        "Lowercase" => Lowercase::normalize(chars).collect(),
+        "AsciiLowercase" => AsciiLowercase::normalize(chars).collect(),
+        "DashUnderscore" => DashUnderscore::normalize(chars).collect(),
+        #[cfg(feature = "unicode-normalization")]
+        "Nfc" => Nfc::normalize(chars).collect(),
+        _ => unreachable!("unknown normalizer `{name}`"),
+    }
+}
+
+fn ipv4_arg(expr: &Expr) -> syn::Result<Ipv4Addr> {
+    match ParsedKey::from_expr(expr, None)? {
+        ParsedKey::Ipv4Addr(addr) => Ok(addr),
+        _ => Err(Error::new_spanned(expr, "expected an `Ipv4Addr`")),
+    }
+}
+
+fn ipv6_arg(expr: &Expr) -> syn::Result<Ipv6Addr> {
+    match ParsedKey::from_expr(expr, None)? {
+        ParsedKey::Ipv6Addr(addr) => Ok(addr),
+        _ => Err(Error::new_spanned(expr, "expected an `Ipv6Addr`")),
+    }
+}
+
 fn generate_hash_state<H: PhfHash>(entries: &[H]) -> HashState {
     #[cfg(not(feature = "ptrhash"))]
     {
@@ -328,6 +818,30 @@ fn generate_hash_state<H: PhfHash>(entries: &[H]) -> HashState {
     }
 }
 
+fn generate_hash_family<H: PhfHash>(entries: &[&[H]]) -> Vec<HashState> {
+    #[cfg(not(feature = "ptrhash"))]
+    {
+        phf_generator::generate_hash_family(entries)
+    }
+
+    #[cfg(feature = "ptrhash")]
+    {
+        phf_generator::ptrhash::generate_hash_family(entries)
+    }
+}
+
+fn generate_fingerprints<H: PhfHash>(entries: &[H], state: &HashState) -> Vec<u64> {
+    #[cfg(not(feature = "ptrhash"))]
+    {
+        phf_generator::generate_fingerprints(entries, state)
+    }
+
+    #[cfg(feature = "ptrhash")]
+    {
+        phf_generator::ptrhash::generate_fingerprints(entries, state)
+    }
+}
+
 #[derive(Clone)]
 struct Entry {
     parsed_key: ParsedKey,
@@ -385,8 +899,8 @@ impl Map {
             self.key_hint = Some(parsed_key.clone());
         }
         self.entries.push(Entry {
+            key_expr: wrap_float_literals(key, &parsed_key),
             parsed_key,
-            key_expr: key.clone(),
             value_expr: value.clone(),
         });
         Ok(())
@@ -408,8 +922,11 @@ fn key_has_cfg_attr(key: &parse::Key) -> bool {
 }
 
 fn build_map(entries: &[Entry], state: HashState) -> proc_macro2::TokenStream {
+    let counters = build_counters(entries.len());
+
     #[cfg(not(feature = "ptrhash"))]
     {
+        let format = phf_shared::TABLE_FORMAT;
         let key = state.key;
         let disps = state.disps.iter().map(|&(d1, d2)| quote!((#d1, #d2)));
         let entries = state.map.iter().map(|&idx| {
@@ -420,16 +937,13 @@ fn build_map(entries: &[Entry], state: HashState) -> proc_macro2::TokenStream {
         });
 
         quote! {
-            phf::Map {
-                key: #key,
-                disps: &[#(#disps),*],
-                entries: &[#(#entries),*],
-            }
+            phf::__map!(#format, #key, [#(#disps),*], [#(#entries),*] #counters)
         }
     }
 
     #[cfg(feature = "ptrhash")]
     {
+        let format = phf_shared::TABLE_FORMAT;
         let key = state.seed;
         let pilots = state.pilots.iter().map(|pilot| quote!(#pilot));
         let remap = state.remap.iter().map(|index| quote!(#index));
@@ -441,19 +955,64 @@ fn build_map(entries: &[Entry], state: HashState) -> proc_macro2::TokenStream {
         });
 
         quote! {
-            phf::Map {
-                key: #key,
-                pilots: &[#(#pilots),*],
-                remap: &[#(#remap),*],
-                entries: &[#(#entries),*],
+            phf::__map!(
+                #format,
+                #key,
+                [#(#pilots),*],
+                [#(#remap),*],
+                [#(#entries),*]
+                #counters
+            )
+        }
+    }
+}
+
+/// Returns the trailing `counters` argument of `phf::__map!` and similar
+/// macros for a table counting lookups of `len` entries, or nothing without the
+/// `instrument` feature.
+///
+/// The counters live in their own statics rather than in the map, so that maps
+/// can still be stored in promoted constants, as `phf::IpPrefixMap` does.
+fn build_counters(len: usize) -> proc_macro2::TokenStream {
+    if cfg!(feature = "instrument") {
+        // Clippy flags `[const { .. }; 0]` in case the initializer has side
+        // effects.
+        let hits = if len == 0 {
+            quote!([])
+        } else {
+            quote!([const { ::std::sync::atomic::AtomicU64::new(0) }; #len])
+        };
+        quote! {
+            , {
+                static HITS: [::std::sync::atomic::AtomicU64; #len] = #hits;
+                static COUNTERS: phf::instrument::Counters = phf::instrument::Counters::new(
+                    ::core::concat!(::core::module_path!(), ":", ::core::line!()),
+                    &HITS,
+                );
+                &COUNTERS
             }
         }
+    } else {
+        quote!()
     }
 }
 
-fn build_ordered_map(entries: &[Entry], state: HashState) -> proc_macro2::TokenStream {
+/// Builds a `phf::OrderedMap`, counting its lookups unless `counted` is false
+/// because its owner counts them.
+fn build_ordered_map(
+    entries: &[Entry],
+    state: HashState,
+    counted: bool,
+) -> proc_macro2::TokenStream {
+    let counters = if counted {
+        build_counters(entries.len())
+    } else {
+        quote!()
+    };
+
     #[cfg(not(feature = "ptrhash"))]
     {
+        let format = phf_shared::TABLE_FORMAT;
         let key = state.key;
         let disps = state.disps.iter().map(|&(d1, d2)| quote!((#d1, #d2)));
         let idxs = state.map.iter().map(|idx| quote!(#idx));
@@ -464,17 +1023,20 @@ fn build_ordered_map(entries: &[Entry], state: HashState) -> proc_macro2::TokenS
         });
 
         quote! {
-            phf::OrderedMap {
-                key: #key,
-                disps: &[#(#disps),*],
-                idxs: &[#(#idxs),*],
-                entries: &[#(#entries),*],
-            }
+            phf::__ordered_map!(
+                #format,
+                #key,
+                [#(#disps),*],
+                [#(#idxs),*],
+                [#(#entries),*]
+                #counters
+            )
         }
     }
 
     #[cfg(feature = "ptrhash")]
     {
+        let format = phf_shared::TABLE_FORMAT;
         let key = state.seed;
         let pilots = state.pilots.iter().map(|pilot| quote!(#pilot));
         let remap = state.remap.iter().map(|index| quote!(#index));
@@ -486,22 +1048,441 @@ fn build_ordered_map(entries: &[Entry], state: HashState) -> proc_macro2::TokenS
         });
 
         quote! {
-            phf::OrderedMap {
-                key: #key,
-                pilots: &[#(#pilots),*],
-                remap: &[#(#remap),*],
-                idxs: &[#(#idxs),*],
-                entries: &[#(#entries),*],
+            phf::__ordered_map!(
+                #format,
+                #key,
+                [#(#pilots),*],
+                [#(#remap),*],
+                [#(#idxs),*],
+                [#(#entries),*]
+                #counters
+            )
+        }
+    }
+}
+
+/// Returns the key lengths of a map for `phf::find::FindMap`, which require
+/// the keys to be string or byte string literals.
+fn build_key_lengths(entries: &[Entry]) -> syn::Result<proc_macro2::TokenStream> {
+    let lens = entries
+        .iter()
+        .map(|entry| match &entry.parsed_key {
+            ParsedKey::Str(s) => Ok(s.len()),
+            ParsedKey::Binary(s) => Ok(s.len()),
+            _ => Err(Error::new_spanned(
+                &entry.key_expr,
+                "`#![find]` requires string or byte string keys",
+            )),
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let phf_generator::find::KeyLengths {
+        small,
+        long,
+        min,
+        max,
+    } = phf_generator::find::key_lengths(lens);
+    Ok(quote! {
+        &phf::find::KeyLengths::from_raw_parts([#(#small),*], &[#(#long),*], #min, #max)
+    })
+}
+
+/// Returns the deletion table of a map for `phf::suggest::SuggestMap`, with
+/// each deletion mapped to the slots in `state` of the keys it comes from.
+fn build_suggest_index(
+    entries: &[Entry],
+    state: &HashState,
+    max_distance: usize,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let keys = entries
+        .iter()
+        .map(|entry| match &entry.parsed_key {
+            ParsedKey::Str(s) => Ok(s.as_str()),
+            _ => Err(Error::new_spanned(
+                &entry.key_expr,
+                "`#![suggest]` requires string keys",
+            )),
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let mut slots = vec![0u32; keys.len()];
+    for (slot, &idx) in state.map.iter().enumerate() {
+        slots[idx] = slot as u32;
+    }
+
+    let deletes = phf_generator::suggest::generate_deletes(&keys, max_distance)
+        .into_iter()
+        .map(|(delete, idxs)| {
+            let candidates = idxs.iter().map(|&idx| slots[idx]);
+            Entry {
+                key_expr: syn::parse_quote!(#delete),
+                value_expr: syn::parse_quote!(&[#(#candidates),*]),
+                parsed_key: ParsedKey::Str(delete),
+            }
+        })
+        .collect::<Vec<_>>();
+    let deletes_state = generate_hash_state(&deletes);
+    let deletes = build_map(&deletes, deletes_state);
+
+    Ok(quote! {
+        &phf::suggest::SuggestIndex::from_raw_parts(#max_distance, #deletes)
+    })
+}
+
+/// Builds a `phf::Map`, or a `phf::Set` if `set` is true, wrapped in the
+/// table type selected by `#![find]` or `#![suggest]`.
+fn build_wrapped(
+    attrs: &parse::InnerAttrs,
+    entries: &[Entry],
+    state: HashState,
+    set: bool,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let key_lengths = match attrs.find {
+        Some(_) => Some(build_key_lengths(entries)?),
+        None => None,
+    };
+    let index = match &attrs.suggest {
+        Some(suggest) => Some(build_suggest_index(entries, &state, suggest.max_distance)?),
+        None => None,
+    };
+
+    let map = build_map(entries, state);
+    let (table, kind) = if set {
+        (quote!(phf::__set!(#map)), "set")
+    } else {
+        (map, "map")
+    };
+    Ok(if let Some(key_lengths) = key_lengths {
+        let wrap = format_ident!("__find_{}", kind);
+        quote!(phf::#wrap!(#table, #key_lengths))
+    } else if let Some(index) = index {
+        let wrap = format_ident!("__suggest_{}", kind);
+        quote!(phf::#wrap!(#table, #index))
+    } else {
+        table
+    })
+}
+
+/// Rejects `#![find]` and `#![suggest]` on a macro that doesn't support them.
+fn unsupported_attrs(attrs: &parse::InnerAttrs, macro_name: &str) -> Option<TokenStream> {
+    let attr = attrs.first()?;
+    let name = attr.path().to_token_stream();
+    Some(
+        Error::new_spanned(
+            attr,
+            format!("`#![{name}]` isn't supported by `{macro_name}!`"),
+        )
+        .to_compile_error()
+        .into(),
+    )
+}
+
+fn build_lazy_map(entries: &[Entry], state: HashState) -> proc_macro2::TokenStream {
+    let map = build_map(entries, state);
+    quote! {
+        phf::__lazy_map!(#map)
+    }
+}
+
+fn build_filter(entries: &[Entry], state: HashState) -> proc_macro2::TokenStream {
+    // Fingerprints are truncated to the filter's fingerprint type by `as _`.
+    let fingerprints = generate_fingerprints(entries, &state)
+        .into_iter()
+        .map(|fingerprint| quote!(#fingerprint as _));
+
+    #[cfg(not(feature = "ptrhash"))]
+    {
+        let format = phf_shared::TABLE_FORMAT;
+        let key = state.key;
+        let disps = state.disps.iter().map(|&(d1, d2)| quote!((#d1, #d2)));
+
+        quote! {
+            phf::__filter!(#format, #key, [#(#disps),*], [#(#fingerprints),*])
+        }
+    }
+
+    #[cfg(feature = "ptrhash")]
+    {
+        let format = phf_shared::TABLE_FORMAT;
+        let key = state.seed;
+        let pilots = state.pilots.iter().map(|pilot| quote!(#pilot));
+        let remap = state.remap.iter().map(|index| quote!(#index));
+
+        quote! {
+            phf::__filter!(
+                #format,
+                #key,
+                [#(#pilots),*],
+                [#(#remap),*],
+                [#(#fingerprints),*],
+            )
+        }
+    }
+}
+
+/// Returns the first element of a tuple key expression.
+fn tuple_head(mut expr: &Expr) -> Option<&Expr> {
+    loop {
+        match expr {
+            Expr::Group(group) => expr = &group.expr,
+            Expr::Paren(paren) => expr = &paren.expr,
+            Expr::Tuple(tuple) => return tuple.elems.first(),
+            _ => return None,
+        }
+    }
+}
+
+fn build_grouped_map(entries: &[Entry], mut state: HashState) -> proc_macro2::TokenStream {
+    // Group entries by the first key component, keeping definition order.
+    let mut heads = Vec::<Entry>::new();
+    let mut members = Vec::<Vec<usize>>::new();
+    let mut group_of = HashMap::<&ParsedKey, usize>::new();
+    for (idx, entry) in entries.iter().enumerate() {
+        let (head, head_expr) = match (&entry.parsed_key, tuple_head(&entry.key_expr)) {
+            (ParsedKey::Tuple(elements), Some(expr)) if !elements.is_empty() => {
+                (&elements[0], expr)
             }
+            _ => {
+                return Error::new_spanned(
+                    &entry.key_expr,
+                    "`phf_grouped_map!` keys must be tuples",
+                )
+                .to_compile_error();
+            }
+        };
+        let group = *group_of.entry(head).or_insert_with(|| {
+            heads.push(Entry {
+                parsed_key: head.clone(),
+                key_expr: head_expr.clone(),
+                value_expr: syn::parse_quote!(()),
+            });
+            members.push(Vec::new());
+            heads.len() - 1
+        });
+        members[group].push(idx);
+    }
+
+    // Store each group contiguously and point the hash slots at the new
+    // positions.
+    let order = members.concat();
+    let mut positions = vec![0; entries.len()];
+    for (position, &idx) in order.iter().enumerate() {
+        positions[idx] = position;
+    }
+    for idx in &mut state.map {
+        *idx = positions[*idx];
+    }
+    let grouped = order
+        .iter()
+        .map(|&idx| entries[idx].clone())
+        .collect::<Vec<_>>();
+    let map = build_ordered_map(&grouped, state, true);
+
+    let mut start = 0usize;
+    for (group, members) in heads.iter_mut().zip(&members) {
+        let end = start + members.len();
+        group.value_expr = syn::parse_quote!((#start, #end));
+        start = end;
+    }
+    let groups = build_map(&heads, generate_hash_state(&heads));
+
+    let format = phf_shared::TABLE_FORMAT;
+    quote! {
+        phf::__grouped_map!(#format, #map, #groups)
+    }
+}
+
+fn build_ip_prefix_map(entries: &[Entry]) -> syn::Result<proc_macro2::TokenStream> {
+    // Tables keyed by prefix length, longest first.
+    let mut v4 = BTreeMap::<Reverse<u8>, Vec<Entry>>::new();
+    let mut v6 = BTreeMap::<Reverse<u8>, Vec<Entry>>::new();
+    let mut seen = HashSet::new();
+    for entry in entries {
+        let ParsedKey::Str(cidr) = &entry.parsed_key else {
+            return Err(Error::new_spanned(
+                &entry.key_expr,
+                "expected a string literal in CIDR notation",
+            ));
+        };
+        let prefix = phf_generator::ip::parse_cidr(cidr)
+            .map_err(|err| Error::new_spanned(&entry.key_expr, err))?;
+        if !seen.insert(prefix) {
+            return Err(Error::new_spanned(&entry.key_expr, "duplicate network"));
+        }
+        let value_expr = entry.value_expr.clone();
+        match prefix {
+            Prefix::V4 { network, len } => v4.entry(Reverse(len)).or_default().push(Entry {
+                parsed_key: ParsedKey::U32(network),
+                key_expr: syn::parse_quote!(#network),
+                value_expr,
+            }),
+            Prefix::V6 { network, len } => v6.entry(Reverse(len)).or_default().push(Entry {
+                parsed_key: ParsedKey::U128(network),
+                key_expr: syn::parse_quote!(#network),
+                value_expr,
+            }),
+        }
+    }
+
+    let tables = |tables: BTreeMap<Reverse<u8>, Vec<Entry>>| {
+        tables.into_iter().map(|(Reverse(len), entries)| {
+            let map = build_map(&entries, generate_hash_state(&entries));
+            quote!((#len, #map))
+        })
+    };
+    let v4 = tables(v4);
+    let v6 = tables(v6);
+
+    let format = phf_shared::TABLE_FORMAT;
+    Ok(quote! {
+        phf::__ip_prefix_map!(#format, [#(#v4),*], [#(#v6),*])
+    })
+}
+
+/// One range of a `phf_range_map!`, with inclusive bounds.
+struct RangeEntry {
+    start: Entry,
+    end: ParsedKey,
+    end_expr: Expr,
+    range_expr: Expr,
+}
+
+/// Compares two keys of the same type that can be used as range bounds.
+fn cmp_range_keys(a: &ParsedKey, b: &ParsedKey) -> Option<Ordering> {
+    match (a, b) {
+        (ParsedKey::Char(a), ParsedKey::Char(b)) => Some(a.cmp(b)),
+        (ParsedKey::I8(a), ParsedKey::I8(b)) => Some(a.cmp(b)),
+        (ParsedKey::I16(a), ParsedKey::I16(b)) => Some(a.cmp(b)),
+        (ParsedKey::I32(a), ParsedKey::I32(b)) => Some(a.cmp(b)),
+        (ParsedKey::I64(a), ParsedKey::I64(b)) => Some(a.cmp(b)),
+        (ParsedKey::I128(a), ParsedKey::I128(b)) => Some(a.cmp(b)),
+        (ParsedKey::Isize(a), ParsedKey::Isize(b)) => Some(a.cmp(b)),
+        (ParsedKey::U8(a), ParsedKey::U8(b)) => Some(a.cmp(b)),
+        (ParsedKey::U16(a), ParsedKey::U16(b)) => Some(a.cmp(b)),
+        (ParsedKey::U32(a), ParsedKey::U32(b)) => Some(a.cmp(b)),
+        (ParsedKey::U64(a), ParsedKey::U64(b)) => Some(a.cmp(b)),
+        (ParsedKey::U128(a), ParsedKey::U128(b)) => Some(a.cmp(b)),
+        (ParsedKey::Usize(a), ParsedKey::Usize(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+fn parse_range_bound(expr: &Expr, hint: &mut Option<ParsedKey>) -> syn::Result<ParsedKey> {
+    let key = ParsedKey::from_expr(expr, hint.as_ref())?;
+    if cmp_range_keys(&key, &key).is_none() {
+        return Err(Error::new_spanned(
+            expr,
+            "range keys must be characters or integers",
+        ));
+    }
+    match hint {
+        Some(hint) if !key.has_same_type_as(hint) => Err(Error::new_spanned(
+            expr,
+            "key type does not match the first key",
+        )),
+        Some(_) => Ok(key),
+        None => {
+            *hint = Some(key.clone());
+            Ok(key)
         }
     }
 }
 
+/// Handle OR patterns and ranges within the key expression
+fn add_ranges(
+    ranges: &mut Vec<RangeEntry>,
+    hint: &mut Option<ParsedKey>,
+    key: &Expr,
+    value: &Expr,
+) -> syn::Result<()> {
+    let (start_expr, end_expr) = match key {
+        Expr::Binary(binary) if matches!(binary.op, BinOp::BitOr(_)) => {
+            add_ranges(ranges, hint, &binary.left, value)?;
+            return add_ranges(ranges, hint, &binary.right, value);
+        }
+        Expr::Range(range) => match (&range.start, &range.limits, &range.end) {
+            (Some(start), RangeLimits::Closed(_), Some(end)) => (&**start, &**end),
+            (Some(_), RangeLimits::HalfOpen(_), Some(_)) => {
+                return Err(Error::new_spanned(key, "use an inclusive range (`..=`)"));
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    key,
+                    "ranges must have a start and an end",
+                ));
+            }
+        },
+        _ => (key, key),
+    };
+
+    let start = parse_range_bound(start_expr, hint)?;
+    let end = parse_range_bound(end_expr, hint)?;
+    if cmp_range_keys(&start, &end) == Some(Ordering::Greater) {
+        return Err(Error::new_spanned(
+            key,
+            "range start is greater than its end",
+        ));
+    }
+    ranges.push(RangeEntry {
+        start: Entry {
+            parsed_key: start,
+            key_expr: start_expr.clone(),
+            value_expr: value.clone(),
+        },
+        end,
+        end_expr: end_expr.clone(),
+        range_expr: key.clone(),
+    });
+    Ok(())
+}
+
+fn build_range_map(entries: &[parse::Entry]) -> syn::Result<proc_macro2::TokenStream> {
+    let mut ranges = Vec::new();
+    let mut hint = None;
+    for entry in entries {
+        add_ranges(&mut ranges, &mut hint, &entry.key.expr, &entry.value)?;
+    }
+
+    ranges.sort_by(|a, b| cmp_range_keys(&a.start.parsed_key, &b.start.parsed_key).unwrap());
+    for pair in ranges.windows(2) {
+        if cmp_range_keys(&pair[0].end, &pair[1].start.parsed_key) != Some(Ordering::Less) {
+            return Err(Error::new_spanned(
+                &pair[1].range_expr,
+                "range overlaps another range",
+            ));
+        }
+    }
+
+    let (singletons, ranges): (Vec<_>, Vec<_>) = ranges
+        .into_iter()
+        .partition(|range| range.start.parsed_key == range.end);
+    let singletons = singletons
+        .into_iter()
+        .map(|range| range.start)
+        .collect::<Vec<_>>();
+    let singleton_count = singletons.len();
+    // The singletons are counted by the `RangeMap`'s counters.
+    let singletons = build_ordered_map(&singletons, generate_hash_state(&singletons), false);
+    let counters = build_counters(singleton_count + ranges.len());
+    let ranges = ranges.iter().map(|range| {
+        let start = &range.start.key_expr;
+        let end = &range.end_expr;
+        let value = &range.start.value_expr;
+        quote!((#start, #end, #value))
+    });
+
+    let format = phf_shared::TABLE_FORMAT;
+    Ok(quote! {
+        phf::__range_map!(#format, #singletons, [#(#ranges),*] #counters)
+    })
+}
+
 fn resolve_cfg<T: AsMapEntry + ToTokens>(
     macro_name: impl ToTokens,
+    attrs: &parse::InnerAttrs,
     entries: Punctuated<T, Token![,]>,
 ) -> TokenStream {
-    let mut cfg_args = quote! { #macro_name [] };
+    // `#![find]` and `#![suggest]` are passed back with the resolved entries.
+    let mut cfg_args = quote! { #macro_name [#attrs] };
 
     // Wrap conditional entries and groups of unconditional entries in { ... }.
     // Grouping avoids unnecessarily hitting macro recursion limit. Entries are
@@ -532,13 +1513,14 @@ fn resolve_cfg<T: AsMapEntry + ToTokens>(
 
 fn emit_code(
     macro_name: impl ToTokens,
+    attrs: &parse::InnerAttrs,
     entries: Punctuated<impl AsMapEntry + ToTokens, Token![,]>,
-    builder: fn(&[Entry], HashState) -> proc_macro2::TokenStream,
+    builder: impl FnOnce(&[Entry], HashState) -> proc_macro2::TokenStream,
 ) -> TokenStream {
     // If any entries have cfg attributes, resolve them via decl macro
     let has_cfg_attrs = entries.iter().any(|entry| key_has_cfg_attr(entry.key()));
     if has_cfg_attrs {
-        return resolve_cfg(macro_name, entries);
+        return resolve_cfg(macro_name, attrs, entries);
     }
 
     // No cfg attributes - generate code directly
@@ -551,32 +1533,247 @@ fn emit_code(
     }
 }
 
+/// Like `emit_code`, but wraps the map in a `phf::MapWithDefault` if it has a
+/// `_ => value` arm.
+fn emit_map_code(
+    macro_name: impl ToTokens,
+    attrs: &parse::InnerAttrs,
+    mut entries: Punctuated<parse::Entry, Token![,]>,
+    default: Option<parse::Entry>,
+    builder: impl FnOnce(&[Entry], HashState) -> proc_macro2::TokenStream,
+) -> TokenStream {
+    let Some(default) = default else {
+        return emit_code(macro_name, attrs, entries, builder);
+    };
+    if let Some(attr) = attrs.first() {
+        let name = attr.path().to_token_stream();
+        return Error::new_spanned(
+            attr,
+            format!("`#![{name}]` can't be combined with a `_` arm"),
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    // The `_` arm goes through `__resolve_cfg` as the last entry, and is split
+    // off again when the macro is called back.
+    if entries.iter().any(|entry| key_has_cfg_attr(entry.key())) {
+        entries.push(default);
+        return resolve_cfg(macro_name, attrs, entries);
+    }
+
+    match Map::from_parsed(entries) {
+        Ok(map) => {
+            let state = generate_hash_state(&map.entries);
+            let map = builder(&map.entries, state);
+            let default = &default.value;
+            quote! {
+                phf::__map_with_default!(#map, #default)
+            }
+            .into()
+        }
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Rejects a `_ => value` arm in macros that don't support it.
+fn no_default(map: &parse::Map) -> syn::Result<()> {
+    match &map.default {
+        Some(default) => Err(Error::new_spanned(
+            &default.key,
+            "`_` arms are only supported by `phf_map!` and `phf_ordered_map!`",
+        )),
+        None => Ok(()),
+    }
+}
+
 #[proc_macro]
 pub fn phf_map(input: TokenStream) -> TokenStream {
     let map = parse_macro_input!(input as parse::Map);
-    emit_code(quote! { phf_map }, map.entries, build_map)
+    let attrs = &map.attrs;
+    emit_map_code(
+        quote! { phf_map },
+        attrs,
+        map.entries,
+        map.default,
+        |entries, state| {
+            build_wrapped(attrs, entries, state, false).unwrap_or_else(|err| err.to_compile_error())
+        },
+    )
+}
+
+fn build_map_family(family: parse::MapFamily) -> syn::Result<proc_macro2::TokenStream> {
+    let mut maps = Vec::with_capacity(family.members.len());
+    for member in &family.members {
+        if let Some(entry) = member.entries.iter().find(|e| key_has_cfg_attr(e.key())) {
+            return Err(Error::new_spanned(
+                &entry.key,
+                "`#[cfg]` attributes on keys are not supported in `phf_map_family!`",
+            ));
+        }
+        maps.push(Map::from_parsed(member.entries.clone())?);
+    }
+
+    let entries = maps.iter().map(|map| &map.entries[..]).collect::<Vec<_>>();
+    let states = generate_hash_family(&entries);
+
+    let items = family
+        .members
+        .iter()
+        .zip(&maps)
+        .zip(states)
+        .map(|((member, map), state)| {
+            let parse::FamilyMember {
+                attrs,
+                vis,
+                ident,
+                ty,
+                ..
+            } = member;
+            let map = build_map(&map.entries, state);
+            quote! {
+                #(#attrs)*
+                #vis static #ident: #ty = #map;
+            }
+        });
+
+    Ok(quote! { #(#items)* })
+}
+
+#[proc_macro]
+pub fn phf_ip_prefix_map(input: TokenStream) -> TokenStream {
+    let map = parse_macro_input!(input as parse::Map);
+    if let Some(err) = unsupported_attrs(&map.attrs, "phf_ip_prefix_map") {
+        return err;
+    }
+    if let Err(err) = no_default(&map) {
+        return err.to_compile_error().into();
+    }
+    if map
+        .entries
+        .iter()
+        .any(|entry| key_has_cfg_attr(entry.key()))
+    {
+        return resolve_cfg(quote! { phf_ip_prefix_map }, &map.attrs, map.entries);
+    }
+
+    match Map::from_parsed(map.entries).and_then(|map| build_ip_prefix_map(&map.entries)) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro]
+pub fn phf_range_map(input: TokenStream) -> TokenStream {
+    let map = parse_macro_input!(input as parse::RangeMap);
+    if map
+        .entries
+        .iter()
+        .any(|entry| key_has_cfg_attr(entry.key()))
+    {
+        return resolve_cfg(
+            quote! { phf_range_map },
+            &parse::InnerAttrs::default(),
+            map.entries,
+        );
+    }
+
+    let entries = map.entries.into_iter().collect::<Vec<_>>();
+    match build_range_map(&entries) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro]
+pub fn phf_map_family(input: TokenStream) -> TokenStream {
+    let family = parse_macro_input!(input as parse::MapFamily);
+    match build_map_family(family) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
 }
 
 #[proc_macro]
 pub fn phf_set(input: TokenStream) -> TokenStream {
     let set = parse_macro_input!(input as parse::Set);
-    emit_code(quote! { phf_set }, set.keys, |entries, state| {
-        let map = build_map(entries, state);
-        quote!(phf::Set { map: #map })
+    let attrs = &set.attrs;
+    emit_code(quote! { phf_set }, attrs, set.keys, |entries, state| {
+        build_wrapped(attrs, entries, state, true).unwrap_or_else(|err| err.to_compile_error())
     })
 }
 
+#[proc_macro]
+pub fn phf_filter(input: TokenStream) -> TokenStream {
+    let set = parse_macro_input!(input as parse::Set);
+    if let Some(err) = unsupported_attrs(&set.attrs, "phf_filter") {
+        return err;
+    }
+    emit_code(quote! { phf_filter }, &set.attrs, set.keys, build_filter)
+}
+
+#[proc_macro]
+pub fn phf_lazy_map(input: TokenStream) -> TokenStream {
+    let map = parse_macro_input!(input as parse::Map);
+    if let Some(err) = unsupported_attrs(&map.attrs, "phf_lazy_map") {
+        return err;
+    }
+    if let Err(err) = no_default(&map) {
+        return err.to_compile_error().into();
+    }
+    emit_code(
+        quote! { phf_lazy_map },
+        &map.attrs,
+        map.entries,
+        build_lazy_map,
+    )
+}
+
 #[proc_macro]
 pub fn phf_ordered_map(input: TokenStream) -> TokenStream {
     let map = parse_macro_input!(input as parse::Map);
-    emit_code(quote! { phf_ordered_map }, map.entries, build_ordered_map)
+    if let Some(err) = unsupported_attrs(&map.attrs, "phf_ordered_map") {
+        return err;
+    }
+    emit_map_code(
+        quote! { phf_ordered_map },
+        &map.attrs,
+        map.entries,
+        map.default,
+        |entries, state| build_ordered_map(entries, state, true),
+    )
+}
+
+#[proc_macro]
+pub fn phf_grouped_map(input: TokenStream) -> TokenStream {
+    let map = parse_macro_input!(input as parse::Map);
+    if let Some(err) = unsupported_attrs(&map.attrs, "phf_grouped_map") {
+        return err;
+    }
+    if let Err(err) = no_default(&map) {
+        return err.to_compile_error().into();
+    }
+    emit_code(
+        quote! { phf_grouped_map },
+        &map.attrs,
+        map.entries,
+        build_grouped_map,
+    )
 }
 
 #[proc_macro]
 pub fn phf_ordered_set(input: TokenStream) -> TokenStream {
     let set = parse_macro_input!(input as parse::Set);
-    emit_code(quote! { phf_ordered_set }, set.keys, |entries, state| {
-        let map = build_ordered_map(entries, state);
-        quote!(phf::OrderedSet { map: #map })
-    })
+    if let Some(err) = unsupported_attrs(&set.attrs, "phf_ordered_set") {
+        return err;
+    }
+    emit_code(
+        quote! { phf_ordered_set },
+        &set.attrs,
+        set.keys,
+        |entries, state| {
+            let map = build_ordered_map(entries, state, true);
+            quote!(phf::__ordered_set!(#map))
+        },
+    )
 }
@@ -2,10 +2,11 @@
 //! values so the map-building path can be shared.
 
 use proc_macro2::TokenStream;
-use quote::ToTokens;
+use quote::{ToTokens, quote, quote_spanned};
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::{Expr, Result, Token};
+use syn::spanned::Spanned;
+use syn::{BinOp, Expr, Ident, Pat, Result, Token, Type, Visibility, braced};
 
 #[derive(Clone)]
 pub struct Key {
@@ -22,6 +23,13 @@ impl Parse for Key {
     }
 }
 
+impl Key {
+    /// Returns true for the `_` of a default arm.
+    pub fn is_wildcard(&self) -> bool {
+        matches!(self.expr, Expr::Infer(_))
+    }
+}
+
 impl ToTokens for Key {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         for attr in &self.attrs {
@@ -58,25 +66,304 @@ impl ToTokens for Entry {
 }
 
 pub struct Map {
+    pub attrs: InnerAttrs,
     pub entries: Punctuated<Entry, Token![,]>,
+    /// A trailing `_ => value` arm.
+    pub default: Option<Entry>,
 }
 
 impl Parse for Map {
     fn parse(input: ParseStream<'_>) -> Result<Map> {
+        let attrs = input.call(syn::Attribute::parse_inner)?;
+        let attrs = InnerAttrs::parse(&attrs)?;
+        let mut entries = Punctuated::<Entry, Token![,]>::parse_terminated(input)?;
+        let default = match entries.iter().position(|entry| entry.key.is_wildcard()) {
+            Some(idx) if idx + 1 < entries.len() => {
+                return Err(syn::Error::new_spanned(
+                    &entries[idx].key,
+                    "the `_` arm must be the last entry",
+                ));
+            }
+            Some(_) => entries.pop().map(|pair| pair.into_value()),
+            None => None,
+        };
+        if let Some(default) = &default {
+            if let Some(attr) = default.key.attrs.first() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "attributes are not supported on the `_` arm",
+                ));
+            }
+        }
+        attrs.wrap_keys(entries.iter_mut().map(|entry| &mut entry.key));
         Ok(Map {
-            entries: Punctuated::parse_terminated(input)?,
+            attrs,
+            entries,
+            default,
+        })
+    }
+}
+
+/// One `static` item of a `phf_map_family!` invocation.
+pub struct FamilyMember {
+    pub attrs: Vec<syn::Attribute>,
+    pub vis: Visibility,
+    pub ident: Ident,
+    pub ty: Type,
+    pub entries: Punctuated<Entry, Token![,]>,
+}
+
+impl Parse for FamilyMember {
+    fn parse(input: ParseStream<'_>) -> Result<FamilyMember> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![static]>()?;
+        let ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let content;
+        braced!(content in input);
+        let entries = Punctuated::parse_terminated(&content)?;
+        input.parse::<Token![;]>()?;
+        Ok(FamilyMember {
+            attrs,
+            vis,
+            ident,
+            ty,
+            entries,
         })
     }
 }
 
+pub struct MapFamily {
+    pub members: Vec<FamilyMember>,
+}
+
+impl Parse for MapFamily {
+    fn parse(input: ParseStream<'_>) -> Result<MapFamily> {
+        let mut members = Vec::new();
+        while !input.is_empty() {
+            members.push(input.parse()?);
+        }
+        Ok(MapFamily { members })
+    }
+}
+
 pub struct Set {
+    pub attrs: InnerAttrs,
     pub keys: Punctuated<Key, Token![,]>,
 }
 
 impl Parse for Set {
     fn parse(input: ParseStream<'_>) -> Result<Set> {
-        Ok(Set {
-            keys: Punctuated::parse_terminated(input)?,
+        let attrs = input.call(syn::Attribute::parse_inner)?;
+        let attrs = InnerAttrs::parse(&attrs)?;
+        let mut keys = Punctuated::parse_terminated(input)?;
+        attrs.wrap_keys(keys.iter_mut());
+        Ok(Set { attrs, keys })
+    }
+}
+
+/// Returns the call that `#![ascii_case_insensitive]` or `#![normalize(..)]`
+/// wraps keys in.
+fn key_wrapper(attr: &syn::Attribute) -> Result<TokenStream> {
+    if attr.path().is_ident("ascii_case_insensitive") {
+        attr.meta.require_path_only()?;
+        return Ok(quote!(phf::AsciiNoCase));
+    }
+    let normalizers = attr.parse_args_with(Punctuated::<Type, Token![,]>::parse_terminated)?;
+    let normalizers = normalizers.iter().map(|ty| match ty {
+        // Bare names refer to the built-in normalizers.
+        Type::Path(path) if path.qself.is_none() && path.path.get_ident().is_some() => {
+            quote_spanned!(path.span()=> phf::normalize::#path)
+        }
+        ty => quote!(#ty),
+    });
+    let normalizer = match normalizers.len() {
+        1 => quote!(#(#normalizers)*),
+        _ => quote!((#(#normalizers),*)),
+    };
+    Ok(quote!(phf::normalize::Normalized::<#normalizer, _>::new))
+}
+
+/// Wraps each alternative of a key in a call to `wrapper`.
+fn wrap_key(expr: &Expr, wrapper: &TokenStream) -> Expr {
+    match expr {
+        Expr::Binary(binary) if matches!(binary.op, BinOp::BitOr(_)) => {
+            let mut binary = binary.clone();
+            binary.left = Box::new(wrap_key(&binary.left, wrapper));
+            binary.right = Box::new(wrap_key(&binary.right, wrapper));
+            Expr::Binary(binary)
+        }
+        expr => {
+            // Errors about the key span from the first token of the wrapper
+            // to the closing parenthesis, so they should point at the key.
+            let span = expr.span();
+            let mut wrapper = wrapper.clone().into_iter().collect::<Vec<_>>();
+            wrapper[0].set_span(span);
+            syn::parse_quote_spanned!(span=> #(#wrapper)*(#expr))
+        }
+    }
+}
+
+/// The input of `phf_range_map!`. Keys are parsed as patterns so that ranges
+/// can be combined with `|` the way they are in `match` arms.
+pub struct RangeMap {
+    pub entries: Punctuated<Entry, Token![,]>,
+}
+
+impl Parse for RangeMap {
+    fn parse(input: ParseStream<'_>) -> Result<RangeMap> {
+        Ok(RangeMap {
+            entries: Punctuated::parse_terminated_with(input, |input| {
+                Ok(Entry {
+                    key: Key {
+                        attrs: input.call(syn::Attribute::parse_outer)?,
+                        expr: pat_to_expr(Pat::parse_multi_with_leading_vert(input)?)?,
+                    },
+                    arrow: input.parse()?,
+                    value: input.parse()?,
+                })
+            })?,
+        })
+    }
+}
+
+/// Converts a range key pattern to the expression form the other macros use,
+/// with alternatives as `|` binary expressions. Parentheses are dropped, since
+/// `syn` adds them around ranges when printing such expressions back out.
+fn pat_to_expr(pat: Pat) -> Result<Expr> {
+    match pat {
+        Pat::Or(pat) => {
+            let mut cases = pat.cases.into_pairs().map(|pair| pair.into_tuple());
+            let (first, mut op) = cases.next().expect("or-pattern has no cases");
+            let mut expr = pat_to_expr(first)?;
+            for (case, next_op) in cases {
+                expr = Expr::Binary(syn::ExprBinary {
+                    attrs: Vec::new(),
+                    left: Box::new(expr),
+                    op: BinOp::BitOr(op.expect("or-pattern case has no `|`")),
+                    right: Box::new(pat_to_expr(case)?),
+                });
+                op = next_op;
+            }
+            Ok(expr)
+        }
+        Pat::Paren(pat) => pat_to_expr(*pat.pat),
+        Pat::Range(range) => Ok(Expr::Range(range)),
+        pat => syn::parse2(pat.into_token_stream()),
+    }
+}
+
+/// The inner attributes of a `phf_map!` or `phf_set!`, which select a table
+/// type wrapping the map or set.
+#[derive(Default)]
+pub struct InnerAttrs {
+    /// `#![find]`, asking for the key lengths to be recorded in a
+    /// `phf::find::FindMap`.
+    pub find: Option<syn::Attribute>,
+    /// `#![suggest]`, asking for a deletion table in a
+    /// `phf::suggest::SuggestMap`.
+    pub suggest: Option<Suggest>,
+    /// `#![ascii_case_insensitive]` or `#![normalize(..)]`, along with the
+    /// call that every key is wrapped in as it's parsed: `phf::AsciiNoCase`,
+    /// or a `phf::normalize::Normalized` with the given normalizers.
+    pub key_wrapper: Option<(syn::Attribute, TokenStream)>,
+}
+
+impl InnerAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> Result<InnerAttrs> {
+        let mut inner = InnerAttrs::default();
+        for attr in attrs {
+            if attr.path().is_ident("find") {
+                attr.meta.require_path_only()?;
+                if inner.find.replace(attr.clone()).is_some() {
+                    return Err(syn::Error::new_spanned(attr, "duplicate `find` attribute"));
+                }
+            } else if attr.path().is_ident("suggest") {
+                if inner.suggest.replace(Suggest::parse(attr)?).is_some() {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "duplicate `suggest` attribute",
+                    ));
+                }
+            } else if attr.path().is_ident("ascii_case_insensitive")
+                || attr.path().is_ident("normalize")
+            {
+                if inner.key_wrapper.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "`ascii_case_insensitive` and `normalize` can't be combined",
+                    ));
+                }
+                inner.key_wrapper = Some((attr.clone(), key_wrapper(attr)?));
+            } else {
+                return Err(syn::Error::new_spanned(attr, "unsupported attribute"));
+            }
+        }
+        if let (Some(_), Some(suggest)) = (&inner.find, &inner.suggest) {
+            return Err(syn::Error::new_spanned(
+                &suggest.attr,
+                "`find` and `suggest` can't be combined",
+            ));
+        }
+        Ok(inner)
+    }
+
+    /// Applies `#![ascii_case_insensitive]` or `#![normalize(..)]` to `keys`.
+    fn wrap_keys<'a>(&self, keys: impl Iterator<Item = &'a mut Key>) {
+        if let Some((_, wrapper)) = &self.key_wrapper {
+            for key in keys {
+                key.expr = wrap_key(&key.expr, wrapper);
+            }
+        }
+    }
+
+    /// Returns the first of the attributes selecting a table type, if any.
+    pub fn first(&self) -> Option<&syn::Attribute> {
+        self.find
+            .as_ref()
+            .or(self.suggest.as_ref().map(|suggest| &suggest.attr))
+    }
+}
+
+// `#![ascii_case_insensitive]` and `#![normalize(..)]` are left out, since
+// the keys are already wrapped.
+impl ToTokens for InnerAttrs {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.find.to_tokens(tokens);
+        if let Some(suggest) = &self.suggest {
+            suggest.attr.to_tokens(tokens);
+        }
+    }
+}
+
+/// A `#![suggest]` or `#![suggest(max_distance = N)]` attribute.
+pub struct Suggest {
+    pub attr: syn::Attribute,
+    pub max_distance: usize,
+}
+
+impl Suggest {
+    /// The distance the table covers unless `max_distance` is given.
+    const DEFAULT_MAX_DISTANCE: usize = 2;
+
+    fn parse(attr: &syn::Attribute) -> Result<Suggest> {
+        let mut max_distance = Suggest::DEFAULT_MAX_DISTANCE;
+        if !matches!(attr.meta, syn::Meta::Path(_)) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("max_distance") {
+                    max_distance = meta.value()?.parse::<syn::LitInt>()?.base10_parse()?;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported `suggest` option"))
+                }
+            })?;
+        }
+        Ok(Suggest {
+            attr: attr.clone(),
+            max_distance,
         })
     }
 }
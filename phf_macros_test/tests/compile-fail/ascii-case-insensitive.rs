@@ -0,0 +1,14 @@
+use phf::{phf_map, phf_set, AsciiNoCase};
+
+static MAP: phf::Map<AsciiNoCase<&'static str>, isize> = phf_map! {
+    #![ascii_case_insensitive]
+    "FOO" => 42,
+    "foo" => 42, //~ ERROR duplicate key
+};
+
+static SET: phf::Set<AsciiNoCase<&'static str>> = phf_set! {
+    #![case_insensitive] //~ ERROR unsupported attribute
+    "foo",
+};
+
+fn main() {}
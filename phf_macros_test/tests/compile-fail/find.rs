@@ -0,0 +1,19 @@
+use phf::{phf_map, phf_ordered_set};
+
+static NUMBERS: phf::find::FindMap<u32, u32> = phf_map! {
+    #![find]
+    1u32 => 2, //~ ERROR `#![find]` requires string or byte string keys
+};
+
+static ORDERED: phf::OrderedSet<&'static str> = phf_ordered_set! {
+    #![find] //~ ERROR `#![find]` isn't supported by `phf_ordered_set!`
+    "a",
+};
+
+static DEFAULTED: phf::MapWithDefault<&'static str, u32> = phf_map! {
+    #![find] //~ ERROR `#![find]` can't be combined with a `_` arm
+    "a" => 1,
+    _ => 0,
+};
+
+fn main() {}
@@ -0,0 +1,8 @@
+static MAP: phf::Map<u32, u32> = phf::Map::from_raw_parts(0, &[(0, 0)], &[]); //~ ERROR evaluation panicked
+
+static NETWORKS: phf::IpPrefixMap<u32> =
+    phf::IpPrefixMap::from_raw_parts(&[(8, phf::Map::new()), (16, phf::Map::new())], &[]); //~ ERROR evaluation panicked
+
+static LENGTHS: phf::find::KeyLengths = phf::find::KeyLengths::from_raw_parts([0; 4], &[], 2, 1); //~ ERROR evaluation panicked
+
+fn main() {}
@@ -0,0 +1,6 @@
+// Generated for a table format this version of `phf` doesn't use.
+static MAP: phf::Map<u32, u32> = phf::__map!("other/1", 0, [], [], [], [], []); //~ ERROR evaluation panicked
+
+static FILTER: phf::Filter = phf::__filter!("other/1", 0, [], []); //~ ERROR evaluation panicked
+
+fn main() {}
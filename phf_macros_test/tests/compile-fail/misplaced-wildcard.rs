@@ -0,0 +1,8 @@
+use phf::phf_map;
+
+static MAP: phf::MapWithDefault<u32, u32> = phf_map! {
+    _ => 0, //~ ERROR the `_` arm must be the last entry
+    1u32 => 1,
+};
+
+fn main() {}
@@ -0,0 +1,23 @@
+use phf::normalize::{DashUnderscore, Normalized, Trim};
+use phf::{phf_map, phf_set};
+
+static MAP: phf::Map<Normalized<(Trim, DashUnderscore), &'static str>, isize> = phf_map! {
+    #![normalize(Trim, DashUnderscore)]
+    "dry-run" => 42,
+    " dry_run" => 42, //~ ERROR duplicate key
+};
+
+struct Custom;
+
+static SET: phf::Set<Normalized<Custom, &'static str>> = phf_set! {
+    #![normalize(Custom)] //~ ERROR unsupported normalizer
+    "foo",
+};
+
+static BOTH: phf::Set<Normalized<Trim, &'static str>> = phf_set! {
+    #![normalize(Trim)]
+    #![ascii_case_insensitive] //~ ERROR can't be combined
+    "foo",
+};
+
+fn main() {}
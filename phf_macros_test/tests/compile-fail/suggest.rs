@@ -0,0 +1,25 @@
+use phf::{phf_map, phf_set};
+
+static NUMBERS: phf::suggest::SuggestMap<u32, u32> = phf_map! {
+    #![suggest]
+    1u32 => 2, //~ ERROR `#![suggest]` requires string keys
+};
+
+static BOTH: phf::suggest::SuggestSet<&'static str> = phf_set! {
+    #![find]
+    #![suggest] //~ ERROR `find` and `suggest` can't be combined
+    "a",
+};
+
+static TWICE: phf::suggest::SuggestSet<&'static str> = phf_set! {
+    #![suggest]
+    #![suggest(max_distance = 1)] //~ ERROR duplicate `suggest` attribute
+    "a",
+};
+
+static DISTANCE: phf::suggest::SuggestSet<&'static str> = phf_set! {
+    #![suggest(distance = 1)] //~ ERROR unsupported `suggest` option
+    "a",
+};
+
+fn main() {}
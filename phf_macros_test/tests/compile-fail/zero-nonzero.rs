@@ -0,0 +1,10 @@
+use std::num::NonZeroU8;
+
+use phf::phf_map;
+
+static MAP: phf::Map<NonZeroU8, u32> = phf_map! {
+    NonZeroU8::new(1).unwrap() => 1,
+    NonZeroU8::new(0).unwrap() => 0, //~ ERROR `NonZero` keys can't be zero
+};
+
+fn main() {}
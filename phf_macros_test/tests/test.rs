@@ -22,6 +22,56 @@ mod map {
         *b"camembert" => "delicious",
     );
 
+    #[allow(dead_code)]
+    static DROP_VALUE: phf::Map<&'static str, Vec<u8>> = phf_map!(
+        "empty" => Vec::new(),
+    );
+
+    #[test]
+    fn test_from_raw_parts() {
+        static MAP: phf::Map<&'static str, isize> = phf_map!(
+            "foo" => 10,
+            "bar" => 11,
+        );
+        #[cfg(not(feature = "ptrhash"))]
+        const EMPTY: phf::Map<u32, u32> = phf::Map::from_raw_parts(0, &[], &[]);
+        #[cfg(feature = "ptrhash")]
+        const EMPTY: phf::Map<u32, u32> = phf::Map::from_raw_parts(0, &[], &[], &[]);
+        #[cfg(not(feature = "ptrhash"))]
+        let map = phf::Map::from_raw_parts(MAP.key, MAP.disps, MAP.entries);
+        #[cfg(feature = "ptrhash")]
+        let map = phf::Map::from_raw_parts(MAP.key, MAP.pilots, MAP.remap, MAP.entries);
+        assert!(EMPTY.is_empty());
+        assert_eq!(None, EMPTY.get(&0));
+        assert_eq!(Some(&10), map.get("foo"));
+        assert_eq!(Some(&11), map.get("bar"));
+        assert_eq!(None, map.get("baz"));
+    }
+
+    #[test]
+    fn test_validate() {
+        static MAP: phf::Map<&'static str, isize> = phf_map!(
+            "foo" => 10,
+            "bar" => 11,
+            "baz" => 12,
+            "qux" => 13,
+        );
+        assert!(MAP.validate().is_ok());
+        #[cfg(not(feature = "ptrhash"))]
+        let map = phf::Map::from_raw_parts(MAP.key ^ 1, MAP.disps, MAP.entries);
+        #[cfg(feature = "ptrhash")]
+        let map = phf::Map::from_raw_parts(MAP.key ^ 1, MAP.pilots, MAP.remap, MAP.entries);
+        let unreachable = map.validate().unwrap_err();
+        let keys = unreachable.clone().collect::<Vec<_>>();
+        assert!(!keys.is_empty());
+        assert!(keys.iter().all(|key| map.get(*key).is_none()));
+        assert!(
+            unreachable
+                .to_string()
+                .starts_with(&format!("{} of 4 keys can't be looked up", keys.len()))
+        );
+    }
+
     #[test]
     fn test_two() {
         static MAP: phf::Map<&'static str, isize> = phf_map!(
@@ -364,6 +414,412 @@ mod map {
         assert_eq!(Some(&1), MAP.get(&(0u32, key.as_str())));
     }
 
+    #[test]
+    fn test_std_keys() {
+        use std::cmp::Ordering;
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4};
+        use std::num::{NonZero, NonZeroU16};
+        use std::time::Duration;
+
+        static OPTIONS: phf::Map<Option<&str>, u32> = phf_map! {
+            Some("a") => 1,
+            None => 0,
+            Option::Some("b") => 2,
+        };
+        assert_eq!(Some(&1), OPTIONS.get(&Some("a")));
+        assert_eq!(Some(&0), OPTIONS.get(&None::<&str>));
+        assert_eq!(None, OPTIONS.get(&Some("c")));
+        let key = "b".to_string();
+        assert_eq!(Some(&2), OPTIONS.get(&Some(key.as_str())));
+
+        static RESULTS: phf::Map<Result<u8, char>, u32> = phf_map! {
+            Ok(1u8) => 1,
+            Err('x') => 2,
+        };
+        assert_eq!(Some(&1), RESULTS.get(&Ok(1)));
+        assert_eq!(Some(&2), RESULTS.get(&Err('x')));
+        assert_eq!(None, RESULTS.get(&Ok(2)));
+
+        static UNIT: phf::Map<(), u32> = phf_map! { () => 1 };
+        assert_eq!(Some(&1), UNIT.get(&()));
+
+        static ORDERINGS: phf::Map<Ordering, &str> = phf_map! {
+            Ordering::Less => "<",
+            Ordering::Equal => "=",
+            std::cmp::Ordering::Greater => ">",
+        };
+        assert_eq!(Some(&"<"), ORDERINGS.get(&1.cmp(&2)));
+        assert_eq!(Some(&">"), ORDERINGS.get(&Ordering::Greater));
+
+        static NONZERO: phf::Map<NonZeroU16, u32> = phf_map! {
+            NonZeroU16::new(1).unwrap() => 1,
+            NonZero::new(300u16).unwrap() => 300,
+        };
+        assert_eq!(Some(&300), NONZERO.get(&NonZeroU16::new(300).unwrap()));
+        assert_eq!(None, NONZERO.get(&NonZeroU16::new(2).unwrap()));
+
+        static ADDRS: phf::Map<IpAddr, &str> = phf_map! {
+            IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)) => "v4 loopback",
+            IpAddr::V6(Ipv6Addr::LOCALHOST) => "v6 loopback",
+            IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)) => "doc",
+        };
+        assert_eq!(
+            Some(&"v4 loopback"),
+            ADDRS.get(&"127.0.0.1".parse().unwrap())
+        );
+        assert_eq!(Some(&"v6 loopback"), ADDRS.get(&"::1".parse().unwrap()));
+        assert_eq!(Some(&"doc"), ADDRS.get(&"2001:db8::1".parse().unwrap()));
+        assert_eq!(None, ADDRS.get(&"::2".parse().unwrap()));
+
+        static SOCKETS: phf::Map<SocketAddr, &str> = phf_map! {
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 80)) => "http",
+        };
+        assert_eq!(Some(&"http"), SOCKETS.get(&"0.0.0.0:80".parse().unwrap()));
+        assert_eq!(None, SOCKETS.get(&"0.0.0.0:443".parse().unwrap()));
+
+        static TIMEOUTS: phf::Map<Duration, &str> = phf_map! {
+            Duration::from_millis(1500) => "slow",
+            Duration::new(0, 500_000) => "fast",
+            Duration::ZERO => "none",
+        };
+        assert_eq!(Some(&"slow"), TIMEOUTS.get(&Duration::from_secs_f32(1.5)));
+        assert_eq!(Some(&"fast"), TIMEOUTS.get(&Duration::from_micros(500)));
+        assert_eq!(Some(&"none"), TIMEOUTS.get(&Duration::default()));
+    }
+
+    #[test]
+    fn test_float_keys() {
+        use phf::{TotalF32, TotalF64};
+
+        static RATES: phf::Map<TotalF64, &str> = phf_map! {
+            44100.0f64 => "cd",
+            48000.0 => "dvd",
+            -0.0 => "negative zero",
+            0.0 => "zero",
+            TotalF64(f64::INFINITY) => "infinite",
+            f64::NAN => "nan",
+        };
+        assert_eq!(Some(&"cd"), RATES.get(&TotalF64(44100.0)));
+        assert_eq!(Some(&"dvd"), RATES.get(&TotalF64(48000.0)));
+        assert_eq!(Some(&"negative zero"), RATES.get(&TotalF64(-0.0)));
+        assert_eq!(Some(&"zero"), RATES.get(&TotalF64(0.0)));
+        assert_eq!(None, RATES.get(&TotalF64(22050.0)));
+        assert_eq!(Some(&"nan"), RATES.get(&TotalF64(f64::NAN)));
+        assert_eq!(Some(&"infinite"), RATES.get(&TotalF64(1.0 / 0.0)));
+
+        static FACTORS: phf::Map<(&str, TotalF32), u32> = phf_map! {
+            ("km", 1000.0f32) => 1,
+            ("mi", -1.5) => 2,
+        };
+        assert_eq!(Some(&2), FACTORS.get(&("mi", TotalF32(-1.5))));
+
+        static OPTIONAL: phf::Set<Option<TotalF64>> = phf::phf_set! {
+            Some(0.5f64),
+            None,
+        };
+        assert!(OPTIONAL.contains(&Some(TotalF64(0.5))));
+        assert!(!OPTIONAL.contains(&Some(TotalF64(-0.5))));
+    }
+
+    #[test]
+    fn test_ascii_case_insensitive() {
+        use phf::AsciiNoCase;
+
+        static HEADERS: phf::Map<AsciiNoCase<&'static str>, u8> = phf_map! {
+            #![ascii_case_insensitive]
+            "Content-Type" => 1,
+            "Content-Length" | "Transfer-Encoding" => 2,
+            #[cfg(any())]
+            "Cookie" => 3,
+        };
+        assert_eq!(Some(&1), HEADERS.get(&AsciiNoCase("content-type")));
+        assert_eq!(
+            Some(&2),
+            HEADERS.get(&AsciiNoCase(&b"TRANSFER-ENCODING"[..]))
+        );
+        assert_eq!(None, HEADERS.get(&AsciiNoCase("cookie")));
+        assert_eq!(None, HEADERS.get(&AsciiNoCase("content-typ")));
+
+        static TAGS: phf::Map<AsciiNoCase<&'static [u8]>, u8> = phf_map! {
+            AsciiNoCase(b"DIV") => 1,
+            AsciiNoCase(b"Span") => 2,
+        };
+        assert_eq!(Some(&1), TAGS.get(&AsciiNoCase(&b"div"[..])));
+        assert_eq!(Some(&2), TAGS.get(&AsciiNoCase("SPAN")));
+        assert_eq!(
+            Some(&b"Span"[..]),
+            TAGS.get_key(&AsciiNoCase("span")).map(|key| key.0)
+        );
+    }
+
+    #[test]
+    fn test_normalized_keys() {
+        use phf::normalize::{Lowercase, Nfc, Normalized, Trim};
+
+        static LOCALES: phf::Map<Normalized<(Trim, Nfc, Lowercase), &'static str>, u8> = phf_map! {
+            #![normalize(Trim, Nfc, Lowercase)]
+            "Français" => 1,
+            " Español " | "Castellano" => 2,
+            #[cfg(any())]
+            "Deutsch" => 3,
+        };
+        // "Franc\u{327}ais" decomposes the "ç".
+        assert_eq!(Some(&1), LOCALES.get(&Normalized::new("  franc\u{327}ais")));
+        assert_eq!(Some(&2), LOCALES.get(&Normalized::new("ESPAÑOL\t")));
+        assert_eq!(Some(&2), LOCALES.get(&Normalized::new("castellano")));
+        assert_eq!(None, LOCALES.get(&Normalized::new("deutsch")));
+        assert_eq!(
+            Some(" Español "),
+            LOCALES
+                .get_key(&Normalized::new("español"))
+                .map(|key| *key.get())
+        );
+
+        static FLAGS: phf::Map<Normalized<phf::normalize::DashUnderscore, &'static str>, u8> = phf_map! {
+            Normalized::<phf::normalize::DashUnderscore, _>::new("dry-run") => 1,
+        };
+        assert_eq!(Some(&1), FLAGS.get(&Normalized::new("dry_run")));
+    }
+
+    #[test]
+    fn test_c_str_keys() {
+        use std::ffi::{CStr, c_char};
+
+        static EVENTS: phf::Map<&'static CStr, u8> = phf_map! {
+            c"open" => 1,
+            c"close" | c"\xff" => 2,
+        };
+        let name: *const c_char = c"close".as_ptr();
+        assert_eq!(Some(&2), EVENTS.get(unsafe { CStr::from_ptr(name) }));
+        assert_eq!(Some(&2), EVENTS.get(c"\xff"));
+        assert_eq!(Some(&1), EVENTS.get(c"open"));
+        assert_eq!(None, EVENTS.get(c"opened"));
+    }
+
+    #[test]
+    fn test_path_lookups() {
+        use std::path::Path;
+
+        static LANGUAGES: phf::Map<&'static [u8], &'static str> = phf_map! {
+            b"Cargo.toml" => "toml",
+            b"src/main.rs" => "rust",
+        };
+        let path = Path::new("src").join("main.rs");
+        assert_eq!(
+            Some(&"rust"),
+            LANGUAGES.get(path.as_os_str().as_encoded_bytes())
+        );
+
+        let map = phf::ArrayMap::new([
+            (Path::new("/etc/hosts"), "hosts"),
+            (Path::new("/etc/passwd"), "passwd"),
+        ])
+        .unwrap();
+        assert_eq!(Some(&"hosts"), map.get(Path::new("/etc//hosts/")));
+        assert_eq!(None, map.get(Path::new("/etc/shadow")));
+    }
+
+    #[test]
+    fn test_buffer_type_keys() {
+        use bstr::BStr;
+        use bytes::Bytes;
+        use compact_str::CompactString;
+        use smol_str::SmolStr;
+        use uuid::Uuid;
+
+        // `Bytes` has interior mutability, so it can't be a key of a
+        // static table, but it can be looked up in one.
+        static METHODS: phf::Map<&'static [u8], u8> = phf_map! {
+            b"GET" => 1,
+            b"POST" => 2,
+        };
+        assert_eq!(Some(&2), METHODS.get(&Bytes::from_static(b"POST")[..]));
+        let map = phf::ArrayMap::new([(Bytes::from_static(b"GET"), 1)]).unwrap();
+        assert_eq!(Some(&1), map.get(&b"GET"[..]));
+        assert_eq!(None, map.get(&b"PUT"[..]));
+
+        static SMOL: phf::Map<SmolStr, u8> = phf_map! {
+            SmolStr::new_static("a") => 1,
+            SmolStr::new_inline("b") => 2,
+        };
+        assert_eq!(Some(&1), SMOL.get("a"));
+        assert_eq!(Some(&2), SMOL.get(&SmolStr::new("b")));
+
+        static COMPACT: phf::Map<CompactString, u8> = phf_map! {
+            CompactString::const_new("a") => 1,
+        };
+        assert_eq!(Some(&1), COMPACT.get("a"));
+        assert_eq!(Some(&1), COMPACT.get(&CompactString::new("a")));
+
+        static IDS: phf::Map<Uuid, u8> = phf_map! {
+            Uuid::from_u128(0x67e5504410b1426f9247bb680e5fe0c8) => 1,
+            Uuid::from_bytes([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]) => 2,
+        };
+        let id = Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+        assert_eq!(Some(&1), IDS.get(&id));
+        assert_eq!(Some(&1), IDS.get(id.as_bytes()));
+        assert_eq!(Some(&2), IDS.get(&Uuid::nil()));
+
+        let map = phf::ArrayMap::new([(BStr::new("a"), 1), (BStr::new(b"\xff"), 2)]).unwrap();
+        assert_eq!(Some(&2), map.get(&b"\xff"[..]));
+        assert_eq!(Some(&1), map.get(BStr::new("a")));
+    }
+
+    #[test]
+    fn test_get_by_parts() {
+        static MAP: phf::Map<&'static str, isize> = phf_map!(
+            "server.port" => 10,
+            "server.host" => 11,
+            "naïve" => 12,
+        );
+        assert_eq!(Some(&10), MAP.get_by_parts(&["server", ".", "port"]));
+        assert_eq!(Some(&11), MAP.get_by_parts(&["server.host"]));
+        assert_eq!(
+            Some(&11),
+            MAP.get_by_parts(&["", "server.", "", "host", ""])
+        );
+        assert_eq!(None, MAP.get_by_parts(&["server", "port"]));
+        assert_eq!(None, MAP.get_by_parts(&["server.port", "s"]));
+        assert_eq!(None, MAP.get_by_parts(&[]));
+
+        // chunks may split a multi-byte character
+        let bytes = "naïve".as_bytes();
+        assert_eq!(Some(&12), MAP.get_by_chunks([&bytes[..3], &bytes[3..]]));
+
+        static EMPTY: phf::Map<&'static str, isize> = phf_map!();
+        assert_eq!(None, EMPTY.get_by_parts(&["a", "b"]));
+    }
+
+    #[test]
+    fn test_get_by_chunks() {
+        static MAP: phf::Map<&'static [u8], isize> = phf_map!(
+            b"GET /index.html" => 10,
+            b"GET /" => 11,
+        );
+        let buffers: [&[u8]; 3] = [b"GE", b"T /index", b".html"];
+        assert_eq!(Some(&10), MAP.get_by_chunks(buffers));
+        assert_eq!(Some(&11), MAP.get_by_chunks([&b"GE"[..], &b"T /"[..]]));
+        assert_eq!(None, MAP.get_by_chunks(buffers[..2].iter().copied()));
+    }
+
+    #[test]
+    fn test_find_iter() {
+        use phf::MatchKind;
+
+        static MAP: phf::Map<&'static str, isize> = phf_map!(
+            "he" => 1,
+            "hers" => 2,
+            "she" => 3,
+            "his" => 4,
+            "é" => 5,
+        );
+        let found = |kind| {
+            MAP.finder()
+                .match_kind(kind)
+                .find_iter("ushers café")
+                .map(|m| (m.start(), m.end(), *m.value()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(found(MatchKind::LeftmostLongest), [(1, 4, 3), (10, 12, 5)]);
+        assert_eq!(found(MatchKind::LeftmostShortest), [(1, 4, 3), (10, 12, 5)]);
+        assert_eq!(
+            found(MatchKind::Overlapping),
+            [(1, 4, 3), (2, 4, 1), (2, 6, 2), (10, 12, 5)]
+        );
+
+        let matches = MAP.find_iter("hershe").collect::<Vec<_>>();
+        assert_eq!(2, matches.len());
+        assert_eq!(
+            (0..4, &"hers", &2),
+            (matches[0].range(), matches[0].key(), matches[0].value())
+        );
+        assert_eq!(
+            (4..6, &"he", &1),
+            (matches[1].range(), matches[1].key(), matches[1].value())
+        );
+
+        // byte haystacks work for `str` keys
+        assert_eq!(1, MAP.find_iter(&b"xx\xffhis"[..]).count());
+
+        static EMPTY: phf::Map<&'static str, isize> = phf_map!();
+        assert_eq!(0, EMPTY.find_iter("anything").count());
+    }
+
+    #[test]
+    fn test_find_iter_bytes() {
+        static MAP: phf::Map<&'static [u8], isize> = phf_map!(
+            b"\r\n" => 0,
+            b"\n" => 1,
+        );
+        let found = MAP
+            .find_iter(&b"a\r\nb\nc"[..])
+            .map(|m| (m.start(), *m.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(found, [(1, 0), (4, 1)]);
+    }
+
+    #[test]
+    fn test_find_map() {
+        use phf::MatchKind;
+
+        static MAP: phf::find::FindMap<&'static str, isize> = phf_map!(
+            #![find]
+            "he" => 1,
+            "hers" => 2,
+            "she" => 3,
+            #[cfg(feature = "disabled_feature")]
+            "ushers" => 4,
+        );
+        let found = |kind| {
+            MAP.finder()
+                .match_kind(kind)
+                .find_iter("ushers")
+                .map(|m| (m.start(), m.end(), *m.value()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(found(MatchKind::LeftmostLongest), [(1, 4, 3)]);
+        assert_eq!(
+            found(MatchKind::Overlapping),
+            [(1, 4, 3), (2, 4, 1), (2, 6, 2)]
+        );
+        assert_eq!(Some(&2), MAP.get("hers"));
+        assert_eq!(3, MAP.len());
+
+        static EMPTY: phf::find::FindMap<&'static [u8], isize> = phf_map!(#![find]);
+        assert_eq!(0, EMPTY.find_iter(&b"anything"[..]).count());
+    }
+
+    #[test]
+    fn test_suggest() {
+        static MAP: phf::suggest::SuggestMap<&'static str, isize> = phf_map!(
+            #![suggest]
+            "break" => 0,
+            "bread" => 1,
+            "breadth" => 2,
+            "résumé" => 3,
+            #[cfg(feature = "disabled_feature")]
+            "brake" => 4,
+        );
+        assert_eq!(2, MAP.index().max_distance());
+        assert_eq!(
+            MAP.suggest("braek", 2),
+            [(&"break", &0, 1), (&"bread", &1, 2)]
+        );
+        assert_eq!(MAP.suggest("bread", 0), [(&"bread", &1, 0)]);
+        assert_eq!(MAP.suggest("bread", 2).len(), 3);
+        // distances count characters, not bytes
+        assert_eq!(MAP.suggest("resume", 2), [(&"résumé", &3, 2)]);
+        // capped at the index's distance
+        assert!(MAP.suggest("bxxxk", 3).is_empty());
+        // the linear scan on the plain map isn't capped
+        assert_eq!(MAP.map.suggest("bxxxk", 3), [(&"break", &0, 3)]);
+        assert_eq!(MAP.suggest("braek", 2), MAP.map.suggest("braek", 2));
+        assert_eq!(Some(&2), MAP.get("breadth"));
+
+        static EMPTY: phf::suggest::SuggestMap<&'static str, isize> = phf_map!(#![suggest]);
+        assert!(EMPTY.suggest("break", 2).is_empty());
+    }
+
     #[test]
     fn test_or_pattern() {
         static MAP: phf::Map<&'static str, isize> = phf_map!(
@@ -400,6 +856,51 @@ mod map {
     }
 }
 
+mod map_family {
+    phf::phf_map_family! {
+        static KEYWORDS: phf::Map<&'static str, u32> = {
+            "fn" => 0,
+            "let" => 1,
+            "if" | "else" => 2,
+        };
+        #[allow(dead_code)]
+        pub(crate) static BUILTINS: phf::Map<&'static str, u32> = {
+            "print" => 10,
+            "len" => 11,
+        };
+        static EMPTY: phf::Map<&'static str, u32> = {};
+    }
+
+    static UNRELATED: phf::Map<&'static str, u32> = phf::phf_map! {
+        "print" => 20,
+    };
+
+    #[test]
+    fn test_shared_key() {
+        let ident = "print".to_string();
+        let prepared = KEYWORDS.prepare(ident.as_str());
+        assert_eq!(None, KEYWORDS.get_prepared(&prepared));
+        assert_eq!(Some(&10), BUILTINS.get_prepared(&prepared));
+        assert_eq!(None, EMPTY.get_prepared(&prepared));
+        assert_eq!(
+            Some((&"print", &10)),
+            BUILTINS.get_entry_prepared(&prepared)
+        );
+
+        let prepared = BUILTINS.prepare("else");
+        assert_eq!(Some(&2), KEYWORDS.get_prepared(&prepared));
+        assert_eq!(None, BUILTINS.get_prepared(&prepared));
+        assert_eq!(KEYWORDS.get("else"), KEYWORDS.get_prepared(&prepared));
+    }
+
+    #[test]
+    fn test_unrelated_map() {
+        let prepared = BUILTINS.prepare("print");
+        assert_eq!(Some(&20), UNRELATED.get_prepared(&prepared));
+        assert_eq!("print", prepared.key());
+    }
+}
+
 mod set {
     use phf::phf_set;
     use std::collections::HashSet;
@@ -426,6 +927,17 @@ mod set {
         assert_eq!(2, SET.len());
     }
 
+    #[test]
+    fn test_validate() {
+        static SET: phf::Set<u32> = phf_set! {
+            1u32,
+            2u32,
+            3u32,
+        };
+        assert!(SET.validate().is_ok());
+        assert!(phf::Map::<u32, ()>::new().validate().is_ok());
+    }
+
     #[test]
     fn test_iter() {
         static SET: phf::Set<&'static str> = phf_set! {
@@ -458,6 +970,85 @@ mod set {
         }
     }
 
+    #[test]
+    fn test_find_iter() {
+        static STOP_WORDS: phf::Set<&'static str> = phf_set! {
+            "a",
+            "an",
+            "the",
+        };
+        let text = "the cat and a dog";
+        let found = STOP_WORDS
+            .find_iter(text)
+            .map(|m| &text[m.range()])
+            .collect::<Vec<_>>();
+        assert_eq!(found, ["the", "a", "an", "a"]);
+
+        let finder = STOP_WORDS.finder().match_kind(phf::MatchKind::Overlapping);
+        assert_eq!(2, finder.find_iter("an").count());
+        assert_eq!(1, finder.find_iter("theme").count());
+    }
+
+    #[test]
+    fn test_find_set() {
+        static STOP_WORDS: phf::find::FindSet<&'static str> = phf_set! {
+            #![find]
+            "a",
+            "an",
+            "the",
+        };
+        let text = "the cat and a dog";
+        let found = STOP_WORDS
+            .find_iter(text)
+            .map(|m| &text[m.range()])
+            .collect::<Vec<_>>();
+        assert_eq!(found, ["the", "a", "an", "a"]);
+        assert!(STOP_WORDS.contains("an"));
+    }
+
+    #[test]
+    fn test_suggest() {
+        static SET: phf::suggest::SuggestSet<&'static str> = phf_set! {
+            #![suggest(max_distance = 1)]
+            "install",
+            "uninstall",
+        };
+        assert_eq!(1, SET.index().max_distance());
+        assert_eq!(SET.suggest("instal", 1), [(&"install", 1)]);
+        assert!(SET.suggest("unistal", 2).is_empty());
+        assert_eq!(SET.set.suggest("unistal", 2), [(&"uninstall", 2)]);
+    }
+
+    #[test]
+    fn test_ascii_case_insensitive() {
+        use phf::AsciiNoCase;
+
+        static KEYWORDS: phf::Set<AsciiNoCase<&'static str>> = phf_set! {
+            #![ascii_case_insensitive]
+            "SELECT",
+            "from",
+            "Where",
+        };
+        assert!(KEYWORDS.contains(&AsciiNoCase("select")));
+        assert!(KEYWORDS.contains(&AsciiNoCase("FROM")));
+        assert!(KEYWORDS.contains(&AsciiNoCase(&b"wHeRe"[..])));
+        assert!(!KEYWORDS.contains(&AsciiNoCase("join")));
+    }
+
+    #[test]
+    fn test_normalized_keys() {
+        use phf::normalize::{AsciiLowercase, Normalized, Trim};
+
+        static KEYWORDS: phf::Set<Normalized<(Trim, AsciiLowercase), &'static str>> = phf_set! {
+            #![normalize(Trim, AsciiLowercase)]
+            "SELECT",
+            "from",
+        };
+        assert!(KEYWORDS.contains(&Normalized::new(" select\n")));
+        assert!(KEYWORDS.contains(&Normalized::new("FROM")));
+        assert!(!KEYWORDS.contains(&Normalized::new("fro m")));
+    }
+
     #[test]
     fn test_inferred_integer_keys() {
         static SET: phf::Set<u64> = phf_set! {
@@ -557,6 +1148,892 @@ mod set {
     }
 }
 
+mod grouped_map {
+    use phf::phf_grouped_map;
+
+    static MESSAGES: phf::GroupedMap<(&'static str, u32), &'static str, &'static str> = phf_grouped_map! {
+        ("en", 1u32) => "Hello",
+        ("de", 1) => "Hallo",
+        ("en", 2) => "Goodbye",
+        ("fr", 1) => "Bonjour",
+        ("de", 2) => "Auf Wiedersehen",
+    };
+
+    #[test]
+    fn test_get() {
+        assert_eq!(5, MESSAGES.len());
+        assert_eq!(3, MESSAGES.group_count());
+        assert_eq!(Some(&"Goodbye"), MESSAGES.get(&("en", 2)));
+        assert_eq!("Bonjour", MESSAGES[&("fr", 1)]);
+        assert_eq!(None, MESSAGES.get(&("fr", 2)));
+    }
+
+    #[test]
+    fn test_get_group() {
+        let de = MESSAGES.get_group("de").collect::<Vec<_>>();
+        assert_eq!(
+            de,
+            [(&("de", 1), &"Hallo"), (&("de", 2), &"Auf Wiedersehen")]
+        );
+        assert_eq!(1, MESSAGES.get_group("fr").len());
+        assert!(MESSAGES.contains_group("en"));
+        assert!(!MESSAGES.contains_group("es"));
+        assert_eq!(0, MESSAGES.get_group("es").count());
+    }
+
+    #[test]
+    fn test_entries_are_grouped() {
+        let groups = MESSAGES.keys().map(|key| key.0).collect::<Vec<_>>();
+        assert_eq!(groups, ["en", "en", "de", "de", "fr"]);
+    }
+
+    #[test]
+    fn test_cfg() {
+        static MAP: phf::GroupedMap<(u8, u8), u32, u8> = phf_grouped_map! {
+            (1u8, 1u8) => 11,
+            #[cfg(any())]
+            (2, 1) => 21,
+            (1, 2) => 12,
+        };
+        assert_eq!(2, MAP.get_group(&1).count());
+        assert!(!MAP.contains_group(&2));
+    }
+
+    #[test]
+    fn test_empty() {
+        static MAP: phf::GroupedMap<(u8, u8), u32, u8> = phf_grouped_map! {};
+        assert!(MAP.is_empty());
+        assert_eq!(0, MAP.get_group(&1).count());
+    }
+}
+
+mod ip_prefix_map {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    use phf::phf_ip_prefix_map;
+
+    static NETWORKS: phf::IpPrefixMap<u32> = phf_ip_prefix_map! {
+        "10.0.0.0/8" => 8,
+        "10.1.0.0/16" => 16,
+        "10.1.2.3" => 32,
+        "192.168.0.0/16" | "172.16.0.0/12" => 1,
+        "::/0" => 0,
+        "2001:db8::/32" => 6,
+        #[cfg(any())]
+        "11.0.0.0/8" => 11,
+    };
+
+    fn get(addr: &str) -> Option<(u8, &'static u32)> {
+        NETWORKS.longest_match(addr.parse::<IpAddr>().unwrap())
+    }
+
+    #[test]
+    fn test_from_raw_parts() {
+        const EMPTY: phf::IpPrefixMap<u32> = phf::IpPrefixMap::from_raw_parts(&[], &[]);
+        let map = phf::IpPrefixMap::from_raw_parts(NETWORKS.v4, NETWORKS.v6);
+        assert!(EMPTY.is_empty());
+        assert_eq!(NETWORKS.len(), map.len());
+        assert_eq!(
+            Some((16, &16)),
+            map.longest_match("10.1.2.4".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_longest_match() {
+        assert_eq!(7, NETWORKS.len());
+        assert_eq!(Some((32, &32)), get("10.1.2.3"));
+        assert_eq!(Some((16, &16)), get("10.1.2.4"));
+        assert_eq!(Some((8, &8)), get("10.255.0.1"));
+        assert_eq!(Some((12, &1)), get("172.31.255.255"));
+        assert_eq!(None, get("172.32.0.0"));
+        assert_eq!(None, get("11.0.0.1"));
+        assert_eq!(Some((32, &6)), get("2001:db8::1"));
+        assert_eq!(Some((0, &0)), get("2001:db9::1"));
+    }
+
+    #[test]
+    fn test_get_by_family() {
+        assert_eq!(Some(&1), NETWORKS.get_v4(Ipv4Addr::new(192, 168, 1, 1)));
+        assert_eq!(Some(&0), NETWORKS.get_v6(Ipv6Addr::LOCALHOST));
+        // IPv4-mapped addresses are not matched against IPv4 networks
+        assert_eq!(
+            Some(&0),
+            NETWORKS.get_v6(Ipv4Addr::new(10, 0, 0, 1).to_ipv6_mapped())
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        static EMPTY: phf::IpPrefixMap<u32> = phf_ip_prefix_map! {};
+        assert!(EMPTY.is_empty());
+        assert_eq!(None, EMPTY.get(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+    }
+}
+
+mod filter {
+    use phf::{Filter, phf_filter};
+
+    #[test]
+    fn test_maybe_contains() {
+        static WORDS: Filter = phf_filter! {
+            "apple", "banana", "cherry", "durian", "elderberry",
+            #[cfg(any())]
+            "fig",
+        };
+        assert_eq!(5, WORDS.len());
+        for word in ["apple", "banana", "cherry", "durian", "elderberry"] {
+            assert!(WORDS.maybe_contains(word));
+        }
+        assert!(!WORDS.maybe_contains("fig"));
+    }
+
+    #[test]
+    fn test_false_positive_rate() {
+        static KEYS: Filter<u8> = phf_filter! {
+            0u32, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+        };
+        assert_eq!(1.0 / 256.0, KEYS.false_positive_rate());
+        assert!((0..16u32).all(|key| KEYS.maybe_contains(&key)));
+        // about 40 expected
+        let false_positives = (16..10_016u32)
+            .filter(|key| KEYS.maybe_contains(key))
+            .count();
+        assert!(false_positives < 100, "{false_positives} false positives");
+    }
+
+    #[test]
+    fn test_fingerprint_widths() {
+        static NARROW: Filter<u8> = phf_filter!(b"GET", b"PUT");
+        static WIDE: Filter<u32> = phf_filter!(b"GET", b"PUT");
+        assert!(NARROW.maybe_contains(&b"GET"[..]));
+        assert!(WIDE.maybe_contains(&b"PUT"[..]));
+        assert!(!WIDE.maybe_contains(&b"POST"[..]));
+    }
+
+    #[test]
+    fn test_empty() {
+        static EMPTY: Filter = phf_filter! {};
+        assert!(EMPTY.is_empty());
+        assert_eq!(0.0, EMPTY.false_positive_rate());
+        assert!(!EMPTY.maybe_contains("anything"));
+    }
+}
+
+#[cfg(feature = "instrument")]
+mod instrument {
+    use phf::{
+        GroupedMap, Map, OrderedMap, OrderedSet, RangeMap, Set, phf_grouped_map, phf_map,
+        phf_ordered_map, phf_ordered_set, phf_range_map, phf_set,
+    };
+
+    static MAP: Map<&'static str, u32> = phf_map! {
+        "hot" => 0,
+        "cold" => 1,
+    };
+
+    static SET: Set<u32> = phf_set! { 1u32, 2, 3 };
+
+    #[test]
+    fn test_map_stats() {
+        for _ in 0..3 {
+            MAP.get("hot");
+        }
+        MAP.get("missing");
+        MAP.contains_key("nope");
+
+        let stats = MAP.stats();
+        assert!(stats.name().starts_with("test::instrument:"));
+        assert_eq!(2, stats.misses());
+        assert_eq!(3, stats.total_hits());
+        let mut hits = stats.hits().to_vec();
+        hits.sort();
+        assert_eq!(hits, [(&"cold", 0), (&"hot", 3)]);
+        assert_eq!(stats.unused_keys().collect::<Vec<_>>(), [&"cold"]);
+
+        let table = phf::instrument::snapshot()
+            .into_iter()
+            .find(|table| table.name() == stats.name())
+            .unwrap();
+        assert_eq!(3, table.total_hits());
+        assert_eq!(1, table.unused_entries());
+
+        let mut dump = Vec::new();
+        phf::instrument::dump(&mut dump).unwrap();
+        let dump = String::from_utf8(dump).unwrap();
+        let line = format!("{}: 3 hits, 2 misses, 1 of 2 entries unused", stats.name());
+        assert!(dump.lines().any(|l| l == line), "{}", dump);
+    }
+
+    #[test]
+    fn test_set_stats() {
+        assert!(SET.contains(&2));
+        assert!(!SET.contains(&4));
+        let stats = SET.stats();
+        assert_eq!(1, stats.total_hits());
+        assert_eq!(1, stats.misses());
+        assert_eq!(2, stats.unused_keys().count());
+    }
+
+    #[test]
+    fn test_ordered_stats() {
+        static MAP: OrderedMap<&'static str, u32> = phf_ordered_map! {
+            "first" => 0,
+            "second" => 1,
+        };
+        static SET: OrderedSet<u32> = phf_ordered_set! { 1u32, 2 };
+        assert_eq!(Some(1), MAP.get_index("second"));
+        assert_eq!(None, MAP.get("third"));
+        let stats = MAP.stats();
+        assert_eq!(stats.hits(), [(&"first", 0), (&"second", 1)]);
+        assert_eq!(1, stats.misses());
+
+        assert!(SET.contains(&1));
+        assert_eq!(SET.stats().hits(), [(&1, 1), (&2, 0)]);
+    }
+
+    #[test]
+    fn test_grouped_stats() {
+        static MAP: GroupedMap<(&'static str, u32), &'static str, &'static str> = phf_grouped_map! {
+            ("en", 0u32) => "Hello",
+            ("de", 0) => "Hallo",
+            ("en", 1) => "Goodbye",
+        };
+        assert_eq!(Some(&"Hallo"), MAP.get(&("de", 0)));
+        assert_eq!(2, MAP.get_group("en").count());
+        assert!(!MAP.contains_group("fr"));
+        assert_eq!(1, MAP.stats().total_hits());
+        assert_eq!(0, MAP.stats().misses());
+        let groups = MAP.group_stats();
+        assert_eq!(1, groups.total_hits());
+        assert_eq!(1, groups.misses());
+    }
+
+    #[test]
+    fn test_range_stats() {
+        static MAP: RangeMap<u32, char> = phf_range_map! {
+            0u32..=9 => 'd',
+            10 => 'x',
+            20..=29 => 't',
+        };
+        assert_eq!(Some(&'d'), MAP.get(&5));
+        assert_eq!(Some(&'x'), MAP.get(&10));
+        assert_eq!(Some(&'d'), MAP.get(&0));
+        assert_eq!(None, MAP.get(&15));
+        let stats = MAP.stats();
+        assert_eq!(stats.hits(), [(&10, 1), (&0, 2), (&20, 0)]);
+        assert_eq!(1, stats.misses());
+    }
+
+    #[test]
+    fn test_searches_not_counted() {
+        static WORDS: phf::suggest::SuggestMap<&'static str, u32> = phf_map! {
+            #![suggest]
+            "alpha" => 0,
+            "beta" => 1,
+        };
+        assert_eq!(1, WORDS.find_iter("an alpha and more").count());
+        assert_eq!(1, WORDS.suggest("alhpa", 2).len());
+        assert_eq!(1, WORDS.map.suggest("alhpa", 2).len());
+        let stats = WORDS.stats();
+        assert_eq!(0, stats.total_hits());
+        assert_eq!(0, stats.misses());
+    }
+}
+
+mod layout {
+    use phf::{phf_map, phf_ordered_map, phf_ordered_set, phf_set};
+    use std::mem::size_of;
+
+    static MAP: phf::Map<u32, u64> = phf_map! {
+        1u32 => 10,
+        2u32 => 20,
+        3u32 => 30,
+        4u32 => 40,
+        5u32 => 50,
+    };
+
+    static SET: phf::Set<u32> = phf_set! {
+        1u32,
+        2u32,
+        3u32,
+    };
+
+    static ORDERED_MAP: phf::OrderedMap<u32, u64> = phf_ordered_map! {
+        1u32 => 10,
+        2u32 => 20,
+    };
+
+    static ORDERED_SET: phf::OrderedSet<u32> = phf_ordered_set! {
+        1u32,
+        2u32,
+    };
+
+    #[cfg(not(feature = "ptrhash"))]
+    const DISPLACEMENT_SIZE: usize = size_of::<(u32, u32)>();
+    #[cfg(feature = "ptrhash")]
+    const DISPLACEMENT_SIZE: usize = size_of::<u8>();
+
+    #[test]
+    fn test_memory_usage() {
+        let usage = MAP.memory_usage();
+        let stats = MAP.layout_stats();
+        assert_eq!(5 * size_of::<(u32, u64)>(), usage.entries());
+        assert_eq!(stats.buckets() * DISPLACEMENT_SIZE, usage.displacements());
+        assert_eq!(stats.remap_len() * size_of::<u32>(), usage.remap());
+        assert_eq!(0, usage.idxs());
+        #[cfg(not(feature = "instrument"))]
+        assert_eq!(0, usage.auxiliary());
+        #[cfg(feature = "instrument")]
+        assert!(usage.auxiliary() >= 5 * size_of::<u64>());
+        assert_eq!(
+            usage.entries() + usage.displacements() + usage.remap() + usage.auxiliary(),
+            usage.total()
+        );
+
+        assert_eq!(3 * size_of::<u32>(), SET.memory_usage().entries());
+
+        let usage = ORDERED_MAP.memory_usage();
+        assert_eq!(2 * size_of::<(u32, u64)>(), usage.entries());
+        assert_eq!(2 * size_of::<usize>(), usage.idxs());
+        assert_eq!(ORDERED_SET.memory_usage().idxs(), usage.idxs());
+
+        assert_eq!(0, phf::Map::<u32, u64>::new().memory_usage().total());
+    }
+
+    #[test]
+    fn test_auxiliary_memory_usage() {
+        static WORDS: phf::find::FindMap<&'static str, u32> = phf_map! {
+            #![find]
+            "a" => 1,
+            "bc" => 2,
+        };
+        static COMMANDS: phf::suggest::SuggestSet<&'static str> = phf_set! {
+            #![suggest]
+            "build",
+            "bench",
+        };
+
+        let usage = WORDS.memory_usage();
+        assert_eq!(
+            WORDS.map.memory_usage().auxiliary() + size_of::<phf::find::KeyLengths>(),
+            usage.auxiliary()
+        );
+        assert_eq!(WORDS.map.memory_usage().entries(), usage.entries());
+
+        let usage = COMMANDS.memory_usage();
+        let index = COMMANDS.index().memory_usage();
+        assert!(index.entries() > 0);
+        assert!(index.auxiliary() > size_of::<phf::suggest::SuggestIndex>());
+        assert_eq!(
+            COMMANDS.set.memory_usage().total() + index.total(),
+            usage.total()
+        );
+    }
+
+    #[test]
+    fn test_layout_stats() {
+        let stats = MAP.layout_stats();
+        #[cfg(not(feature = "ptrhash"))]
+        assert!(stats.format().starts_with("chd/"));
+        #[cfg(feature = "ptrhash")]
+        assert!(stats.format().starts_with("ptrhash/"));
+        assert_eq!(5, stats.len());
+        assert!(stats.buckets() > 0);
+        let histogram = stats.displacement_histogram();
+        assert_eq!(stats.buckets(), histogram.iter().sum::<usize>());
+        assert_ne!(Some(&0), histogram.last().filter(|_| histogram.len() > 1));
+        assert_eq!(
+            histogram.len() - 1,
+            (u32::BITS - stats.max_displacement().leading_zeros()) as usize
+        );
+        #[cfg(not(feature = "ptrhash"))]
+        assert_eq!(0, stats.remap_len());
+
+        assert_eq!(3, SET.layout_stats().len());
+        assert_eq!(2, ORDERED_MAP.layout_stats().len());
+        assert_eq!(2, ORDERED_SET.layout_stats().len());
+
+        let empty = phf::Map::<u32, u64>::new().layout_stats();
+        assert!(empty.is_empty());
+        assert_eq!(0, empty.buckets());
+        assert_eq!([0], empty.displacement_histogram());
+    }
+}
+
+mod array_map {
+    use phf::ArrayMap;
+    use phf::array_map::{self, BuildError, Scratch};
+
+    #[test]
+    fn test_array_map() {
+        let map = ArrayMap::new([("a", 1), ("b", 2), ("c", 3), ("d", 4)]).unwrap();
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.get("a"), Some(&1));
+        assert_eq!(map.get("d"), Some(&4));
+        assert_eq!(map.get("e"), None);
+        assert_eq!(map.get_key("b"), Some(&"b"));
+        let mut keys = map.keys().copied().collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_owned_keys() {
+        use std::borrow::Cow;
+        use std::rc::Rc;
+        use std::sync::Arc;
+
+        let map = ArrayMap::new([(Box::<str>::from("a"), 1), ("b".into(), 2)]).unwrap();
+        assert_eq!(map.get("b"), Some(&2));
+        let map = ArrayMap::new([(Arc::<str>::from("a"), 1), ("b".into(), 2)]).unwrap();
+        assert_eq!(map.get("a"), Some(&1));
+        let map = ArrayMap::new([(Rc::<str>::from("a"), 1), ("b".into(), 2)]).unwrap();
+        assert_eq!(map.get("c"), None);
+        let map = ArrayMap::new([(Cow::<str>::Owned("a".into()), 1), ("b".into(), 2)]).unwrap();
+        assert_eq!(map.get("a"), Some(&1));
+        let map = ArrayMap::new([(Box::<[u8]>::from(&b"a"[..]), 1), (b"b"[..].into(), 2)]).unwrap();
+        assert_eq!(map.get(&b"b"[..]), Some(&2));
+        let map = ArrayMap::new([(Cow::<[u8]>::Borrowed(b"a"), 1), (b"b"[..].into(), 2)]).unwrap();
+        assert_eq!(map.get(&b"a"[..]), Some(&1));
+    }
+
+    #[test]
+    fn test_empty() {
+        let map = ArrayMap::<u32, u32, 0>::new([]).unwrap();
+        assert!(map.is_empty());
+        assert_eq!(map.get(&0), None);
+    }
+
+    #[test]
+    fn test_with_disps() {
+        let entries = [(1u32, 'a'), (2, 'b'), (3, 'c'), (4, 'd'), (5, 'e')];
+        let map = ArrayMap::<_, _, 5, { array_map::disps_len(5) }>::with_disps(entries).unwrap();
+        assert_eq!(map.get(&4), Some(&'d'));
+        assert_eq!(map.get(&6), None);
+        assert_eq!(
+            std::mem::size_of_val(&map),
+            std::mem::size_of::<ArrayMap<u32, char, 5>>() - 3 * std::mem::size_of::<(u32, u32)>()
+        );
+
+        let map = ArrayMap::<u32, char, 0, 0>::with_disps([]).unwrap();
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_build_in_place() {
+        let mut entries = (0..1000u32).map(|i| (i, i * 2)).collect::<Vec<_>>();
+        let mut disps = vec![(0, 0); array_map::disps_len(entries.len())];
+        let mut scratch = vec![Scratch::EMPTY; entries.len()];
+        let map = array_map::build_in_place(&mut entries, &mut disps, &mut scratch).unwrap();
+        for i in 0..1000 {
+            assert_eq!(map.get(&i), Some(&(i * 2)));
+        }
+        assert_eq!(map.get(&1000), None);
+
+        // The scratch buffer can be reused.
+        let mut entries = [(1u8, ()), (2, ())];
+        let map = array_map::build_in_place(&mut entries, &mut disps, &mut scratch).unwrap();
+        assert!(map.contains_key(&2));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            ArrayMap::new([("a", 1), ("b", 2), ("a", 3)]).unwrap_err(),
+            BuildError::DuplicateKey
+        );
+
+        let mut entries = [("a", 1), ("b", 2), ("c", 3), ("d", 4)];
+        let mut scratch = [Scratch::EMPTY; 4];
+        assert_eq!(
+            array_map::build_in_place(&mut entries, &mut [(0, 0)], &mut scratch).unwrap_err(),
+            BuildError::BufferTooSmall
+        );
+        assert_eq!(
+            array_map::build_in_place(&mut entries, &mut [(0, 0); 2], &mut scratch[..3])
+                .unwrap_err(),
+            BuildError::BufferTooSmall
+        );
+        assert_eq!(entries, [("a", 1), ("b", 2), ("c", 3), ("d", 4)]);
+    }
+
+    #[cfg(not(feature = "ptrhash"))]
+    #[test]
+    fn test_into_map() {
+        let entries = Box::leak(Box::new([("x", 1), ("y", 2)]));
+        let disps = Box::leak(Box::new([(0, 0); array_map::disps_len(2)]));
+        let map = array_map::build_in_place(entries, disps, &mut [Scratch::EMPTY; 2])
+            .unwrap()
+            .into_map();
+        assert_eq!(map.get("y"), Some(&2));
+        assert_eq!(map.get("z"), None);
+    }
+}
+
+#[cfg(feature = "dynamic")]
+mod dynamic {
+    use phf::dynamic::Map;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_insert_remove() {
+        let mut map = Map::new();
+        assert!(map.is_empty());
+        assert_eq!(map.get("a"), None);
+        assert_eq!(map.insert("a", 1), None);
+        assert_eq!(map.insert("b", 2), None);
+        assert_eq!(map.insert("a", 3), Some(1));
+        assert_eq!(map.get("a"), Some(&3));
+        assert_eq!(map.len(), 2);
+
+        map.rebuild();
+        assert_eq!(map.overflow_len(), 0);
+        assert_eq!(map.remove("a"), Some(3));
+        assert_eq!(map.remove("a"), None);
+        assert_eq!(map.get("a"), None);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.insert("a", 4), None);
+        *map.get_mut("a").unwrap() += 1;
+        assert_eq!(map.get_entry("a"), Some((&"a", &5)));
+        assert_eq!(map.overflow_len(), 0);
+    }
+
+    #[test]
+    fn test_against_hash_map() {
+        let mut map = (0..1000u32).map(|i| (i, i)).collect::<Map<_, _>>();
+        let mut expected = (0..1000u32).map(|i| (i, i)).collect::<HashMap<_, _>>();
+        assert_eq!(map.overflow_len(), 0);
+
+        // Enough updates to rebuild the table several times.
+        let mut x = 1u32;
+        for _ in 0..5000 {
+            x = x.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let key = (x >> 8) % 2000;
+            if x & 1 == 0 {
+                assert_eq!(map.insert(key, x), expected.insert(key, x));
+            } else {
+                assert_eq!(map.remove(&key), expected.remove(&key));
+            }
+        }
+
+        assert_eq!(map.len(), expected.len());
+        assert_eq!(map.entries().len(), expected.len());
+        for key in 0..2000 {
+            assert_eq!(map.get(&key), expected.get(&key));
+        }
+        let mut entries = map.entries().map(|(&k, &v)| (k, v)).collect::<Vec<_>>();
+        let mut expected = expected.into_iter().collect::<Vec<_>>();
+        entries.sort();
+        expected.sort();
+        assert_eq!(entries, expected);
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut map = (0..100u32).map(|i| (i, i)).collect::<Map<_, _>>();
+        assert_eq!(map.overflow_len(), 0);
+
+        // A small batch stays in the overflow area, like single inserts do.
+        map.extend((100..110).map(|i| (i, i)));
+        assert_eq!(map.overflow_len(), 10);
+        assert_eq!(map.get(&105), Some(&105));
+
+        map.extend((110..200).map(|i| (i, i)));
+        assert!(map.overflow_len() <= 16);
+        assert_eq!(map.len(), 200);
+        assert!((0..200).all(|i| map.get(&i) == Some(&i)));
+    }
+
+    #[test]
+    fn test_borrowed_lookup() {
+        let map = [(String::from("a"), 1), (String::from("b"), 2)]
+            .into_iter()
+            .collect::<Map<_, _>>();
+        assert_eq!(map.get("b"), Some(&2));
+        assert!(!map.contains_key("c"));
+    }
+}
+
+#[cfg(feature = "proptest")]
+mod proptest {
+    use phf::{phf_map, phf_set};
+    use proptest::prelude::*;
+
+    static STATUS: phf::Map<&'static str, u16> = phf_map! {
+        "ok" => 200,
+        "created" => 201,
+        "not found" => 404,
+    };
+
+    static PORTS: phf::Set<u16> = phf_set! {
+        80u16,
+        443u16,
+    };
+
+    proptest! {
+        #[test]
+        fn test_keys(key in phf::proptest::keys(&STATUS)) {
+            prop_assert!(STATUS.contains_key(*key));
+        }
+
+        #[test]
+        fn test_entries((key, value) in phf::proptest::entries(&STATUS)) {
+            prop_assert_eq!(Some(value), STATUS.get(*key));
+        }
+
+        #[test]
+        fn test_values(value in phf::proptest::values(&STATUS)) {
+            prop_assert!(STATUS.values().any(|v| v == value));
+        }
+
+        #[test]
+        fn test_elements(port in phf::proptest::elements(&PORTS)) {
+            prop_assert!(PORTS.contains(port));
+        }
+
+        #[test]
+        fn test_near_miss_keys(key in phf::proptest::near_miss_keys(&STATUS)) {
+            prop_assert!(STATUS.keys().any(|k| key.len() <= k.len() + 1));
+        }
+
+        #[test]
+        fn test_near_miss_elements(port in phf::proptest::near_miss_elements(&PORTS)) {
+            prop_assert!(!PORTS.contains(&port));
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary {
+    use arbitrary::Unstructured;
+    use phf::near_miss::NearMiss;
+    use phf::{phf_map, phf_set};
+
+    static STATUS: phf::Map<&'static str, u16> = phf_map! {
+        "ok" => 200,
+        "created" => 201,
+        "not found" => 404,
+    };
+
+    static EMPTY: phf::Set<u32> = phf_set! {};
+
+    #[test]
+    fn test_sample() {
+        let data = (0..=255).collect::<Vec<u8>>();
+        let mut u = Unstructured::new(&data);
+        for _ in 0..10 {
+            let (key, value) = phf::arbitrary::entry(&STATUS, &mut u).unwrap();
+            assert_eq!(Some(value), STATUS.get(*key));
+            assert!(STATUS.contains_key(*phf::arbitrary::key(&STATUS, &mut u).unwrap()));
+            let near_miss = phf::arbitrary::near_miss_key(&STATUS, &mut u).unwrap();
+            assert!(STATUS.keys().any(|k| near_miss.len() <= k.len() + 1));
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut u = Unstructured::new(&[1, 2, 3]);
+        assert!(phf::arbitrary::element(&EMPTY, &mut u).is_err());
+    }
+
+    #[test]
+    fn test_near_miss() {
+        for seed in 0..64 {
+            assert_ne!("", "".near_miss(seed));
+            assert_ne!(7u8, 7u8.near_miss(seed));
+            assert_ne!('a', 'a'.near_miss(seed));
+        }
+        assert_eq!("bc", "abc".near_miss(0));
+        assert_eq!("abcbac", "abcabc".near_miss(3 + (3 << 3)));
+        assert_eq!("abcAbc", "abcabc".near_miss(4 + (3 << 3)));
+        assert_eq!(b"abcc", &*b"abc".near_miss(6 + (2 << 3)));
+    }
+
+    #[cfg(feature = "dynamic")]
+    #[test]
+    fn test_dynamic_map() {
+        use arbitrary::Arbitrary;
+
+        // Odd bytes keep `arbitrary_iter` going.
+        let data = (0..=255).map(|b| b | 1).collect::<Vec<u8>>();
+        let mut u = Unstructured::new(&data);
+        let map = phf::dynamic::Map::<u8, u16>::arbitrary(&mut u).unwrap();
+        assert!(!map.is_empty());
+        for (k, v) in &map {
+            assert_eq!(map.get(k), Some(v));
+        }
+    }
+}
+
+mod lazy_map {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use phf::{LazyMap, phf_lazy_map};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    fn answer() -> Vec<u32> {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        vec![4, 2]
+    }
+
+    static MAP: LazyMap<&'static str, Vec<u32>> = phf_lazy_map! {
+        "answer" => answer,
+        "empty" => Vec::new,
+        "range" => || (1..=3).collect(),
+        #[cfg(any())]
+        "disabled" => || vec![0],
+    };
+
+    #[test]
+    fn test_get() {
+        assert_eq!(3, MAP.len());
+        assert!(MAP.contains_key("answer"));
+        assert!(!MAP.contains_key("disabled"));
+        assert_eq!(None, MAP.get_if_initialized("range"));
+        assert_eq!(Some(&vec![1, 2, 3]), MAP.get("range"));
+        assert_eq!(Some(&vec![1, 2, 3]), MAP.get_if_initialized("range"));
+        assert_eq!(&[4, 2], &MAP["answer"][..]);
+        assert_eq!(&[4, 2], &MAP["answer"][..]);
+        assert_eq!(1, CALLS.load(Ordering::SeqCst));
+        assert_eq!(None, MAP.get("missing"));
+    }
+
+    #[test]
+    fn test_entries() {
+        static ENTRIES: LazyMap<u8, String> = phf_lazy_map! {
+            1u8 => || "one".to_owned(),
+            2 => || "two".to_owned(),
+        };
+        assert_eq!(2, ENTRIES.keys().count());
+        assert_eq!(None, ENTRIES.get_if_initialized(&1));
+        let mut entries = ENTRIES
+            .entries()
+            .map(|(k, v)| (*k, v.as_str()))
+            .collect::<Vec<_>>();
+        entries.sort();
+        assert_eq!(entries, [(1, "one"), (2, "two")]);
+        assert!(ENTRIES.get_if_initialized(&2).is_some());
+    }
+
+    #[test]
+    fn test_empty() {
+        static EMPTY: LazyMap<u32, String> = phf_lazy_map! {};
+        assert!(EMPTY.is_empty());
+        assert_eq!(None, EMPTY.get(&0));
+    }
+}
+
+mod map_with_default {
+    use phf::{MapWithDefault, OrderedMap, phf_map, phf_ordered_map};
+
+    static METHODS: MapWithDefault<&'static str, u32> = phf_map! {
+        "GET" => 1,
+        "POST" => 2,
+        #[cfg(any())]
+        "PUT" => 3,
+        _ => 0,
+    };
+
+    static ORDERED: MapWithDefault<u8, &'static str, OrderedMap<u8, &'static str>> = phf_ordered_map! {
+        3u8 => "three",
+        1u8 => "one",
+        _ => "other",
+    };
+
+    static ONLY_DEFAULT: MapWithDefault<u32, u32> = phf_map! {
+        _ => 7,
+    };
+
+    #[test]
+    fn test_get() {
+        assert_eq!(2, METHODS.len());
+        assert_eq!(&1, METHODS.get("GET"));
+        assert_eq!(0, METHODS["PUT"]);
+        assert_eq!(0, METHODS["BREW"]);
+        assert_eq!(Some((&"POST", &2)), METHODS.get_entry("POST"));
+        assert_eq!(None, METHODS.get_entry("PUT"));
+        assert!(METHODS.contains_key("GET"));
+        assert!(!METHODS.contains_key("BREW"));
+        assert_eq!(&0, METHODS.default_value());
+    }
+
+    #[test]
+    fn test_ordered() {
+        assert_eq!("three", ORDERED[&3]);
+        assert_eq!("other", ORDERED[&2]);
+        assert_eq!(vec![&3, &1], ORDERED.map().keys().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_only_default() {
+        assert!(ONLY_DEFAULT.is_empty());
+        assert_eq!(7, ONLY_DEFAULT[&1]);
+    }
+}
+
+mod range_map {
+    use phf::{RangeMap, phf_range_map};
+
+    static CLASSES: RangeMap<char, u8> = phf_range_map! {
+        'a'..='z' | 'A'..='Z' => 1,
+        '0'..='9' => 2,
+        '_' => 3,
+        ' ' | '\t' => 4,
+        #[cfg(any())]
+        '-' => 5,
+    };
+
+    #[test]
+    fn test_get() {
+        assert_eq!(6, CLASSES.len());
+        assert_eq!(Some(&1), CLASSES.get(&'a'));
+        assert_eq!(Some(&1), CLASSES.get(&'z'));
+        assert_eq!(Some(&1), CLASSES.get(&'M'));
+        assert_eq!(Some(&2), CLASSES.get(&'5'));
+        assert_eq!(Some(&3), CLASSES.get(&'_'));
+        assert_eq!(Some(&4), CLASSES.get(&'\t'));
+        assert_eq!(None, CLASSES.get(&'-'));
+        assert_eq!(None, CLASSES.get(&'['));
+        assert_eq!(None, CLASSES.get(&'\0'));
+        assert_eq!(None, CLASSES.get(&'\u{10ffff}'));
+        assert_eq!(Some((&'A', &'Z', &1)), CLASSES.get_range(&'Q'));
+    }
+
+    #[test]
+    fn test_iter() {
+        let starts = CLASSES
+            .iter()
+            .map(|(start, _, _)| *start)
+            .collect::<String>();
+        assert_eq!("\t 0A_a", starts);
+        assert_eq!(6, CLASSES.iter().len());
+    }
+
+    #[test]
+    fn test_integer_keys() {
+        static STATUS: RangeMap<u16, &'static str> = phf_range_map! {
+            100u16..=199 => "informational",
+            200..=299 => "success",
+            418 => "teapot",
+            400..=417 | 419..=499 => "client error",
+        };
+        assert_eq!(Some(&"success"), STATUS.get(&204));
+        assert_eq!(Some(&"teapot"), STATUS.get(&418));
+        assert_eq!(Some(&"client error"), STATUS.get(&404));
+        assert_eq!(None, STATUS.get(&500));
+    }
+
+    #[test]
+    fn test_empty() {
+        static EMPTY: RangeMap<i32, ()> = phf_range_map! {};
+        assert!(EMPTY.is_empty());
+        assert_eq!(None, EMPTY.get(&0));
+        assert_eq!(0, EMPTY.iter().count());
+    }
+}
+
 mod ordered_map {
     use phf::phf_ordered_map;
 
@@ -570,6 +2047,22 @@ mod ordered_map {
         "foo" => 10
     );
 
+    #[test]
+    fn test_from_raw_parts() {
+        static MAP: phf::OrderedMap<&'static str, isize> = phf_ordered_map!(
+            "foo" => 10,
+            "bar" => 11,
+        );
+        #[cfg(not(feature = "ptrhash"))]
+        let map = phf::OrderedMap::from_raw_parts(MAP.key, MAP.disps, MAP.idxs, MAP.entries);
+        #[cfg(feature = "ptrhash")]
+        let map =
+            phf::OrderedMap::from_raw_parts(MAP.key, MAP.pilots, MAP.remap, MAP.idxs, MAP.entries);
+        assert_eq!(Some(&10), map.get("foo"));
+        assert_eq!(Some(1), map.get_index("bar"));
+        assert_eq!(None, map.get("baz"));
+    }
+
     #[test]
     fn test_two() {
         static MAP: phf::OrderedMap<&'static str, isize> = phf_ordered_map!(
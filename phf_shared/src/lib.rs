@@ -8,18 +8,27 @@
 #[cfg(feature = "std")]
 extern crate std as core;
 
+use core::cmp::Ordering;
+use core::ffi::CStr;
 use core::fmt;
 use core::hash::{Hash, Hasher};
-use core::num::Wrapping;
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use core::num::{
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize, NonZeroU8,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize, Wrapping,
+};
+use core::time::Duration;
 use siphasher::sip128::{Hash128, Hasher128, SipHasher13};
 
 mod hasher;
 use hasher::PortableSipHasher;
 
+pub mod normalize;
 #[cfg(feature = "ptrhash")]
 pub mod ptrhash;
 
 #[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Hashes {
     pub g: u32,
     pub f1: u32,
@@ -31,17 +40,59 @@ pub struct Hashes {
 /// Makes experimentation easier by only needing to be updated here.
 pub type HashKey = u64;
 
+/// Identifies the hash functions and table layout that tables are generated
+/// for.
+///
+/// Generated tables embed the table format of the `phf_shared` they were
+/// generated with, and `phf` rejects tables whose format differs from its own
+/// at compile time. It changes whenever a release changes how keys are
+/// hashed or how tables are laid out, and with the `ptrhash` feature.
+#[cfg(not(feature = "ptrhash"))]
+pub const TABLE_FORMAT: &str = "chd/sip13-128/1";
+
+/// Identifies the hash functions and table layout that tables are generated
+/// for.
+///
+/// Generated tables embed the table format of the `phf_shared` they were
+/// generated with, and `phf` rejects tables whose format differs from its own
+/// at compile time. It changes whenever a release changes how keys are
+/// hashed or how tables are laid out, and with the `ptrhash` feature.
+#[cfg(feature = "ptrhash")]
+pub const TABLE_FORMAT: &str = "ptrhash/sip13-64/1";
+
 #[inline]
 pub fn displace(f1: u32, f2: u32, d1: u32, d2: u32) -> u32 {
     (Wrapping(d2) + Wrapping(f1) * Wrapping(d1) + Wrapping(f2)).0
 }
 
 /// `key` is from `phf_generator::HashState`.
+#[cfg(not(feature = "size"))]
 #[inline]
 pub fn hash<T: ?Sized + PhfHash>(x: &T, key: &HashKey) -> Hashes {
     let mut hasher = PortableSipHasher::new(SipHasher13::new_with_keys(0, *key));
     x.phf_hash(&mut hasher);
+    hashes(&hasher)
+}
 
+/// `key` is from `phf_generator::HashState`.
+#[cfg(feature = "size")]
+#[inline]
+pub fn hash<T: ?Sized + PhfHash>(x: &T, key: &HashKey) -> Hashes {
+    hash_dyn(key, &mut |mut state| x.phf_hash(&mut state))
+}
+
+/// Hashes the bytes `feed` writes, through a `dyn Hasher` so that the SipHash
+/// implementation is shared by every key type.
+#[cfg(feature = "size")]
+#[inline(never)]
+fn hash_dyn(key: &HashKey, feed: &mut dyn FnMut(&mut dyn Hasher)) -> Hashes {
+    let mut hasher = PortableSipHasher::new(SipHasher13::new_with_keys(0, *key));
+    feed(&mut hasher);
+    hashes(&hasher)
+}
+
+#[inline]
+fn hashes(hasher: &PortableSipHasher<SipHasher13>) -> Hashes {
     let Hash128 {
         h1: lower,
         h2: upper,
@@ -65,6 +116,26 @@ pub fn get_index(hashes: &Hashes, disps: &[(u32, u32)], len: usize) -> u32 {
     displace(hashes.f1, hashes.f2, d1, d2) % (len as u32)
 }
 
+/// Return bits for a fingerprint of the key `hashes` was computed from.
+///
+/// The bits are mixed from the whole hash so that keys landing at the same
+/// index are unlikely to share a fingerprint.
+#[inline]
+pub fn fingerprint(hashes: &Hashes) -> u64 {
+    let lower = (u64::from(hashes.g) << 32) | u64::from(hashes.f1);
+    fmix64(lower ^ u64::from(hashes.f2).wrapping_mul(0x9e3779b97f4a7c15))
+}
+
+/// The MurmurHash3 64-bit finalizer.
+#[inline]
+pub(crate) fn fmix64(mut k: u64) -> u64 {
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xff51afd7ed558ccd);
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xc4ceb9fe1a85ec53);
+    k ^ (k >> 33)
+}
+
 /// A trait implemented by types which can be used in PHF data structures.
 ///
 /// This differs from the standard library's `Hash` trait in that `PhfHash`'s
@@ -158,6 +229,150 @@ where
     }
 }
 
+/// A byte string split into several chunks.
+///
+/// `ByteChunks` hashes identically to the concatenation of its chunks as a
+/// `[u8]` or `str`, so it can be used to look up such keys without joining the
+/// chunks first. The chunk iterator is cloned for every pass over the chunks,
+/// so it should be cheap to clone.
+#[derive(Clone, Debug)]
+pub struct ByteChunks<I> {
+    chunks: I,
+}
+
+impl<'a, I> ByteChunks<I>
+where
+    I: Iterator<Item = &'a [u8]> + Clone,
+{
+    /// Wraps an iterator over the chunks of a byte string.
+    pub fn new(chunks: I) -> Self {
+        Self { chunks }
+    }
+
+    /// Returns the total length of the chunks in bytes.
+    pub fn len(&self) -> usize {
+        self.chunks.clone().map(<[u8]>::len).sum()
+    }
+
+    /// Returns `true` if the concatenated chunks are empty.
+    pub fn is_empty(&self) -> bool {
+        self.chunks.clone().all(<[u8]>::is_empty)
+    }
+
+    /// Returns `true` if the concatenated chunks are equal to `bytes`.
+    pub fn eq_bytes(&self, mut bytes: &[u8]) -> bool {
+        for chunk in self.chunks.clone() {
+            match bytes.strip_prefix(chunk) {
+                Some(rest) => bytes = rest,
+                None => return false,
+            }
+        }
+        bytes.is_empty()
+    }
+}
+
+impl<'a, I> PhfHash for ByteChunks<I>
+where
+    I: Iterator<Item = &'a [u8]> + Clone,
+{
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        // Mirror `u8::phf_hash_slice`: the length prefix covers the whole
+        // byte string, and the hasher is fed the chunks back to back.
+        state.write_u64(self.len() as u64);
+        for chunk in self.chunks.clone() {
+            state.write(chunk);
+        }
+    }
+}
+
+/// A string or byte string key compared and hashed ignoring ASCII case.
+///
+/// `AsciiNoCase` hashes its bytes as if they were lowercased, without
+/// allocating, so it works on `no_std` and doesn't need the `unicase` or
+/// `uncased` features. Keys built from `str` and `[u8]` hash the same way, and
+/// a map keyed by `AsciiNoCase<&str>` can be looked up with an
+/// `AsciiNoCase<&[u8]>` and vice versa. Non-ASCII bytes are compared exactly.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AsciiNoCase<S: ?Sized>(pub S);
+
+impl<S> AsciiNoCase<S> {
+    /// Wraps a string or byte string.
+    pub const fn new(s: S) -> Self {
+        AsciiNoCase(s)
+    }
+
+    /// Returns the wrapped string or byte string.
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<S: AsRef<[u8]> + ?Sized> AsciiNoCase<S> {
+    fn bytes(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl<S: AsRef<[u8]> + ?Sized, T: AsRef<[u8]> + ?Sized> PartialEq<AsciiNoCase<T>>
+    for AsciiNoCase<S>
+{
+    fn eq(&self, other: &AsciiNoCase<T>) -> bool {
+        self.bytes().eq_ignore_ascii_case(other.bytes())
+    }
+}
+
+impl<S: AsRef<[u8]> + ?Sized> Eq for AsciiNoCase<S> {}
+
+impl<S: AsRef<[u8]> + ?Sized> Hash for AsciiNoCase<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.phf_hash(state)
+    }
+}
+
+impl<S: AsRef<[u8]> + ?Sized> PhfHash for AsciiNoCase<S> {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        // Hash like the lowercased `[u8]`, lowercasing through a small buffer
+        // instead of an allocation.
+        let bytes = self.bytes();
+        state.write_u64(bytes.len() as u64);
+        let mut buf = [0; 64];
+        for chunk in bytes.chunks(buf.len()) {
+            let buf = &mut buf[..chunk.len()];
+            buf.copy_from_slice(chunk);
+            buf.make_ascii_lowercase();
+            state.write(buf);
+        }
+    }
+}
+
+impl<S: FmtConst + ?Sized> FmtConst for AsciiNoCase<S> {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AsciiNoCase(")?;
+        self.0.fmt_const(f)?;
+        f.write_str(")")
+    }
+}
+
+impl<'b, 'a: 'b, S: ?Sized + 'a> PhfBorrow<AsciiNoCase<&'b S>> for AsciiNoCase<&'a S> {
+    fn borrow(&self) -> &AsciiNoCase<&'b S> {
+        self
+    }
+}
+
+impl<'b> PhfEq<AsciiNoCase<&'b [u8]>> for AsciiNoCase<&str> {
+    fn phf_eq(&self, other: &AsciiNoCase<&'b [u8]>) -> bool {
+        self == other
+    }
+}
+
+impl<'b> PhfEq<AsciiNoCase<&'b str>> for AsciiNoCase<&[u8]> {
+    fn phf_eq(&self, other: &AsciiNoCase<&'b str>) -> bool {
+        self == other
+    }
+}
+
 /// Create an impl of `FmtConst` delegating to `fmt::Debug` for types that can deal with it.
 ///
 /// Ideally with specialization this could be just one default impl and then specialized where
@@ -244,6 +459,57 @@ impl<T: PhfHash> PhfHash for Vec<T> {
     }
 }
 
+/// Implements `PhfHash`, `PhfBorrow` and `FmtConst` for an owned or shared
+/// form of `str` or `[u8]`, delegating to the borrowed type. Types without a
+/// `const` constructor print as the borrowed type, like `String` and `Vec`.
+macro_rules! owned_impl (
+    ($ty:ty => $borrowed:ty) => (
+        #[cfg(feature = "std")]
+        impl PhfHash for $ty {
+            #[inline]
+            fn phf_hash<H: Hasher>(&self, state: &mut H) {
+                (**self).phf_hash(state)
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl PhfBorrow<$borrowed> for $ty {
+            fn borrow(&self) -> &$borrowed {
+                self
+            }
+        }
+    );
+    ($ty:ty => $borrowed:ty, delegate_fmt) => (
+        owned_impl!($ty => $borrowed);
+
+        #[cfg(feature = "std")]
+        impl FmtConst for $ty {
+            fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                (**self).fmt_const(f)
+            }
+        }
+    );
+    ($ty:ty => $borrowed:ty, cow) => (
+        owned_impl!($ty => $borrowed);
+
+        #[cfg(feature = "std")]
+        impl FmtConst for $ty {
+            fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("::std::borrow::Cow::Borrowed(")?;
+                (**self).fmt_const(f)?;
+                f.write_str(")")
+            }
+        }
+    );
+);
+
+owned_impl!(std::boxed::Box<str> => str, delegate_fmt);
+owned_impl!(std::sync::Arc<str> => str, delegate_fmt);
+owned_impl!(std::rc::Rc<str> => str, delegate_fmt);
+owned_impl!(std::borrow::Cow<'_, str> => str, cow);
+owned_impl!(std::boxed::Box<[u8]> => [u8], delegate_fmt);
+owned_impl!(std::borrow::Cow<'_, [u8]> => [u8], cow);
+
 impl<'a, T: 'a + PhfHash + ?Sized> PhfHash for &'a T {
     fn phf_hash<H: Hasher>(&self, state: &mut H) {
         (*self).phf_hash(state)
@@ -275,6 +541,122 @@ impl<T> PhfBorrow<[T]> for &[T] {
     }
 }
 
+impl PhfHash for CStr {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        self.to_bytes().phf_hash(state)
+    }
+}
+
+// `c"..."` literals need edition 2021, so the `CStr` is built from a byte
+// string at compile time instead. The cast keeps Clippy from suggesting a
+// `c"..."` literal in the generated code.
+impl FmtConst for CStr {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("match ::core::ffi::CStr::from_bytes_with_nul(b\"")?;
+        for &byte in self.to_bytes() {
+            write!(f, "{}", core::ascii::escape_default(byte))?;
+        }
+        f.write_str(
+            "\\0\" as &[u8]) { ::core::result::Result::Ok(s) => s, \
+             ::core::result::Result::Err(_) => ::core::panic!() }",
+        )
+    }
+}
+
+impl PhfBorrow<CStr> for &CStr {
+    fn borrow(&self) -> &CStr {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl PhfBorrow<CStr> for std::ffi::CString {
+    fn borrow(&self) -> &CStr {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl PhfHash for std::ffi::CString {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        (**self).phf_hash(state)
+    }
+}
+
+// `OsStr` hashes its encoded bytes, which are the bytes of the string on Unix
+// and the UTF-8 bytes of valid Unicode on every platform, so it hashes like
+// the `str` or `[u8]` with the same contents. `Path` hashes its components
+// instead, since that's how paths compare: `a/b` and `a//b/` are the same key.
+// Neither has a `const` constructor, so static tables of paths store `&[u8]`
+// or `&str` keys, while runtime-built maps can store `&Path` keys directly.
+
+#[cfg(feature = "std")]
+impl PhfHash for std::ffi::OsStr {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        self.as_encoded_bytes().phf_hash(state)
+    }
+}
+
+#[cfg(feature = "std")]
+impl PhfHash for std::ffi::OsString {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        (**self).phf_hash(state)
+    }
+}
+
+#[cfg(feature = "std")]
+impl PhfHash for std::path::Path {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        for component in self.components() {
+            component.as_os_str().phf_hash(state);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl PhfHash for std::path::PathBuf {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        self.as_path().phf_hash(state)
+    }
+}
+
+#[cfg(feature = "std")]
+impl_reflexive!(std::ffi::OsStr, std::path::Path);
+
+#[cfg(feature = "std")]
+impl PhfBorrow<std::ffi::OsStr> for &std::ffi::OsStr {
+    fn borrow(&self) -> &std::ffi::OsStr {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl PhfBorrow<std::ffi::OsStr> for std::ffi::OsString {
+    fn borrow(&self) -> &std::ffi::OsStr {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl PhfBorrow<std::path::Path> for &std::path::Path {
+    fn borrow(&self) -> &std::path::Path {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl PhfBorrow<std::path::Path> for std::path::PathBuf {
+    fn borrow(&self) -> &std::path::Path {
+        self
+    }
+}
+
 impl<T, const N: usize> PhfBorrow<[T; N]> for &[T; N] {
     fn borrow(&self) -> &[T; N] {
         self
@@ -370,13 +752,153 @@ impl FmtConst for uncased::UncasedStr {
     }
 }
 
-#[cfg(feature = "uncased")]
-impl PhfBorrow<uncased::UncasedStr> for &uncased::UncasedStr {
-    fn borrow(&self) -> &uncased::UncasedStr {
-        self
+#[cfg(feature = "uncased")]
+impl PhfBorrow<uncased::UncasedStr> for &uncased::UncasedStr {
+    fn borrow(&self) -> &uncased::UncasedStr {
+        self
+    }
+}
+
+#[cfg(feature = "bstr")]
+impl PhfHash for bstr::BStr {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        (**self).phf_hash(state)
+    }
+}
+
+// `BStr` has no `const` constructor, so it prints as a byte slice, like
+// `Vec<u8>`.
+#[cfg(feature = "bstr")]
+impl FmtConst for bstr::BStr {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt_const(f)
+    }
+}
+
+#[cfg(feature = "bstr")]
+impl_reflexive!(bstr::BStr);
+
+#[cfg(feature = "bstr")]
+impl PhfBorrow<bstr::BStr> for &bstr::BStr {
+    fn borrow(&self) -> &bstr::BStr {
+        self
+    }
+}
+
+#[cfg(feature = "bstr")]
+impl PhfBorrow<[u8]> for &bstr::BStr {
+    fn borrow(&self) -> &[u8] {
+        self
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl PhfHash for bytes::Bytes {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        (**self).phf_hash(state)
+    }
+}
+
+// `Bytes` has interior mutability, so it can't be stored in a static table
+// and prints as a byte slice.
+#[cfg(feature = "bytes")]
+impl FmtConst for bytes::Bytes {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt_const(f)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl PhfBorrow<[u8]> for bytes::Bytes {
+    fn borrow(&self) -> &[u8] {
+        self
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl_reflexive!(bytes::Bytes);
+
+#[cfg(feature = "smol_str")]
+impl PhfHash for smol_str::SmolStr {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().phf_hash(state)
+    }
+}
+
+#[cfg(feature = "smol_str")]
+impl FmtConst for smol_str::SmolStr {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SmolStr::new_static(")?;
+        self.as_str().fmt_const(f)?;
+        f.write_str(")")
+    }
+}
+
+#[cfg(feature = "smol_str")]
+impl PhfBorrow<str> for smol_str::SmolStr {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+#[cfg(feature = "smol_str")]
+impl_reflexive!(smol_str::SmolStr);
+
+#[cfg(feature = "compact_str")]
+impl PhfHash for compact_str::CompactString {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().phf_hash(state)
+    }
+}
+
+#[cfg(feature = "compact_str")]
+impl FmtConst for compact_str::CompactString {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CompactString::const_new(")?;
+        self.as_str().fmt_const(f)?;
+        f.write_str(")")
+    }
+}
+
+#[cfg(feature = "compact_str")]
+impl PhfBorrow<str> for compact_str::CompactString {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+#[cfg(feature = "compact_str")]
+impl_reflexive!(compact_str::CompactString);
+
+#[cfg(feature = "uuid")]
+impl PhfHash for uuid::Uuid {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().phf_hash(state)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl FmtConst for uuid::Uuid {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Uuid::from_u128({:#034x})", self.as_u128())
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl PhfBorrow<[u8; 16]> for uuid::Uuid {
+    fn borrow(&self) -> &[u8; 16] {
+        self.as_bytes()
     }
 }
 
+#[cfg(feature = "uuid")]
+impl_reflexive!(uuid::Uuid);
+
 macro_rules! integer_impl (
     ($t:ty) => (
         impl PhfHash for $t {
@@ -671,7 +1193,406 @@ tuple_eq_impl!(
     (L, l, LT, lt)
 );
 
+impl PhfHash for () {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+impl FmtConst for () {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("()")
+    }
+}
+
+impl<T: PhfHash> PhfHash for Option<T> {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            None => state.write_u8(0),
+            Some(value) => {
+                state.write_u8(1);
+                value.phf_hash(state);
+            }
+        }
+    }
+}
+
+impl<T: FmtConst> FmtConst for Option<T> {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            None => f.write_str("::core::option::Option::None"),
+            Some(value) => {
+                f.write_str("::core::option::Option::Some(")?;
+                value.fmt_const(f)?;
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+// Like tuples, `Option` and `Result` compare their contents with `PartialEq`
+// so that references inside them can use shorter lifetimes at lookup time.
+impl<T: PartialEq<U>, U> PhfEq<Option<U>> for Option<T> {
+    fn phf_eq(&self, other: &Option<U>) -> bool {
+        match (self, other) {
+            (Some(left), Some(right)) => left == right,
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: PhfHash, E: PhfHash> PhfHash for Result<T, E> {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Ok(value) => {
+                state.write_u8(0);
+                value.phf_hash(state);
+            }
+            Err(error) => {
+                state.write_u8(1);
+                error.phf_hash(state);
+            }
+        }
+    }
+}
+
+impl<T: FmtConst, E: FmtConst> FmtConst for Result<T, E> {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ok(value) => {
+                f.write_str("::core::result::Result::Ok(")?;
+                value.fmt_const(f)?;
+            }
+            Err(error) => {
+                f.write_str("::core::result::Result::Err(")?;
+                error.fmt_const(f)?;
+            }
+        }
+        f.write_str(")")
+    }
+}
+
+impl<T: PartialEq<U>, U, E: PartialEq<F>, F> PhfEq<Result<U, F>> for Result<T, E> {
+    fn phf_eq(&self, other: &Result<U, F>) -> bool {
+        match (self, other) {
+            (Ok(left), Ok(right)) => left == right,
+            (Err(left), Err(right)) => left == right,
+            _ => false,
+        }
+    }
+}
+
+impl PhfHash for Ordering {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        (*self as i8).phf_hash(state)
+    }
+}
+
+impl FmtConst for Ordering {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "::core::cmp::Ordering::{:?}", self)
+    }
+}
+
+macro_rules! nonzero_impl (
+    ($t:ident) => (
+        // Hashes like the integer it wraps.
+        impl PhfHash for $t {
+            #[inline]
+            fn phf_hash<H: Hasher>(&self, state: &mut H) {
+                self.get().phf_hash(state)
+            }
+        }
+
+        impl FmtConst for $t {
+            fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, concat!("::core::num::", stringify!($t), "::new({:?}).unwrap()"), self.get())
+            }
+        }
+
+        impl_reflexive!($t);
+    )
+);
+
+nonzero_impl!(NonZeroU8);
+nonzero_impl!(NonZeroI8);
+nonzero_impl!(NonZeroU16);
+nonzero_impl!(NonZeroI16);
+nonzero_impl!(NonZeroU32);
+nonzero_impl!(NonZeroI32);
+nonzero_impl!(NonZeroU64);
+nonzero_impl!(NonZeroI64);
+nonzero_impl!(NonZeroUsize);
+nonzero_impl!(NonZeroIsize);
+nonzero_impl!(NonZeroU128);
+nonzero_impl!(NonZeroI128);
+
+impl PhfHash for Ipv4Addr {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        self.octets().phf_hash(state)
+    }
+}
+
+impl FmtConst for Ipv4Addr {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d] = self.octets();
+        write!(f, "::core::net::Ipv4Addr::new({a}, {b}, {c}, {d})")
+    }
+}
+
+impl PhfHash for Ipv6Addr {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        self.octets().phf_hash(state)
+    }
+}
+
+impl FmtConst for Ipv6Addr {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d, e, ff, g, h] = self.segments();
+        write!(
+            f,
+            "::core::net::Ipv6Addr::new({a:#x}, {b:#x}, {c:#x}, {d:#x}, {e:#x}, {ff:#x}, {g:#x}, {h:#x})"
+        )
+    }
+}
+
+impl PhfHash for IpAddr {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            IpAddr::V4(addr) => {
+                state.write_u8(0);
+                addr.phf_hash(state);
+            }
+            IpAddr::V6(addr) => {
+                state.write_u8(1);
+                addr.phf_hash(state);
+            }
+        }
+    }
+}
+
+impl FmtConst for IpAddr {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpAddr::V4(addr) => {
+                f.write_str("::core::net::IpAddr::V4(")?;
+                addr.fmt_const(f)?;
+            }
+            IpAddr::V6(addr) => {
+                f.write_str("::core::net::IpAddr::V6(")?;
+                addr.fmt_const(f)?;
+            }
+        }
+        f.write_str(")")
+    }
+}
+
+impl PhfHash for SocketAddrV4 {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        self.ip().phf_hash(state);
+        self.port().phf_hash(state);
+    }
+}
+
+impl FmtConst for SocketAddrV4 {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("::core::net::SocketAddrV4::new(")?;
+        self.ip().fmt_const(f)?;
+        write!(f, ", {})", self.port())
+    }
+}
+
+impl PhfHash for SocketAddrV6 {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        self.ip().phf_hash(state);
+        self.port().phf_hash(state);
+        self.flowinfo().phf_hash(state);
+        self.scope_id().phf_hash(state);
+    }
+}
+
+impl FmtConst for SocketAddrV6 {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("::core::net::SocketAddrV6::new(")?;
+        self.ip().fmt_const(f)?;
+        write!(
+            f,
+            ", {}, {}, {})",
+            self.port(),
+            self.flowinfo(),
+            self.scope_id()
+        )
+    }
+}
+
+impl PhfHash for SocketAddr {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            SocketAddr::V4(addr) => {
+                state.write_u8(0);
+                addr.phf_hash(state);
+            }
+            SocketAddr::V6(addr) => {
+                state.write_u8(1);
+                addr.phf_hash(state);
+            }
+        }
+    }
+}
+
+impl FmtConst for SocketAddr {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SocketAddr::V4(addr) => {
+                f.write_str("::core::net::SocketAddr::V4(")?;
+                addr.fmt_const(f)?;
+            }
+            SocketAddr::V6(addr) => {
+                f.write_str("::core::net::SocketAddr::V6(")?;
+                addr.fmt_const(f)?;
+            }
+        }
+        f.write_str(")")
+    }
+}
+
+impl PhfHash for Duration {
+    #[inline]
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        self.as_secs().phf_hash(state);
+        self.subsec_nanos().phf_hash(state);
+    }
+}
+
+impl FmtConst for Duration {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "::core::time::Duration::new({}, {})",
+            self.as_secs(),
+            self.subsec_nanos()
+        )
+    }
+}
+
+impl_reflexive!(
+    (),
+    Ordering,
+    Ipv4Addr,
+    Ipv6Addr,
+    IpAddr,
+    SocketAddrV4,
+    SocketAddrV6,
+    SocketAddr,
+    Duration
+);
+
+macro_rules! total_float_impl (
+    ($name:ident, $float:ident, $bits:ident) => (
+        #[doc = concat!("An `", stringify!($float), "` key that compares with [`", stringify!($float), "::total_cmp`].")]
+        ///
+        /// Floats aren't `Eq`, so they can't be used as keys directly. This wrapper
+        /// compares by total order instead of IEEE 754 equality, so `-0.0` and `0.0`
+        /// are different keys and a NaN equals a NaN with the same bit pattern. It
+        /// hashes the bit pattern, which is the same on every target.
+        ///
+        #[doc = concat!("Generated code constructs it as `", stringify!($name), "(..)` or `", stringify!($name), "::from_bits(..)`, so it")]
+        /// must be in scope there.
+        #[derive(Clone, Copy, Debug, Default)]
+        #[repr(transparent)]
+        pub struct $name(pub $float);
+
+        impl $name {
+            #[doc = concat!("Wraps an `", stringify!($float), "`.")]
+            pub const fn new(value: $float) -> Self {
+                Self(value)
+            }
+
+            /// Creates a key from the bit pattern of a float.
+            pub const fn from_bits(bits: $bits) -> Self {
+                Self($float::from_bits(bits))
+            }
+
+            /// Returns the wrapped float.
+            pub const fn get(self) -> $float {
+                self.0
+            }
+
+            /// Returns the bit pattern of the wrapped float.
+            pub const fn to_bits(self) -> $bits {
+                self.0.to_bits()
+            }
+        }
+
+        impl From<$float> for $name {
+            fn from(value: $float) -> Self {
+                Self(value)
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.to_bits() == other.to_bits()
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.total_cmp(&other.0)
+            }
+        }
+
+        impl Hash for $name {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.to_bits().hash(state)
+            }
+        }
+
+        impl PhfHash for $name {
+            #[inline]
+            fn phf_hash<H: Hasher>(&self, state: &mut H) {
+                self.to_bits().phf_hash(state)
+            }
+        }
+
+        impl FmtConst for $name {
+            fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                // `Debug` round-trips finite floats, including the sign of zero.
+                if self.0.is_finite() {
+                    write!(f, concat!(stringify!($name), "({:?}", stringify!($float), ")"), self.0)
+                } else {
+                    write!(f, concat!(stringify!($name), "::from_bits({:#x})"), self.to_bits())
+                }
+            }
+        }
+
+        impl_reflexive!($name);
+    )
+);
+
+total_float_impl!(TotalF32, f32, u32);
+total_float_impl!(TotalF64, f64, u64);
+
 #[cfg(test)]
+// Keys are hashed through references on purpose, to cover the `&T` impls.
+#[allow(clippy::needless_borrows_for_generic_args)]
 mod tests {
     use super::*;
 
@@ -806,6 +1727,56 @@ mod tests {
         assert!(assert_mixed_tuple(&(1, a.as_str())));
     }
 
+    #[test]
+    fn byte_chunks_hash_like_concatenation() {
+        let chunks = |parts: &'static [&'static str]| {
+            ByteChunks::new(parts.iter().map(|part| part.as_bytes()))
+        };
+
+        assert_eq!(
+            test_hash(chunks(&["ab", "", "c"])),
+            [
+                HashCall::U64(3),
+                HashCall::Bytes(b"ab".to_vec()),
+                HashCall::Bytes(b"".to_vec()),
+                HashCall::Bytes(b"c".to_vec()),
+            ]
+        );
+
+        let key = 0x1234_5678;
+        let concatenated = "server.http.port.with.a.key.longer.than.one.sip.block";
+        let expected = hash(concatenated, &key);
+        let actual = hash(
+            &chunks(&[
+                "server",
+                ".http.",
+                "port.with.a",
+                ".key.longer.than.one.sip",
+                ".block",
+            ]),
+            &key,
+        );
+        assert_eq!(
+            (expected.g, expected.f1, expected.f2),
+            (actual.g, actual.f1, actual.f2)
+        );
+        assert_eq!(
+            hash(&b""[..], &key).f1,
+            hash(&chunks(&[]), &key).f1,
+            "no chunks must hash like the empty string"
+        );
+    }
+
+    #[test]
+    fn byte_chunks_compare_piecewise() {
+        let chunks = ByteChunks::new(["ab", "", "cd"].into_iter().map(str::as_bytes));
+        assert_eq!(chunks.len(), 4);
+        assert!(chunks.eq_bytes(b"abcd"));
+        assert!(!chunks.eq_bytes(b"abc"));
+        assert!(!chunks.eq_bytes(b"abcde"));
+        assert!(!chunks.eq_bytes(b"abce"));
+    }
+
     #[test]
     fn variable_width_slice_elements_are_delimited() {
         assert_ne!(test_hash(&["ab", "c"]), test_hash(&["a", "bc"]));
@@ -818,4 +1789,165 @@ mod tests {
             "different string arrays must not produce identical PHF hashes"
         );
     }
+
+    #[test]
+    fn ascii_no_case_hashes_like_lowercased_bytes() {
+        let long = "X-Custom-Header-".repeat(10);
+        for s in ["", "Content-Type", "ÉTAGE-Überfall", &long] {
+            let lower = s.to_ascii_lowercase();
+            let expected = hash(lower.as_bytes(), &7).g;
+            assert_eq!(hash(&AsciiNoCase(s), &7).g, expected);
+            assert_eq!(hash(&AsciiNoCase(s.as_bytes()), &7).g, expected);
+        }
+        assert!(AsciiNoCase("Content-Type").phf_eq(&AsciiNoCase(&b"CONTENT-type"[..])));
+        assert!(!AsciiNoCase("É").phf_eq(&AsciiNoCase("é")));
+    }
+
+    #[test]
+    fn normalized_keys_hash_like_normalized_strs() {
+        use crate::normalize::{DashUnderscore, Lowercase, Normalized, Trim};
+
+        type Key<'a> = Normalized<(Trim, Lowercase, DashUnderscore), &'a str>;
+
+        let long = format!("  {}\t", "Long_Key ".repeat(20));
+        for (key, normalized) in [
+            ("", ""),
+            ("  ", ""),
+            (" No_Color\n", "no-color"),
+            ("A  B ", "a  b"),
+            ("İ", "i\u{307}"),
+            (&long, long.trim().to_lowercase().replace('_', "-").as_str()),
+        ] {
+            let key = Key::new(key);
+            assert_eq!(key.chars().collect::<String>(), normalized);
+            assert_eq!(hash(&key, &7).g, hash(normalized, &7).g);
+            assert!(key.phf_eq(&Key::new(normalized)));
+        }
+        assert!(!Key::new("a b").phf_eq(&Key::new("ab")));
+    }
+
+    #[test]
+    fn trim_keeps_long_inner_runs() {
+        use crate::normalize::{Normalized, Trim};
+
+        let inner = " \t".repeat(50_000);
+        let key = format!("\n a{}b{}", inner, " ".repeat(100_000));
+        let trimmed = Normalized::<Trim, _>::new(key.as_str());
+        assert_eq!(trimmed.chars().collect::<String>(), format!("a{}b", inner));
+        assert_eq!(hash(&trimmed, &7).g, hash(key.trim(), &7).g);
+    }
+
+    #[test]
+    fn ffi_keys_hash_like_their_bytes() {
+        use std::ffi::{CString, OsStr};
+        use std::path::Path;
+
+        let expected = hash("dir/file.txt", &7).g;
+        assert_eq!(hash(c"dir/file.txt", &7).g, expected);
+        assert_eq!(hash(&CString::new("dir/file.txt").unwrap(), &7).g, expected);
+        assert_eq!(hash(OsStr::new("dir/file.txt"), &7).g, expected);
+        assert_eq!(
+            hash(Path::new("dir//file.txt/"), &7).g,
+            hash(Path::new("dir/file.txt"), &7).g
+        );
+
+        struct Const<'a>(&'a CStr);
+        impl fmt::Display for Const<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt_const(f)
+            }
+        }
+        assert_eq!(
+            Const(c"a\"b\\\x7f\xff").to_string(),
+            r#"match ::core::ffi::CStr::from_bytes_with_nul(b"a\"b\\\x7f\xff\0" as &[u8]) { "#
+                .to_owned()
+                + "::core::result::Result::Ok(s) => s, "
+                + "::core::result::Result::Err(_) => ::core::panic!() }"
+        );
+    }
+
+    #[test]
+    fn owned_keys_hash_like_borrowed_keys() {
+        use std::borrow::Cow;
+        use std::rc::Rc;
+        use std::sync::Arc;
+
+        let expected = hash("key", &7).g;
+        assert_eq!(hash(&Box::<str>::from("key"), &7).g, expected);
+        assert_eq!(hash(&Arc::<str>::from("key"), &7).g, expected);
+        assert_eq!(hash(&Rc::<str>::from("key"), &7).g, expected);
+        assert_eq!(hash(&Cow::<str>::Owned("key".into()), &7).g, expected);
+        assert_eq!(hash(&Box::<[u8]>::from(&b"key"[..]), &7).g, expected);
+        assert_eq!(hash(&Cow::<[u8]>::Borrowed(b"key"), &7).g, expected);
+        assert!(Arc::<str>::from("key").phf_eq("key"));
+        assert!(Cow::<[u8]>::Borrowed(b"key").phf_eq(&b"key"[..]));
+    }
+
+    #[test]
+    fn std_key_types_hash_portably() {
+        assert_eq!(test_hash(()), []);
+        assert_eq!(test_hash(None::<u32>), [HashCall::U8(0)]);
+        assert_eq!(test_hash(Some(7u32)), [HashCall::U8(1), HashCall::U32(7)]);
+        assert_ne!(test_hash(Ok::<u8, u8>(1)), test_hash(Err::<u8, u8>(1)));
+        assert_eq!(test_hash(NonZeroU32::new(7).unwrap()), test_hash(7u32));
+        assert_eq!(test_hash(Ordering::Less), test_hash(-1i8));
+        assert_eq!(
+            test_hash(Ipv4Addr::new(127, 0, 0, 1)),
+            [HashCall::U64(4), HashCall::Bytes([127, 0, 0, 1].to_vec())]
+        );
+        assert_ne!(
+            test_hash(IpAddr::from(Ipv4Addr::LOCALHOST)),
+            test_hash(IpAddr::from(Ipv4Addr::LOCALHOST.to_ipv6_mapped()))
+        );
+        assert_eq!(
+            test_hash(Duration::new(3, 5)),
+            [HashCall::U64(3), HashCall::U32(5)]
+        );
+    }
+
+    #[test]
+    fn total_floats_compare_and_hash_bit_patterns() {
+        assert_eq!(TotalF64(1.5), TotalF64::from(1.5));
+        assert_ne!(TotalF64(0.0), TotalF64(-0.0));
+        assert_eq!(TotalF32(f32::NAN), TotalF32(f32::NAN));
+        assert_ne!(TotalF32(f32::NAN), TotalF32(-f32::NAN));
+        assert!(TotalF64(-0.0) < TotalF64(0.0));
+        assert_eq!(test_hash(TotalF32(1.0)), [HashCall::U32(0x3f80_0000)]);
+        assert_eq!(
+            test_hash(TotalF64(-2.0)),
+            [HashCall::U64(0xc000_0000_0000_0000)]
+        );
+    }
+
+    #[test]
+    fn option_and_result_eq_allow_shorter_reference_lifetimes() {
+        fn assert_option<'a>(key: &Option<&'a str>) -> bool
+        where
+            Option<&'static str>: PhfEq<Option<&'a str>>,
+        {
+            Some("a").phf_eq(key)
+        }
+
+        fn assert_result<'a>(key: &Result<&'a str, u8>) -> bool
+        where
+            Result<&'static str, u8>: PhfEq<Result<&'a str, u8>>,
+        {
+            Ok::<_, u8>("a").phf_eq(key)
+        }
+
+        let a = String::from("a");
+        assert!(assert_option(&Some(a.as_str())));
+        assert!(!assert_option(&None));
+        assert!(assert_result(&Ok(a.as_str())));
+        assert!(!assert_result(&Err(0)));
+    }
+
+    #[test]
+    fn hash_does_not_depend_on_size_feature() {
+        let key = 0x0123_4567_89ab_cdef;
+        let value = ("abc", 7u32, [-1i64, 2], 'x');
+        let mut hasher = PortableSipHasher::new(SipHasher13::new_with_keys(0, key));
+        value.phf_hash(&mut hasher);
+        assert_eq!(hash(&value, &key), hashes(&hasher));
+    }
 }
@@ -0,0 +1,277 @@
+//! Keys that are normalized before they are hashed or compared.
+//!
+//! A [`Normalized`] key carries its [`KeyNormalizer`] as a type parameter, so
+//! the map stores it in its key type: a `Map<Normalized<Trim, &str>, V>` is
+//! built from trimmed keys and trims lookup keys as it hashes them.
+//! Normalizers work on `char` iterators, so lookups don't allocate.
+//!
+//! Generators compare keys in their normalized form, so keys that only differ
+//! before normalization are reported as duplicates.
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+
+use crate::{FmtConst, PhfBorrow, PhfHash};
+
+/// A transformation applied to string keys before they are hashed or compared.
+///
+/// A tuple of normalizers applies them from left to right. Tuple impls are
+/// provided up to 4 elements.
+pub trait KeyNormalizer {
+    /// Returns the normalized form of `chars`.
+    fn normalize<I>(chars: I) -> impl Iterator<Item = char> + Clone
+    where
+        I: Iterator<Item = char> + Clone;
+}
+
+/// Strips leading and trailing whitespace.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Trim;
+
+impl KeyNormalizer for Trim {
+    fn normalize<I>(chars: I) -> impl Iterator<Item = char> + Clone
+    where
+        I: Iterator<Item = char> + Clone,
+    {
+        Trimmed {
+            chars,
+            started: false,
+            run: None,
+            pending: 0,
+            peeked: None,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Trimmed<I> {
+    chars: I,
+    started: bool,
+    // The rest of an inner whitespace run, which `run` yields `pending` of,
+    // and the character that ends it.
+    run: Option<I>,
+    pending: usize,
+    peeked: Option<char>,
+}
+
+impl<I> Iterator for Trimmed<I>
+where
+    I: Iterator<Item = char> + Clone,
+{
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.pending > 0 {
+            self.pending -= 1;
+            return self.run.as_mut().and_then(Iterator::next);
+        }
+        if let Some(c) = self.peeked.take() {
+            return Some(c);
+        }
+        loop {
+            let c = self.chars.next()?;
+            if !c.is_whitespace() {
+                self.started = true;
+                return Some(c);
+            }
+            if !self.started {
+                continue;
+            }
+            // Scan to the end of the run once. Inner whitespace is kept,
+            // trailing whitespace isn't.
+            let run = self.chars.clone();
+            let mut pending = 0;
+            loop {
+                match self.chars.next() {
+                    Some(next) if next.is_whitespace() => pending += 1,
+                    Some(next) => {
+                        self.peeked = Some(next);
+                        break;
+                    }
+                    None => return None,
+                }
+            }
+            self.run = Some(run);
+            self.pending = pending;
+            return Some(c);
+        }
+    }
+}
+
+/// Lowercases keys with [`char::to_lowercase`], which is close to, but not
+/// the same as, Unicode case folding.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Lowercase;
+
+impl KeyNormalizer for Lowercase {
+    fn normalize<I>(chars: I) -> impl Iterator<Item = char> + Clone
+    where
+        I: Iterator<Item = char> + Clone,
+    {
+        chars.flat_map(char::to_lowercase)
+    }
+}
+
+/// Lowercases ASCII letters and leaves other characters alone.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AsciiLowercase;
+
+impl KeyNormalizer for AsciiLowercase {
+    fn normalize<I>(chars: I) -> impl Iterator<Item = char> + Clone
+    where
+        I: Iterator<Item = char> + Clone,
+    {
+        chars.map(|c| c.to_ascii_lowercase())
+    }
+}
+
+/// Treats `_` and `-` as the same character.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DashUnderscore;
+
+impl KeyNormalizer for DashUnderscore {
+    fn normalize<I>(chars: I) -> impl Iterator<Item = char> + Clone
+    where
+        I: Iterator<Item = char> + Clone,
+    {
+        chars.map(|c| if c == '_' { '-' } else { c })
+    }
+}
+
+/// Converts keys to Unicode Normalization Form C.
+///
+/// Requires the `unicode-normalization` feature.
+#[cfg(feature = "unicode-normalization")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Nfc;
+
+#[cfg(feature = "unicode-normalization")]
+impl KeyNormalizer for Nfc {
+    fn normalize<I>(chars: I) -> impl Iterator<Item = char> + Clone
+    where
+        I: Iterator<Item = char> + Clone,
+    {
+        unicode_normalization::UnicodeNormalization::nfc(chars)
+    }
+}
+
+macro_rules! tuple_normalizer_impl {
+    ($first:ident $(, $rest:ident)*) => {
+        impl<$first: KeyNormalizer, $($rest: KeyNormalizer),*> KeyNormalizer for ($first, $($rest,)*) {
+            fn normalize<I>(chars: I) -> impl Iterator<Item = char> + Clone
+            where
+                I: Iterator<Item = char> + Clone,
+            {
+                let chars = $first::normalize(chars);
+                $(let chars = $rest::normalize(chars);)*
+                chars
+            }
+        }
+    };
+}
+
+tuple_normalizer_impl!(A);
+tuple_normalizer_impl!(A, B);
+tuple_normalizer_impl!(A, B, C);
+tuple_normalizer_impl!(A, B, C, D);
+
+/// A string key that is normalized by `N` before it is hashed or compared.
+///
+/// `Normalized` hashes the same as its normalized form as a `str`. The key
+/// itself is stored as written.
+pub struct Normalized<N, S> {
+    marker: PhantomData<fn() -> N>,
+    key: S,
+}
+
+impl<N, S> Normalized<N, S> {
+    /// Wraps a key.
+    pub const fn new(key: S) -> Self {
+        Normalized {
+            marker: PhantomData,
+            key,
+        }
+    }
+
+    /// Returns the key as written.
+    pub fn get(&self) -> &S {
+        &self.key
+    }
+
+    /// Returns the key as written.
+    pub fn into_inner(self) -> S {
+        self.key
+    }
+}
+
+impl<N: KeyNormalizer, S: AsRef<str>> Normalized<N, S> {
+    /// Returns the characters of the normalized key.
+    pub fn chars(&self) -> impl Iterator<Item = char> + Clone + '_ {
+        N::normalize(self.key.as_ref().chars())
+    }
+}
+
+impl<N, S: Clone> Clone for Normalized<N, S> {
+    fn clone(&self) -> Self {
+        Normalized::new(self.key.clone())
+    }
+}
+
+impl<N, S: Copy> Copy for Normalized<N, S> {}
+
+impl<N, S: fmt::Debug> fmt::Debug for Normalized<N, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Normalized").field(&self.key).finish()
+    }
+}
+
+impl<N: KeyNormalizer, S: AsRef<str>, T: AsRef<str>> PartialEq<Normalized<N, T>>
+    for Normalized<N, S>
+{
+    fn eq(&self, other: &Normalized<N, T>) -> bool {
+        self.chars().eq(other.chars())
+    }
+}
+
+impl<N: KeyNormalizer, S: AsRef<str>> Eq for Normalized<N, S> {}
+
+impl<N: KeyNormalizer, S: AsRef<str>> Hash for Normalized<N, S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.phf_hash(state)
+    }
+}
+
+impl<N: KeyNormalizer, S: AsRef<str>> PhfHash for Normalized<N, S> {
+    fn phf_hash<H: Hasher>(&self, state: &mut H) {
+        // The length prefix of a `str` comes first, so the key is normalized
+        // twice rather than into an allocation.
+        let chars = self.chars();
+        let len = chars.clone().map(char::len_utf8).sum::<usize>();
+        state.write_u64(len as u64);
+        let mut buf = [0; 64];
+        let mut filled = 0;
+        for c in chars {
+            if filled + c.len_utf8() > buf.len() {
+                state.write(&buf[..filled]);
+                filled = 0;
+            }
+            filled += c.encode_utf8(&mut buf[filled..]).len();
+        }
+        state.write(&buf[..filled]);
+    }
+}
+
+impl<N, S: FmtConst> FmtConst for Normalized<N, S> {
+    fn fmt_const(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Normalized::new(")?;
+        self.key.fmt_const(f)?;
+        f.write_str(")")
+    }
+}
+
+impl<'b, 'a: 'b, N, S: ?Sized + 'a> PhfBorrow<Normalized<N, &'b S>> for Normalized<N, &'a S> {
+    fn borrow(&self) -> &Normalized<N, &'b S> {
+        self
+    }
+}
@@ -4,6 +4,7 @@ use siphasher::sip::SipHasher13;
 use crate::{HashKey, PhfHash, PortableSipHasher};
 
 /// `key` is from `phf_generator::ptrhash::HashState`.
+#[cfg(not(feature = "size"))]
 #[inline]
 pub fn hash<T: ?Sized + PhfHash>(x: &T, key: &HashKey) -> u64 {
     let mut hasher = PortableSipHasher::new(SipHasher13::new_with_keys(0, *key));
@@ -11,6 +12,29 @@ pub fn hash<T: ?Sized + PhfHash>(x: &T, key: &HashKey) -> u64 {
     hasher.finish()
 }
 
+/// `key` is from `phf_generator::ptrhash::HashState`.
+#[cfg(feature = "size")]
+#[inline]
+pub fn hash<T: ?Sized + PhfHash>(x: &T, key: &HashKey) -> u64 {
+    hash_dyn(key, &mut |mut state| x.phf_hash(&mut state))
+}
+
+/// Hashes the bytes `feed` writes, through a `dyn Hasher` so that the SipHash
+/// implementation is shared by every key type.
+#[cfg(feature = "size")]
+#[inline(never)]
+fn hash_dyn(key: &HashKey, feed: &mut dyn FnMut(&mut dyn Hasher)) -> u64 {
+    let mut hasher = PortableSipHasher::new(SipHasher13::new_with_keys(0, *key));
+    feed(&mut hasher);
+    hasher.finish()
+}
+
+/// Return bits for a fingerprint of the key `hash` was computed from.
+#[inline]
+pub fn fingerprint(hash: u64) -> u64 {
+    crate::fmix64(hash)
+}
+
 #[inline]
 pub fn hash_pilot(seed: u64, pilot: u8) -> u64 {
     const C: u64 = 0x517cc1b727220a95;